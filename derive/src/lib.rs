@@ -0,0 +1,195 @@
+//! Derive macros for Plonky2 circuit types.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Member, Path, PathArguments,
+    Result,
+};
+
+/// Derives `plonky2::iop::circuit_type::CircuitType` for a struct whose fields all implement it,
+/// such as `Target`, `BoolTarget`, `HashOutTarget`, `U32Target`, arrays of those, or other
+/// structs deriving `CircuitType`.
+///
+/// By default, the native type is a generated struct named after the target struct with a
+/// `Native` suffix, generic over the field `F` and having the same fields, each holding the
+/// native value of the corresponding target. Generic structs must instead name an existing
+/// native type with matching fields, e.g. `#[circuit_type(native = ECDSASignature<C>)]`.
+#[proc_macro_derive(CircuitType, attributes(circuit_type))]
+pub fn derive_circuit_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_circuit_type(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_circuit_type(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) if !matches!(data.fields, Fields::Unit) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "CircuitType can only be derived for structs with fields",
+            ))
+        }
+    };
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        })
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let name = &input.ident;
+    let native_path = parse_native_attr(&input)?;
+    let has_generics = !input.generics.params.is_empty();
+
+    let krate = quote!(::plonky2);
+    let circuit_type = quote!(#krate::iop::circuit_type::CircuitType<__F>);
+
+    // Either use the given native type, or generate one.
+    let (native_def, native_ty, native_expr) = match native_path {
+        Some(path) => {
+            let expr = expr_style(path.clone());
+            (quote!(), quote!(#path), quote!(#expr))
+        }
+        None if has_generics => {
+            return Err(Error::new(
+                input.generics.span(),
+                "generic structs must specify their native type with #[circuit_type(native = ...)]",
+            ));
+        }
+        None => {
+            let native_name = format_ident!("{}Native", name);
+            let vis = &input.vis;
+            let doc = format!("The native value of a [`{name}`].");
+            let body = match fields {
+                Fields::Named(_) => {
+                    let field_defs = fields.iter().map(|field| {
+                        let vis = &field.vis;
+                        let ident = &field.ident;
+                        let ty = &field.ty;
+                        quote!(#vis #ident: <#ty as #krate::iop::circuit_type::CircuitType<F>>::Native)
+                    });
+                    quote!({ #(#field_defs),* })
+                }
+                Fields::Unnamed(_) => {
+                    let field_defs = fields.iter().map(|field| {
+                        let vis = &field.vis;
+                        let ty = &field.ty;
+                        quote!(#vis <#ty as #krate::iop::circuit_type::CircuitType<F>>::Native)
+                    });
+                    quote!(( #(#field_defs),* );)
+                }
+                Fields::Unit => unreachable!(),
+            };
+            let def = quote! {
+                #[doc = #doc]
+                #[derive(Clone, Debug)]
+                #vis struct #native_name<F: #krate::hash::hash_types::RichField> #body
+            };
+            (def, quote!(#native_name<__F>), quote!(#native_name::<__F>))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(__F: #krate::hash::hash_types::RichField));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #native_def
+
+        impl #impl_generics #circuit_type for #name #ty_generics #where_clause {
+            type Native = #native_ty;
+
+            fn add_virtual<const __D: usize>(
+                builder: &mut #krate::plonk::circuit_builder::CircuitBuilder<__F, __D>,
+            ) -> Self
+            where
+                __F: #krate::field::extension::Extendable<__D>,
+            {
+                Self {
+                    #(#members: <#types as #circuit_type>::add_virtual(builder),)*
+                }
+            }
+
+            fn set_witness<__W: #krate::iop::witness::WitnessWrite<__F>>(
+                &self,
+                witness: &mut __W,
+                value: &Self::Native,
+            ) {
+                #(<#types as #circuit_type>::set_witness(&self.#members, witness, &value.#members);)*
+            }
+
+            fn get_witness<__W: #krate::iop::witness::Witness<__F>>(
+                &self,
+                witness: &__W,
+            ) -> Self::Native {
+                #native_expr {
+                    #(#members: <#types as #circuit_type>::get_witness(&self.#members, witness),)*
+                }
+            }
+
+            fn connect<const __D: usize>(
+                builder: &mut #krate::plonk::circuit_builder::CircuitBuilder<__F, __D>,
+                x: &Self,
+                y: &Self,
+            ) where
+                __F: #krate::field::extension::Extendable<__D>,
+            {
+                #(<#types as #circuit_type>::connect(builder, &x.#members, &y.#members);)*
+            }
+
+            fn select<const __D: usize>(
+                builder: &mut #krate::plonk::circuit_builder::CircuitBuilder<__F, __D>,
+                b: #krate::iop::target::BoolTarget,
+                x: &Self,
+                y: &Self,
+            ) -> Self
+            where
+                __F: #krate::field::extension::Extendable<__D>,
+            {
+                Self {
+                    #(#members: <#types as #circuit_type>::select(builder, b, &x.#members, &y.#members),)*
+                }
+            }
+        }
+    })
+}
+
+/// Parses the optional `#[circuit_type(native = ...)]` attribute.
+fn parse_native_attr(input: &DeriveInput) -> Result<Option<Path>> {
+    let mut native = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("circuit_type") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("native") {
+                native = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported circuit_type attribute"))
+            }
+        })?;
+    }
+    Ok(native)
+}
+
+/// Converts a type path such as `Foo<C>` into a path usable in expressions, like `Foo::<C>`.
+fn expr_style(mut path: Path) -> Path {
+    for segment in &mut path.segments {
+        if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+            args.colon2_token = Some(Default::default());
+        }
+    }
+    path
+}
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use plonky2::field::types::Sample;
+use plonky2::hash::hash_types::{HashOut, HashOutTarget};
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::PartialWitness;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+use plonky2_derive::CircuitType;
+use plonky2_u32::gadgets::arithmetic_u32::U32Target;
+
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+#[derive(Clone, Debug, CircuitType)]
+struct Note {
+    owner: HashOutTarget,
+    amount: U32Target,
+    spent: BoolTarget,
+    memo: [Target; 2],
+}
+
+#[derive(Clone, Debug, CircuitType)]
+struct Pair(Target, BoolTarget);
+
+#[derive(Clone, Debug, CircuitType)]
+struct Transfer {
+    input: Note,
+    output: Note,
+    flags: Pair,
+}
+
+fn random_note(amount: u32, spent: bool) -> NoteNative<F> {
+    NoteNative {
+        owner: HashOut::rand(),
+        amount,
+        spent,
+        memo: [F::rand(), F::rand()],
+    }
+}
+
+#[test]
+fn test_derived_witness_roundtrip() -> Result<()> {
+    let config = CircuitConfig::standard_recursion_config();
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let transfer = Transfer::add_virtual(&mut builder);
+
+    let value = TransferNative {
+        input: random_note(100, true),
+        output: random_note(u32::MAX, false),
+        flags: PairNative(F::rand(), true),
+    };
+    let mut pw = PartialWitness::new();
+    transfer.set_witness(&mut pw, &value);
+
+    let read = transfer.get_witness(&pw);
+    assert_eq!(read.input.owner, value.input.owner);
+    assert_eq!(read.output.amount, u32::MAX);
+    assert_eq!(read.flags.0, value.flags.0);
+    assert!(read.flags.1);
+
+    let data = builder.build::<C>();
+    let proof = data.prove(pw)?;
+    Ok(data.verify(proof)?)
+}
+
+#[test]
+fn test_derived_select_and_connect() -> Result<()> {
+    let config = CircuitConfig::standard_recursion_config();
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let x = Note::add_virtual(&mut builder);
+    let y = Note::add_virtual(&mut builder);
+    let b = BoolTarget::add_virtual(&mut builder);
+    let selected = Note::select(&mut builder, b, &x, &y);
+    let expected = Note::add_virtual(&mut builder);
+    Note::connect(&mut builder, &selected, &expected);
+
+    let x_value = random_note(1, false);
+    let y_value = random_note(2, true);
+    let mut pw = PartialWitness::new();
+    x.set_witness(&mut pw, &x_value);
+    y.set_witness(&mut pw, &y_value);
+    b.set_witness(&mut pw, &true);
+    expected.set_witness(&mut pw, &x_value);
+
+    let data = builder.build::<C>();
+    let proof = data.prove(pw)?;
+    Ok(data.verify(proof)?)
+}
@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2::field::types::Sample;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::witness::PartialWitness;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+use plonky2_ecdsa::curve::ecdsa::{sign_message, ECDSASecretKey};
+use plonky2_ecdsa::curve::secp256k1::Secp256K1;
+use plonky2_ecdsa::gadgets::curve::CircuitBuilderCurve;
+use plonky2_ecdsa::gadgets::ecdsa::{
+    batch_verify_message_circuit, ECDSAPublicKeyTarget, ECDSASignatureTarget,
+};
+use plonky2_ecdsa::gadgets::nonnative::CircuitBuilderNonNative;
+
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+fn bench_batch_ecdsa(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch-ecdsa");
+    group.sample_size(10);
+
+    for num_sigs in [16, 64, 256] {
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        let mut msg_targets = Vec::new();
+        let mut sig_targets = Vec::new();
+        let mut pk_targets = Vec::new();
+        for _ in 0..num_sigs {
+            let msg = Secp256K1Scalar::rand();
+            let sk = ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::rand());
+            let sig = sign_message(msg, sk);
+
+            let sig_target = ECDSASignatureTarget::<Secp256K1>::add_virtual(&mut builder);
+            sig_target.set_witness(&mut pw, &sig);
+            msg_targets.push(builder.constant_nonnative(msg));
+            sig_targets.push(sig_target);
+            pk_targets.push(ECDSAPublicKeyTarget(
+                builder.constant_affine_point(sk.to_public().0),
+            ));
+        }
+        batch_verify_message_circuit(&mut builder, &msg_targets, &sig_targets, &pk_targets);
+        println!(
+            "{num_sigs} signatures: {} gates per signature",
+            builder.num_gates() / num_sigs
+        );
+        let data = builder.build::<C>();
+
+        group.bench_with_input(BenchmarkId::from_parameter(num_sigs), &num_sigs, |b, _| {
+            b.iter(|| data.prove(pw.clone()).unwrap());
+        });
+    }
+}
+
+criterion_group!(benches, bench_batch_ecdsa);
+criterion_main!(benches);
@@ -1,8 +1,18 @@
-use plonky2::field::types::{Field, Sample};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use hmac::{Hmac, Mac};
+use keccak_hash::keccak;
+use num::BigUint;
+use plonky2::field::types::{Field, PrimeField};
+use plonky2::util::ceil_div_usize;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 
 use crate::curve::curve_msm::msm_parallel;
-use crate::curve::curve_types::{base_to_scalar, AffinePoint, Curve, CurveScalar};
+use crate::curve::curve_types::{base_to_scalar, scalar_to_base, AffinePoint, Curve, CurveScalar};
+use crate::curve::secp256k1::Secp256K1;
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct ECDSASignature<C: Curve> {
@@ -10,6 +20,106 @@ pub struct ECDSASignature<C: Curve> {
     pub s: C::ScalarField,
 }
 
+impl<C: Curve> ECDSASignature<C> {
+    /// Returns whether `s <= (n - 1) / 2`. Both `s` and `n - s` are valid, so some protocols only
+    /// accept the low one to make signatures non-malleable.
+    pub fn is_low_s(&self) -> bool {
+        self.s.to_canonical_biguint() <= C::ScalarField::order() >> 1
+    }
+
+    /// Returns the equivalent signature with a low `s`.
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            *self
+        } else {
+            Self {
+                r: self.r,
+                s: -self.s,
+            }
+        }
+    }
+
+    /// Returns the DER encoding of the signature, as an ASN.1 sequence of the integers `r` and
+    /// `s`.
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut content = Vec::new();
+        for x in [self.r, self.s] {
+            let mut bytes = x.to_canonical_biguint().to_bytes_be();
+            // DER integers are signed.
+            if bytes[0] & 0x80 != 0 {
+                bytes.insert(0, 0);
+            }
+            content.extend([DER_INTEGER, bytes.len() as u8]);
+            content.extend(bytes);
+        }
+        assert!(
+            content.len() < 0x80,
+            "Long-form DER lengths are not supported"
+        );
+
+        let mut der = vec![DER_SEQUENCE, content.len() as u8];
+        der.extend(content);
+        der
+    }
+
+    /// Parses a strict DER encoding, as produced by `to_der`. Returns `None` if the encoding is
+    /// invalid or non-canonical, or if `r` or `s` is not in `[1, n)`.
+    pub fn from_der(bytes: &[u8]) -> Option<Self> {
+        let (&tag, rest) = bytes.split_first()?;
+        let (&len, mut content) = rest.split_first()?;
+        if tag != DER_SEQUENCE || len as usize != content.len() {
+            return None;
+        }
+
+        let mut parse_integer = || {
+            let (&tag, rest) = content.split_first()?;
+            let (&len, rest) = rest.split_first()?;
+            if tag != DER_INTEGER || len == 0 || len as usize > rest.len() {
+                return None;
+            }
+            let (bytes, rest) = rest.split_at(len as usize);
+            content = rest;
+            // Negative integers and unnecessary leading zeros are rejected.
+            let minimal =
+                bytes[0] & 0x80 == 0 && !(bytes.len() > 1 && bytes[0] == 0 && bytes[1] & 0x80 == 0);
+            minimal.then(|| BigUint::from_bytes_be(bytes))
+        };
+        let r = parse_integer()?;
+        let s = parse_integer()?;
+        if !content.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            r: nonzero_scalar_from_biguint::<C>(r)?,
+            s: nonzero_scalar_from_biguint::<C>(s)?,
+        })
+    }
+
+    /// Returns the compact encoding of the signature: the big-endian `r` followed by the big-endian
+    /// `s`, each padded to the byte length of the scalar field.
+    pub fn to_compact(&self) -> Vec<u8> {
+        let len = field_byte_len::<C::ScalarField>();
+        let mut bytes = field_to_bytes_be(self.r, len);
+        bytes.extend(field_to_bytes_be(self.s, len));
+        bytes
+    }
+
+    /// Parses a compact encoding. Returns `None` if its length is wrong, or if `r` or `s` is not in
+    /// `[1, n)`.
+    pub fn from_compact(bytes: &[u8]) -> Option<Self> {
+        let len = field_byte_len::<C::ScalarField>();
+        if bytes.len() != 2 * len {
+            return None;
+        }
+        let (r, s) = bytes.split_at(len);
+        Some(Self {
+            r: nonzero_scalar_from_biguint::<C>(BigUint::from_bytes_be(r))?,
+            s: nonzero_scalar_from_biguint::<C>(BigUint::from_bytes_be(s))?,
+        })
+    }
+}
+
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct ECDSASecretKey<C: Curve>(pub C::ScalarField);
 
@@ -22,21 +132,187 @@ impl<C: Curve> ECDSASecretKey<C> {
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct ECDSAPublicKey<C: Curve>(pub AffinePoint<C>);
 
-pub fn sign_message<C: Curve>(msg: C::ScalarField, sk: ECDSASecretKey<C>) -> ECDSASignature<C> {
-    let (k, rr) = {
-        let mut k = C::ScalarField::rand();
-        let mut rr = (CurveScalar(k) * C::GENERATOR_PROJECTIVE).to_affine();
-        while rr.x == C::BaseField::ZERO {
-            k = C::ScalarField::rand();
-            rr = (CurveScalar(k) * C::GENERATOR_PROJECTIVE).to_affine();
+impl<C: Curve> ECDSAPublicKey<C> {
+    /// Returns the SEC1 encoding of the key: `0x04` followed by the big-endian coordinates, or if
+    /// `compressed`, `0x02` or `0x03` depending on the parity of `y`, followed by the big-endian
+    /// `x`.
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        let AffinePoint { x, y, zero } = self.0;
+        assert!(!zero, "The point at infinity is not a valid public key");
+
+        let len = field_byte_len::<C::BaseField>();
+        if compressed {
+            let mut bytes = vec![SEC1_COMPRESSED_EVEN | y.to_canonical_biguint().bit(0) as u8];
+            bytes.extend(field_to_bytes_be(x, len));
+            bytes
+        } else {
+            let mut bytes = vec![SEC1_UNCOMPRESSED];
+            bytes.extend(field_to_bytes_be(x, len));
+            bytes.extend(field_to_bytes_be(y, len));
+            bytes
         }
-        (k, rr)
-    };
-    let r = base_to_scalar::<C>(rr.x);
+    }
+
+    /// Parses a compressed or uncompressed SEC1 encoding. Returns `None` if the encoding is
+    /// invalid, or if it isn't a point of the curve.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Option<Self> {
+        let len = field_byte_len::<C::BaseField>();
+        let (&tag, coordinates) = bytes.split_first()?;
+        let point = match tag {
+            SEC1_COMPRESSED_EVEN | SEC1_COMPRESSED_ODD if coordinates.len() == len => {
+                let x = field_from_bytes_be::<C::BaseField>(coordinates)?;
+                let mut y = (x.cube() + C::A * x + C::B).sqrt()?;
+                if y.to_canonical_biguint().bit(0) != (tag == SEC1_COMPRESSED_ODD) {
+                    y = -y;
+                }
+                AffinePoint::nonzero(x, y)
+            }
+            SEC1_UNCOMPRESSED if coordinates.len() == 2 * len => {
+                let (x, y) = coordinates.split_at(len);
+                let point = AffinePoint {
+                    x: field_from_bytes_be::<C::BaseField>(x)?,
+                    y: field_from_bytes_be::<C::BaseField>(y)?,
+                    zero: false,
+                };
+                if !point.is_valid() {
+                    return None;
+                }
+                point
+            }
+            _ => return None,
+        };
+        Some(Self(point))
+    }
+}
+
+const DER_INTEGER: u8 = 0x02;
+const DER_SEQUENCE: u8 = 0x30;
+
+const SEC1_COMPRESSED_EVEN: u8 = 0x02;
+const SEC1_COMPRESSED_ODD: u8 = 0x03;
+const SEC1_UNCOMPRESSED: u8 = 0x04;
 
-    let s = k.inverse() * (msg + r * sk.0);
+fn field_byte_len<F: Field>() -> usize {
+    ceil_div_usize(F::BITS, 8)
+}
+
+fn field_to_bytes_be<F: PrimeField>(x: F, len: usize) -> Vec<u8> {
+    let bytes = x.to_canonical_biguint().to_bytes_be();
+    let mut padded = vec![0; len - bytes.len()];
+    padded.extend(bytes);
+    padded
+}
+
+/// Parses a big-endian field element, returning `None` if it isn't reduced.
+fn field_from_bytes_be<F: PrimeField>(bytes: &[u8]) -> Option<F> {
+    let x = BigUint::from_bytes_be(bytes);
+    (x < F::order()).then(|| F::from_noncanonical_biguint(x))
+}
+
+fn nonzero_scalar_from_biguint<C: Curve>(x: BigUint) -> Option<C::ScalarField> {
+    (x != BigUint::default() && x < C::ScalarField::order())
+        .then(|| C::ScalarField::from_noncanonical_biguint(x))
+}
+
+type HmacSha256 = Hmac<Sha256>;
 
-    ECDSASignature { r, s }
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for d in data {
+        mac.update(d);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// The deterministic generation of nonces of RFC 6979, with HMAC-SHA256.
+struct Rfc6979<C: Curve> {
+    k: [u8; 32],
+    v: [u8; 32],
+    _phantom: PhantomData<C>,
+}
+
+impl<C: Curve> Rfc6979<C> {
+    /// Initializes the generator for a message whose hash, reduced modulo `n`, is `msg`.
+    fn new(msg: C::ScalarField, sk: ECDSASecretKey<C>) -> Self {
+        let len = field_byte_len::<C::ScalarField>();
+        let x = field_to_bytes_be(sk.0, len);
+        let h = field_to_bytes_be(msg, len);
+
+        let mut k = [0; 32];
+        let mut v = [1; 32];
+        k = hmac_sha256(&k, &[&v, &[0], &x, &h]);
+        v = hmac_sha256(&k, &[&v]);
+        k = hmac_sha256(&k, &[&v, &[1], &x, &h]);
+        v = hmac_sha256(&k, &[&v]);
+        Self {
+            k,
+            v,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the next candidate nonce in `[1, n)`.
+    fn next_nonce(&mut self) -> C::ScalarField {
+        let qlen = C::ScalarField::BITS;
+        loop {
+            let mut t = Vec::new();
+            while t.len() * 8 < qlen {
+                self.v = hmac_sha256(&self.k, &[&self.v]);
+                t.extend(self.v);
+            }
+            let candidate = BigUint::from_bytes_be(&t) >> (t.len() * 8 - qlen);
+
+            // Prepare for the next candidate, in case this one is rejected here or by the caller.
+            self.k = hmac_sha256(&self.k, &[&self.v, &[0]]);
+            self.v = hmac_sha256(&self.k, &[&self.v]);
+
+            if let Some(k) = nonzero_scalar_from_biguint::<C>(candidate) {
+                return k;
+            }
+        }
+    }
+}
+
+/// Returns the RFC 6979 nonce used by `sign_message`, with HMAC-SHA256. `msg` is the hash of the
+/// message, reduced modulo `n`.
+pub fn rfc6979_nonce<C: Curve>(msg: C::ScalarField, sk: ECDSASecretKey<C>) -> C::ScalarField {
+    Rfc6979::new(msg, sk).next_nonce()
+}
+
+/// Signs `msg` with a deterministic RFC 6979 nonce, and returns the signature with a low `s`
+/// together with its recovery id. Bit 0 of the recovery id is the parity of the y-coordinate of
+/// the nonce point `R`, and bit 1 is set if `R.x >= n`.
+pub fn sign_message_recoverable<C: Curve>(
+    msg: C::ScalarField,
+    sk: ECDSASecretKey<C>,
+) -> (ECDSASignature<C>, u8) {
+    let mut nonces = Rfc6979::new(msg, sk);
+    loop {
+        let k = nonces.next_nonce();
+        let rr = (CurveScalar(k) * C::GENERATOR_PROJECTIVE).to_affine();
+        let r = base_to_scalar::<C>(rr.x);
+        if r == C::ScalarField::ZERO {
+            continue;
+        }
+        let s = k.inverse() * (msg + r * sk.0);
+        if s == C::ScalarField::ZERO {
+            continue;
+        }
+
+        let sig = ECDSASignature { r, s };
+        let x_overflow = rr.x.to_canonical_biguint() >= C::ScalarField::order();
+        let mut y_odd = rr.y.to_canonical_biguint().bit(0);
+        // Negating `s` amounts to negating `R`.
+        if !sig.is_low_s() {
+            y_odd = !y_odd;
+        }
+        return (sig.normalize_s(), y_odd as u8 | (x_overflow as u8) << 1);
+    }
+}
+
+/// Signs `msg` with a deterministic RFC 6979 nonce. The returned signature has a low `s`.
+pub fn sign_message<C: Curve>(msg: C::ScalarField, sk: ECDSASecretKey<C>) -> ECDSASignature<C> {
+    sign_message_recoverable(msg, sk).0
 }
 
 pub fn verify_message<C: Curve>(
@@ -61,14 +337,62 @@ pub fn verify_message<C: Curve>(
     r == x
 }
 
+/// Recovers the public key which signed `msg`, given the parity `v` of the y-coordinate of the
+/// signer's nonce point `R`. Only the recovery ids for which `R.x == r` are supported; the others,
+/// where `R.x == r + n`, occur with negligible probability for curves like secp256k1. Returns `None`
+/// if no such key exists.
+pub fn ecrecover<C: Curve>(
+    msg: C::ScalarField,
+    sig: ECDSASignature<C>,
+    v: bool,
+) -> Option<ECDSAPublicKey<C>> {
+    let ECDSASignature { r, s } = sig;
+    if r == C::ScalarField::ZERO || s == C::ScalarField::ZERO {
+        return None;
+    }
+
+    let x = scalar_to_base::<C>(r);
+    let mut y = (x.cube() + C::A * x + C::B).sqrt()?;
+    if y.to_canonical_biguint().bit(0) != v {
+        y = -y;
+    }
+    let rr = AffinePoint::<C>::nonzero(x, y);
+
+    // `Q = r^-1 (s R - msg G)`.
+    let r_inv = r.inverse();
+    let u1 = -msg * r_inv;
+    let u2 = s * r_inv;
+    let q = (CurveScalar(u1) * C::GENERATOR_PROJECTIVE + CurveScalar(u2) * rr.to_projective())
+        .to_affine();
+
+    (!q.zero).then_some(ECDSAPublicKey(q))
+}
+
+/// Returns the Ethereum address of a public key: the last 20 bytes of the Keccak-256 hash of its
+/// big-endian coordinates.
+pub fn eth_address(pk: ECDSAPublicKey<Secp256K1>) -> [u8; 20] {
+    keccak(&pk.to_sec1_bytes(false)[1..]).0[12..]
+        .try_into()
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
+    use hex_literal::hex;
+    use num::BigUint;
     use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
-    use plonky2::field::types::Sample;
+    use plonky2::field::types::{Field, Sample};
 
-    use crate::curve::ecdsa::{sign_message, verify_message, ECDSASecretKey};
+    use crate::curve::ecdsa::{
+        ecrecover, eth_address, rfc6979_nonce, sign_message, sign_message_recoverable,
+        verify_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature,
+    };
     use crate::curve::secp256k1::Secp256K1;
 
+    fn scalar(bytes: &[u8]) -> Secp256K1Scalar {
+        Secp256K1Scalar::from_noncanonical_biguint(BigUint::from_bytes_be(bytes))
+    }
+
     #[test]
     fn test_ecdsa_native() {
         type C = Secp256K1;
@@ -81,4 +405,187 @@ mod tests {
         let result = verify_message(msg, sig, pk);
         assert!(result);
     }
+
+    #[test]
+    fn test_ecrecover_native() {
+        type C = Secp256K1;
+
+        let msg = Secp256K1Scalar::rand();
+        let sk = ECDSASecretKey::<C>(Secp256K1Scalar::rand());
+        let pk = sk.to_public();
+
+        let sig = sign_message(msg, sk);
+        let recovered = [false, true].map(|v| ecrecover(msg, sig, v));
+        assert!(recovered.contains(&Some(pk)));
+        assert_ne!(recovered[0], recovered[1]);
+    }
+
+    #[test]
+    fn test_eth_address() {
+        let sk = ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::ONE);
+        assert_eq!(
+            eth_address(sk.to_public()),
+            [
+                0x7e, 0x5f, 0x45, 0x52, 0x09, 0x1a, 0x69, 0x12, 0x5d, 0x5d, 0xfc, 0xb7, 0xb8, 0xc2,
+                0x65, 0x90, 0x29, 0x39, 0x5b, 0xdf
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rfc6979_vectors() {
+        type C = Secp256K1;
+
+        // (secret key, SHA-256 of the message, r, low s)
+        let vectors = [
+            // "Satoshi Nakamoto"
+            (
+                hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+                hex!("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e"),
+                hex!("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"),
+                hex!("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"),
+            ),
+            // "Equations are more important to me, because politics is for the present, but an
+            // equation is something for eternity."
+            (
+                hex!("fee0a1f7afebf9d2a5a80c0c98a31c709681cce195cbcd06342b517970c0be1e"),
+                hex!("5e9f0a0d593efdcf78ac923bc3313e4e7d408d574354ee2b3288c0da9fbba6ed"),
+                hex!("1f0ed0f5df104f704ae73c6f5fdcb46fdf56318469e65b360602bc5ec226f5e6"),
+                hex!("5ae8031487d78e71a7ae97ab8a38ea5da8e07c7a8f09dfef69d04a833a1e358e"),
+            ),
+            // "Satoshi Nakamoto"
+            (
+                hex!("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140"),
+                hex!("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e"),
+                hex!("fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0"),
+                hex!("6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5"),
+            ),
+        ];
+        for (sk, msg, r, s) in vectors {
+            let sk = ECDSASecretKey::<C>(scalar(&sk));
+            let msg = scalar(&msg);
+            let sig = sign_message(msg, sk);
+            assert_eq!(sig, sign_message(msg, sk));
+            assert_eq!(sig.r, scalar(&r));
+            assert_eq!(sig.s, scalar(&s));
+            assert!(verify_message(msg, sig, sk.to_public()));
+        }
+
+        let sk = ECDSASecretKey::<C>(Secp256K1Scalar::ONE);
+        let msg = scalar(&vectors[0].1);
+        assert_eq!(
+            rfc6979_nonce(msg, sk),
+            scalar(&hex!(
+                "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_low_s_and_recovery_id() {
+        type C = Secp256K1;
+
+        for _ in 0..10 {
+            let msg = Secp256K1Scalar::rand();
+            let sk = ECDSASecretKey::<C>(Secp256K1Scalar::rand());
+            let (sig, recovery_id) = sign_message_recoverable(msg, sk);
+            assert!(sig.is_low_s());
+            assert!(verify_message(msg, sig, sk.to_public()));
+
+            let high_s = ECDSASignature::<C> {
+                r: sig.r,
+                s: -sig.s,
+            };
+            assert!(!high_s.is_low_s());
+            assert_eq!(high_s.normalize_s(), sig);
+
+            // `R.x >= n` has negligible probability for secp256k1.
+            assert!(recovery_id < 2);
+            assert_eq!(ecrecover(msg, sig, recovery_id == 1), Some(sk.to_public()));
+        }
+    }
+
+    #[test]
+    fn test_sec1_encoding() {
+        type C = Secp256K1;
+
+        let pk = ECDSASecretKey::<C>(Secp256K1Scalar::ONE).to_public();
+        let neg_pk = ECDSASecretKey::<C>(Secp256K1Scalar::NEG_ONE).to_public();
+        let x = hex!("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        let y = hex!("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+        assert_eq!(pk.to_sec1_bytes(true), [&[0x02][..], &x].concat());
+        assert_eq!(neg_pk.to_sec1_bytes(true), [&[0x03][..], &x].concat());
+        assert_eq!(pk.to_sec1_bytes(false), [&[0x04][..], &x, &y].concat());
+
+        for _ in 0..10 {
+            let pk = ECDSASecretKey::<C>(Secp256K1Scalar::rand()).to_public();
+            for compressed in [false, true] {
+                let bytes = pk.to_sec1_bytes(compressed);
+                assert_eq!(ECDSAPublicKey::from_sec1_bytes(&bytes), Some(pk));
+            }
+        }
+
+        // A point which isn't on the curve, an unknown tag and a wrong length.
+        let mut not_on_curve = pk.to_sec1_bytes(false);
+        not_on_curve[64] ^= 1;
+        assert_eq!(ECDSAPublicKey::<C>::from_sec1_bytes(&not_on_curve), None);
+        let mut unknown_tag = pk.to_sec1_bytes(true);
+        unknown_tag[0] = 0x05;
+        assert_eq!(ECDSAPublicKey::<C>::from_sec1_bytes(&unknown_tag), None);
+        assert_eq!(ECDSAPublicKey::<C>::from_sec1_bytes(&x), None);
+    }
+
+    #[test]
+    fn test_signature_encoding() {
+        type C = Secp256K1;
+
+        // The high-s signature of "Satoshi Nakamoto" with the secret key 1.
+        let der = hex!(
+            "3046022100934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8022100dbbd"
+            "3162d46e9f9bef7feb87c16dc13b4f6568a87f4e83f728e2443ba586675c"
+        );
+        let sig = ECDSASignature::<C>::from_der(&der).unwrap();
+        assert!(!sig.is_low_s());
+        assert_eq!(sig.to_der(), der);
+        let sk = ECDSASecretKey::<C>(Secp256K1Scalar::ONE);
+        let msg = scalar(&hex!(
+            "a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e"
+        ));
+        assert_eq!(sig.normalize_s(), sign_message(msg, sk));
+
+        let der = hex!(
+            "304402201f0ed0f5df104f704ae73c6f5fdcb46fdf56318469e65b360602bc5ec226f5e602205ae80314"
+            "87d78e71a7ae97ab8a38ea5da8e07c7a8f09dfef69d04a833a1e358e"
+        );
+        let sig = ECDSASignature::<C>::from_der(&der).unwrap();
+        assert_eq!(sig.to_der(), der);
+        assert_eq!(
+            sig.to_compact(),
+            [
+                &hex!("1f0ed0f5df104f704ae73c6f5fdcb46fdf56318469e65b360602bc5ec226f5e6")[..],
+                &hex!("5ae8031487d78e71a7ae97ab8a38ea5da8e07c7a8f09dfef69d04a833a1e358e"),
+            ]
+            .concat()
+        );
+        assert_eq!(ECDSASignature::from_compact(&sig.to_compact()), Some(sig));
+
+        // Trailing bytes, a non-minimal integer, a negative integer and a zero `s`.
+        let mut trailing = der.to_vec();
+        trailing.push(0);
+        assert_eq!(ECDSASignature::<C>::from_der(&trailing), None);
+        let non_minimal = hex!("300702020001020101");
+        assert_eq!(ECDSASignature::<C>::from_der(&non_minimal), None);
+        let negative = hex!("3006020181020101");
+        assert_eq!(ECDSASignature::<C>::from_der(&negative), None);
+        let zero_s = hex!("3006020101020100");
+        assert_eq!(ECDSASignature::<C>::from_der(&zero_s), None);
+        assert_eq!(
+            ECDSASignature::<C>::from_der(&hex!("3006020101020101")),
+            Some(ECDSASignature {
+                r: Secp256K1Scalar::ONE,
+                s: Secp256K1Scalar::ONE,
+            })
+        );
+        assert_eq!(ECDSASignature::<C>::from_compact(&[0; 64]), None);
+    }
 }
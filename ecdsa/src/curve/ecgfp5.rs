@@ -0,0 +1,264 @@
+use core::ops::{Add, Neg, Sub};
+
+use plonky2::field::ecgfp5_scalar::EcGFp5Scalar;
+use plonky2::field::extension::quintic::QuinticExtension;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::ops::Square;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+
+/// The degree five extension `GF(p)[z] / (z^5 - 3)` of the Goldilocks field, over which ecGFp5 is
+/// defined.
+pub type GFp5 = QuinticExtension<GoldilocksField>;
+
+const fn gfp5(coefficients: [u64; 5]) -> GFp5 {
+    QuinticExtension([
+        GoldilocksField(coefficients[0]),
+        GoldilocksField(coefficients[1]),
+        GoldilocksField(coefficients[2]),
+        GoldilocksField(coefficients[3]),
+        GoldilocksField(coefficients[4]),
+    ])
+}
+
+/// The coefficient `A = 263z - 4/3` of ecGFp5 in short Weierstrass form.
+pub const ECGFP5_A: GFp5 = gfp5([6148914689804861439, 263, 0, 0, 0]);
+
+/// The coefficient `B = (16 - 4734z) / 27` of ecGFp5 in short Weierstrass form.
+pub const ECGFP5_B: GFp5 = gfp5([15713893096167979237, 6148914689804861265, 0, 0, 0]);
+
+/// `2/3`, the x-coordinate of the point of order two.
+const TWO_THIRDS: GFp5 = gfp5([6148914689804861441, 0, 0, 0, 0]);
+
+/// A generator of the subgroup of order `n`: the double of the point with `x = 0` and the smaller
+/// `y`.
+pub const ECGFP5_GENERATOR: EcGFp5Point = EcGFp5Point {
+    x: gfp5([
+        15622315679105259,
+        9233938668908914291,
+        14943848313873695123,
+        1210072233909776598,
+        2930298871824402754,
+    ]),
+    y: gfp5([
+        13975352102087968007,
+        3055552835992475956,
+        5901154331134124558,
+        5088106612831722,
+        5553689672635880669,
+    ]),
+    zero: false,
+};
+
+/// A point on ecGFp5, represented in affine coordinates. The curve `y^2 = x(x^2 + 2x + 263z)` is
+/// used in the short Weierstrass form `y^2 = x^3 + Ax + B` given by the change of variable
+/// `x -> x - 2/3`. Its order is `2n` for the prime `n` of `EcGFp5Scalar`, and only the subgroup of
+/// order `n` is used for signatures.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+pub struct EcGFp5Point {
+    pub x: GFp5,
+    pub y: GFp5,
+    pub zero: bool,
+}
+
+impl EcGFp5Point {
+    pub const ZERO: Self = Self {
+        x: GFp5::ZERO,
+        y: GFp5::ZERO,
+        zero: true,
+    };
+
+    pub fn nonzero(x: GFp5, y: GFp5) -> Self {
+        let point = Self { x, y, zero: false };
+        debug_assert!(point.is_valid());
+        point
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let Self { x, y, zero } = *self;
+        zero || y.square() == x.cube() + ECGFP5_A * x + ECGFP5_B
+    }
+
+    /// Returns whether the point is in the subgroup of order `n`. In the original coordinates,
+    /// these are the points whose `x` is a non-zero square.
+    pub fn is_in_subgroup(&self) -> bool {
+        if self.zero {
+            return true;
+        }
+        let x = self.x - TWO_THIRDS;
+        x.is_nonzero() && gfp5_sqrt(x).is_some()
+    }
+
+    pub fn double(&self) -> Self {
+        let Self { x, y, zero } = *self;
+        if zero || y.is_zero() {
+            return Self::ZERO;
+        }
+
+        let lambda = (x.square().triple() + ECGFP5_A) / y.double();
+        let x3 = lambda.square() - x.double();
+        let y3 = lambda * (x - x3) - y;
+        Self {
+            x: x3,
+            y: y3,
+            zero: false,
+        }
+    }
+
+    /// Computes `k * self` by double-and-add.
+    pub fn mul_scalar(&self, k: EcGFp5Scalar) -> Self {
+        let k = k.to_canonical_biguint();
+        let mut result = Self::ZERO;
+        for i in (0..k.bits()).rev() {
+            result = result.double();
+            if k.bit(i) {
+                result = result + *self;
+            }
+        }
+        result
+    }
+}
+
+impl PartialEq for EcGFp5Point {
+    fn eq(&self, other: &Self) -> bool {
+        if self.zero || other.zero {
+            return self.zero == other.zero;
+        }
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl Eq for EcGFp5Point {}
+
+impl Add for EcGFp5Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        if self.zero {
+            return rhs;
+        }
+        if rhs.zero {
+            return self;
+        }
+
+        let Self { x: x1, y: y1, .. } = self;
+        let Self { x: x2, y: y2, .. } = rhs;
+        if x1 == x2 {
+            return if y1 == y2 { self.double() } else { Self::ZERO };
+        }
+
+        let lambda = (y2 - y1) / (x2 - x1);
+        let x3 = lambda.square() - x1 - x2;
+        let y3 = lambda * (x1 - x3) - y1;
+        Self {
+            x: x3,
+            y: y3,
+            zero: false,
+        }
+    }
+}
+
+impl Neg for EcGFp5Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+            zero: self.zero,
+        }
+    }
+}
+
+impl Sub for EcGFp5Point {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+/// Computes a square root of `x` with the Tonelli-Shanks algorithm, if there is one.
+pub fn gfp5_sqrt(x: GFp5) -> Option<GFp5> {
+    if x.is_zero() {
+        return Some(x);
+    }
+
+    // `|GFp5*| = 2^s t` with `t` odd.
+    let s = GFp5::TWO_ADICITY;
+    let t = (GFp5::order() - 1u32) >> s;
+    if x.exp_biguint(&(GFp5::order() >> 1)) != GFp5::ONE {
+        return None;
+    }
+
+    let mut z = GFp5::POWER_OF_TWO_GENERATOR;
+    let mut w = x.exp_biguint(&((t - 1u32) >> 1));
+    let mut root = w * x;
+    let mut b = root * w;
+    let mut v = s;
+    while !b.is_one() {
+        let mut k = 0;
+        let mut b2k = b;
+        while !b2k.is_one() {
+            b2k = b2k.square();
+            k += 1;
+        }
+        w = z.exp_power_of_2(v - k - 1);
+        z = w.square();
+        b *= z;
+        root *= w;
+        v = k;
+    }
+    Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Sample;
+
+    use super::*;
+
+    #[test]
+    fn test_generator() {
+        let g = ECGFP5_GENERATOR;
+        assert!(g.is_valid());
+        assert!(g.is_in_subgroup());
+        assert_eq!(g.mul_scalar(EcGFp5Scalar::NEG_ONE) + g, EcGFp5Point::ZERO);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let g = ECGFP5_GENERATOR;
+        let a = EcGFp5Scalar::rand();
+        let b = EcGFp5Scalar::rand();
+        let p = g.mul_scalar(a);
+        assert!(p.is_valid());
+        assert_eq!(p + g.mul_scalar(b), g.mul_scalar(a + b));
+        assert_eq!(p.double(), p + p);
+        assert_eq!(p - p, EcGFp5Point::ZERO);
+        assert_eq!(p + EcGFp5Point::ZERO, p);
+    }
+
+    #[test]
+    fn test_subgroup() {
+        // The point of order two, and its sum with the generator, are on the curve but outside the
+        // subgroup of order `n`.
+        let n = EcGFp5Point::nonzero(TWO_THIRDS, GFp5::ZERO);
+        assert!(!n.is_in_subgroup());
+        assert_eq!(n.double(), EcGFp5Point::ZERO);
+        let p = ECGFP5_GENERATOR + n;
+        assert!(p.is_valid());
+        assert!(!p.is_in_subgroup());
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for _ in 0..10 {
+            let x = GFp5::rand();
+            let root = gfp5_sqrt(x.square()).unwrap();
+            assert_eq!(root.square(), x.square());
+        }
+        assert_eq!(gfp5_sqrt(GFp5::MULTIPLICATIVE_GROUP_GENERATOR), None);
+    }
+}
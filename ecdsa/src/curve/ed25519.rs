@@ -0,0 +1,137 @@
+use num::BigUint;
+use plonky2::field::ed25519_base::Ed25519Base;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::ops::Square;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+/// The length in bytes of an encoded Ed25519 point.
+pub const ED25519_POINT_LEN: usize = 32;
+
+#[derive(Debug, Copy, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Ed25519;
+
+impl TwistedEdwardsCurve for Ed25519 {
+    type BaseField = Ed25519Base;
+    type ScalarField = Ed25519Scalar;
+
+    const A: Ed25519Base = Ed25519Base::NEG_ONE;
+    const D: Ed25519Base = ED25519_D;
+    const GENERATOR: EdwardsPoint<Self> = EdwardsPoint {
+        x: ED25519_GENERATOR_X,
+        y: ED25519_GENERATOR_Y,
+    };
+}
+
+/// -121665 / 121666
+/// 37095705934669439343138083508754565189542113879843219016388785533085940283555
+const ED25519_D: Ed25519Base = Ed25519Base([
+    0x75EB4DCA135978A3,
+    0x00700A4D4141D8AB,
+    0x8CC740797779E898,
+    0x52036CEE2B6FFE73,
+]);
+
+/// 15112221349535400772501151409588531511454012693041857206046113283949847762202
+const ED25519_GENERATOR_X: Ed25519Base = Ed25519Base([
+    0xC9562D608F25D51A,
+    0x692CC7609525A7B2,
+    0xC0A4E231FDD6DC5C,
+    0x216936D3CD6E53FE,
+]);
+
+/// 4 / 5
+/// 46316835694926478169428394003475163141307993866256225615783033603165251855960
+const ED25519_GENERATOR_Y: Ed25519Base = Ed25519Base([
+    0x6666666666666658,
+    0x6666666666666666,
+    0x6666666666666666,
+    0x6666666666666666,
+]);
+
+impl EdwardsPoint<Ed25519> {
+    /// Encodes the point as in RFC 8032: the little-endian `y`, with the parity of `x` in the most
+    /// significant bit.
+    pub fn to_bytes(&self) -> [u8; ED25519_POINT_LEN] {
+        let mut bytes = [0; ED25519_POINT_LEN];
+        let y_bytes = self.y.to_canonical_biguint().to_bytes_le();
+        bytes[..y_bytes.len()].copy_from_slice(&y_bytes);
+        if self.x.to_canonical_biguint().bit(0) {
+            bytes[ED25519_POINT_LEN - 1] |= 0x80;
+        }
+        bytes
+    }
+
+    /// Decodes a point encoded as in RFC 8032, rejecting non-canonical encodings.
+    pub fn from_bytes(bytes: &[u8; ED25519_POINT_LEN]) -> Option<Self> {
+        let mut y_bytes = *bytes;
+        let x_odd = y_bytes[ED25519_POINT_LEN - 1] & 0x80 != 0;
+        y_bytes[ED25519_POINT_LEN - 1] &= 0x7f;
+        let y = BigUint::from_bytes_le(&y_bytes);
+        if y >= Ed25519Base::order() {
+            return None;
+        }
+        let y = Ed25519Base::from_noncanonical_biguint(y);
+
+        // `x^2 = (y^2 - 1) / (d y^2 - a)`.
+        let y_squared = y.square();
+        let x_squared = (y_squared - Ed25519Base::ONE) / (Ed25519::D * y_squared - Ed25519::A);
+        let x = x_squared.sqrt()?;
+        if x.is_zero() && x_odd {
+            return None;
+        }
+        let x = if x.to_canonical_biguint().bit(0) == x_odd {
+            x
+        } else {
+            -x
+        };
+        Some(Self::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::ed25519_scalar::Ed25519Scalar;
+    use plonky2::field::types::{Field, Sample};
+
+    use crate::curve::ed25519::Ed25519;
+    use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+    #[test]
+    fn test_generator() {
+        let g = Ed25519::GENERATOR;
+        assert!(g.is_valid());
+        assert!((-g).is_valid());
+
+        // The generator has prime order.
+        assert_eq!(g.mul_scalar(Ed25519Scalar::NEG_ONE) + g, EdwardsPoint::ZERO);
+    }
+
+    #[test]
+    fn test_addition() {
+        let g = Ed25519::GENERATOR;
+        let five = Ed25519Scalar::from_canonical_u64(5);
+        assert_eq!(g.mul_scalar(five), g + g.double().double());
+        assert_eq!(g + EdwardsPoint::ZERO, g);
+        assert_eq!(g + -g, EdwardsPoint::ZERO);
+    }
+
+    #[test]
+    fn test_encoding() {
+        let mut expected = [0x66; 32];
+        expected[0] = 0x58;
+        assert_eq!(Ed25519::GENERATOR.to_bytes(), expected);
+
+        let p = Ed25519::GENERATOR.mul_scalar(Ed25519Scalar::rand());
+        assert_eq!(EdwardsPoint::from_bytes(&p.to_bytes()), Some(p));
+        assert_eq!(EdwardsPoint::from_bytes(&(-p).to_bytes()), Some(-p));
+
+        // `y = p` is a non-canonical encoding of `y = 0`.
+        let mut non_canonical = [0xff; 32];
+        non_canonical[0] = 0xed;
+        non_canonical[31] = 0x7f;
+        assert_eq!(EdwardsPoint::<Ed25519>::from_bytes(&non_canonical), None);
+    }
+}
@@ -0,0 +1,159 @@
+use num::BigUint;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+use crate::curve::ed25519::{Ed25519, ED25519_POINT_LEN};
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+/// The length in bytes of an encoded Ed25519 signature.
+pub const EDDSA_SIGNATURE_LEN: usize = 2 * ED25519_POINT_LEN;
+
+/// An Ed25519 signature `(R, S)`.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EdDSASignature {
+    pub r: EdwardsPoint<Ed25519>,
+    pub s: Ed25519Scalar,
+}
+
+impl EdDSASignature {
+    pub fn to_bytes(&self) -> [u8; EDDSA_SIGNATURE_LEN] {
+        let mut bytes = [0; EDDSA_SIGNATURE_LEN];
+        bytes[..ED25519_POINT_LEN].copy_from_slice(&self.r.to_bytes());
+        let s_bytes = self.s.to_canonical_biguint().to_bytes_le();
+        bytes[ED25519_POINT_LEN..ED25519_POINT_LEN + s_bytes.len()].copy_from_slice(&s_bytes);
+        bytes
+    }
+
+    /// Decodes a signature, rejecting non-canonical encodings of `R` and `S`.
+    pub fn from_bytes(bytes: &[u8; EDDSA_SIGNATURE_LEN]) -> Option<Self> {
+        let r = EdwardsPoint::from_bytes(bytes[..ED25519_POINT_LEN].try_into().unwrap())?;
+        let s = BigUint::from_bytes_le(&bytes[ED25519_POINT_LEN..]);
+        if s >= Ed25519Scalar::order() {
+            return None;
+        }
+        let s = Ed25519Scalar::from_noncanonical_biguint(s);
+        Some(Self { r, s })
+    }
+}
+
+/// An Ed25519 secret key, i.e. the 32-byte seed from which the signing scalar and the nonce prefix
+/// are derived.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct EdDSASecretKey(pub [u8; 32]);
+
+impl EdDSASecretKey {
+    /// Returns the clamped signing scalar and the nonce prefix.
+    fn expand(&self) -> (Ed25519Scalar, [u8; 32]) {
+        let hash = Sha512::digest(self.0);
+        let mut scalar_bytes: [u8; 32] = hash[..32].try_into().unwrap();
+        scalar_bytes[0] &= 0xf8;
+        scalar_bytes[31] &= 0x7f;
+        scalar_bytes[31] |= 0x40;
+        let scalar = Ed25519Scalar::from_noncanonical_biguint(
+            BigUint::from_bytes_le(&scalar_bytes) % Ed25519Scalar::order(),
+        );
+        (scalar, hash[32..].try_into().unwrap())
+    }
+
+    pub fn to_public(&self) -> EdDSAPublicKey {
+        let (scalar, _) = self.expand();
+        EdDSAPublicKey(Ed25519::GENERATOR.mul_scalar(scalar))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EdDSAPublicKey(pub EdwardsPoint<Ed25519>);
+
+/// Reduces a little-endian SHA-512 digest modulo the order of the Ed25519 subgroup.
+pub fn scalar_from_digest(digest: &[u8]) -> Ed25519Scalar {
+    Ed25519Scalar::from_noncanonical_biguint(
+        BigUint::from_bytes_le(digest) % Ed25519Scalar::order(),
+    )
+}
+
+/// Computes the challenge `SHA-512(R || A || msg) mod l`.
+pub fn eddsa_challenge(r: EdwardsPoint<Ed25519>, pk: EdDSAPublicKey, msg: &[u8]) -> Ed25519Scalar {
+    let digest = Sha512::new()
+        .chain_update(r.to_bytes())
+        .chain_update(pk.0.to_bytes())
+        .chain_update(msg)
+        .finalize();
+    scalar_from_digest(&digest)
+}
+
+/// Signs `msg` as in RFC 8032, with a deterministic nonce.
+pub fn sign_message(msg: &[u8], sk: EdDSASecretKey) -> EdDSASignature {
+    let (scalar, prefix) = sk.expand();
+    let pk = EdDSAPublicKey(Ed25519::GENERATOR.mul_scalar(scalar));
+
+    let nonce = scalar_from_digest(
+        &Sha512::new()
+            .chain_update(prefix)
+            .chain_update(msg)
+            .finalize(),
+    );
+    let r = Ed25519::GENERATOR.mul_scalar(nonce);
+    let s = nonce + eddsa_challenge(r, pk, msg) * scalar;
+
+    EdDSASignature { r, s }
+}
+
+/// Checks the cofactorless verification equation `S B = R + k A`, which is what the in-circuit
+/// verifier enforces. It accepts a subset of the signatures accepted by the cofactored equation of
+/// RFC 8032, and they coincide for honestly generated signatures.
+pub fn verify_message(msg: &[u8], sig: EdDSASignature, pk: EdDSAPublicKey) -> bool {
+    let EdDSASignature { r, s } = sig;
+    if !r.is_valid() || !pk.0.is_valid() {
+        return false;
+    }
+
+    let k = eddsa_challenge(r, pk, msg);
+    Ed25519::GENERATOR.mul_scalar(s) == r + pk.0.mul_scalar(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_bytes<const N: usize>(hex: &str) -> [u8; N] {
+        assert_eq!(hex.len(), 2 * N);
+        core::array::from_fn(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+    }
+
+    /// Tests 1 and 2 of RFC 8032, section 7.1.
+    #[test]
+    fn test_rfc8032_vectors() {
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                &[][..],
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                 5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                &[0x72][..],
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+                 085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+        ];
+
+        for (sk, pk, msg, sig) in vectors {
+            let sk = EdDSASecretKey(hex_bytes(sk));
+            let pk = hex_bytes(pk);
+            let sig = hex_bytes(sig);
+
+            let public = sk.to_public();
+            assert_eq!(public.0.to_bytes(), pk);
+            let signature = sign_message(msg, sk);
+            assert_eq!(signature.to_bytes(), sig);
+            assert_eq!(EdDSASignature::from_bytes(&sig), Some(signature));
+            assert!(verify_message(msg, signature, public));
+            assert!(!verify_message(b"other message", signature, public));
+        }
+    }
+}
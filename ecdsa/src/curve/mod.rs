@@ -4,5 +4,10 @@ pub mod curve_multiplication;
 pub mod curve_summation;
 pub mod curve_types;
 pub mod ecdsa;
+pub mod ecgfp5;
+pub mod ed25519;
+pub mod eddsa;
 pub mod glv;
+pub mod schnorr;
 pub mod secp256k1;
+pub mod twisted_edwards;
@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use num::BigUint;
+use plonky2::field::ecgfp5_scalar::EcGFp5Scalar;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::{Field, PrimeField64, Sample};
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::plonk::config::Hasher;
+use serde::{Deserialize, Serialize};
+
+use crate::curve::ecgfp5::{EcGFp5Point, ECGFP5_GENERATOR};
+
+/// A Schnorr signature `(e, s)` over ecGFp5, where `e` is the challenge and `s = k - e * sk` for
+/// the nonce `k`.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SchnorrSignature {
+    pub e: EcGFp5Scalar,
+    pub s: EcGFp5Scalar,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SchnorrSecretKey(pub EcGFp5Scalar);
+
+impl SchnorrSecretKey {
+    pub fn to_public(&self) -> SchnorrPublicKey {
+        SchnorrPublicKey(ECGFP5_GENERATOR.mul_scalar(self.0))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SchnorrPublicKey(pub EcGFp5Point);
+
+/// Computes the challenge as the Poseidon hash of the coordinates of `R` and of the public key,
+/// followed by `msg`. The four 64-bit elements of the hash are the limbs of a 256-bit scalar,
+/// which is always less than `n`.
+pub fn schnorr_challenge(
+    r: EcGFp5Point,
+    pk: SchnorrPublicKey,
+    msg: &[GoldilocksField],
+) -> EcGFp5Scalar {
+    debug_assert!(!r.zero && !pk.0.zero);
+    let mut inputs = Vec::with_capacity(20 + msg.len());
+    for coordinate in [r.x, r.y, pk.0.x, pk.0.y] {
+        inputs.extend(coordinate.0);
+    }
+    inputs.extend_from_slice(msg);
+
+    let hash = PoseidonHash::hash_no_pad(&inputs);
+    let e = hash
+        .elements
+        .iter()
+        .rev()
+        .fold(BigUint::default(), |acc, x| {
+            (acc << 64) + x.to_canonical_u64()
+        });
+    EcGFp5Scalar::from_noncanonical_biguint(e)
+}
+
+pub fn sign_message(msg: &[GoldilocksField], sk: SchnorrSecretKey) -> SchnorrSignature {
+    let pk = sk.to_public();
+    let (k, r) = {
+        let mut k = EcGFp5Scalar::rand();
+        let mut r = ECGFP5_GENERATOR.mul_scalar(k);
+        while r.zero {
+            k = EcGFp5Scalar::rand();
+            r = ECGFP5_GENERATOR.mul_scalar(k);
+        }
+        (k, r)
+    };
+
+    let e = schnorr_challenge(r, pk, msg);
+    let s = k - e * sk.0;
+    SchnorrSignature { e, s }
+}
+
+/// Verifies a signature by recomputing `R = s G + e pk` and its challenge.
+pub fn verify_message(
+    msg: &[GoldilocksField],
+    sig: SchnorrSignature,
+    pk: SchnorrPublicKey,
+) -> bool {
+    if pk.0.zero || !pk.0.is_valid() || !pk.0.is_in_subgroup() {
+        return false;
+    }
+
+    let SchnorrSignature { e, s } = sig;
+    let r = ECGFP5_GENERATOR.mul_scalar(s) + pk.0.mul_scalar(e);
+    !r.zero && schnorr_challenge(r, pk, msg) == e
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+
+    use super::*;
+
+    #[test]
+    fn test_schnorr() {
+        type F = GoldilocksField;
+
+        let msg = F::rand_vec(5);
+        let sk = SchnorrSecretKey(EcGFp5Scalar::rand());
+        let pk = sk.to_public();
+
+        let sig = sign_message(&msg, sk);
+        assert!(verify_message(&msg, sig, pk));
+
+        let other_msg = F::rand_vec(5);
+        assert!(!verify_message(&other_msg, sig, pk));
+        let other_pk = SchnorrSecretKey(EcGFp5Scalar::rand()).to_public();
+        assert!(!verify_message(&msg, sig, other_pk));
+    }
+}
@@ -0,0 +1,99 @@
+use core::fmt::Debug;
+use core::ops::{Add, Neg};
+
+use plonky2::field::ops::Square;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+
+/// A twisted Edwards curve `a x^2 + y^2 = 1 + d x^2 y^2`. The curve must be complete, i.e. `a`
+/// must be a square and `d` a non-square, so that the addition law has no exceptional cases.
+pub trait TwistedEdwardsCurve: 'static + Sync + Sized + Copy + Debug {
+    type BaseField: PrimeField;
+    type ScalarField: PrimeField;
+
+    const A: Self::BaseField;
+    const D: Self::BaseField;
+
+    /// A generator of the prime-order subgroup.
+    const GENERATOR: EdwardsPoint<Self>;
+}
+
+/// A point on a twisted Edwards curve, represented in affine coordinates. Unlike short
+/// Weierstrass curves, the identity `(0, 1)` is an affine point and needs no special handling.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+pub struct EdwardsPoint<C: TwistedEdwardsCurve> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+}
+
+impl<C: TwistedEdwardsCurve> EdwardsPoint<C> {
+    pub const ZERO: Self = Self {
+        x: C::BaseField::ZERO,
+        y: C::BaseField::ONE,
+    };
+
+    pub fn new(x: C::BaseField, y: C::BaseField) -> Self {
+        let point = Self { x, y };
+        debug_assert!(point.is_valid());
+        point
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let Self { x, y } = *self;
+        let x_squared = x.square();
+        let y_squared = y.square();
+        C::A * x_squared + y_squared == C::BaseField::ONE + C::D * x_squared * y_squared
+    }
+
+    pub fn double(&self) -> Self {
+        *self + *self
+    }
+
+    /// Computes `k * self` by double-and-add.
+    pub fn mul_scalar(&self, k: C::ScalarField) -> Self {
+        let k = k.to_canonical_biguint();
+        let mut result = Self::ZERO;
+        for i in (0..k.bits()).rev() {
+            result = result.double();
+            if k.bit(i) {
+                result = result + *self;
+            }
+        }
+        result
+    }
+}
+
+impl<C: TwistedEdwardsCurve> PartialEq for EdwardsPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Eq for EdwardsPoint<C> {}
+
+impl<C: TwistedEdwardsCurve> Add for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let Self { x: x1, y: y1 } = self;
+        let Self { x: x2, y: y2 } = rhs;
+
+        let x1x2 = x1 * x2;
+        let y1y2 = y1 * y2;
+        let dxxyy = C::D * x1x2 * y1y2;
+        let x3 = (x1 * y2 + y1 * x2) / (C::BaseField::ONE + dxxyy);
+        let y3 = (y1y2 - C::A * x1x2) / (C::BaseField::ONE - dxxyy);
+        Self { x: x3, y: y3 }
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Neg for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+}
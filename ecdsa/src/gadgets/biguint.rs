@@ -2,7 +2,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use num::{BigUint, Integer, Zero};
+use num::{BigUint, Integer, One, Zero};
 use plonky2::field::extension::Extendable;
 use plonky2::field::types::{PrimeField, PrimeField64};
 use plonky2::hash::hash_types::RichField;
@@ -10,10 +10,19 @@ use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartitionWitness, Witness};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::public_input_schema::PublicInputType;
 use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use plonky2_u32::gadgets::multiple_comparison::list_le_u32_circuit;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
 use plonky2_u32::witness::{GeneratedValuesU32, WitnessU32};
 
+/// The number of `u32`s added by each `U32AddManyGate` operation in `mul_biguint`, which keeps the
+/// carry within the gate's four carry bits.
+const MAX_ADDENDS_PER_SUM: usize = 15;
+
+/// The window size, in bits, used by modular exponentiation.
+pub const MOD_EXP_WINDOW_BITS: usize = 4;
+
 #[derive(Clone, Debug)]
 pub struct BigUintTarget {
     pub limbs: Vec<U32Target>,
@@ -46,6 +55,9 @@ pub trait CircuitBuilderBiguint<F: RichField + Extendable<D>, const D: usize> {
 
     fn add_virtual_biguint_target(&mut self, num_limbs: usize) -> BigUintTarget;
 
+    /// Registers `a` as a public input named `name`, recorded in the public input schema.
+    fn register_named_public_biguint(&mut self, name: &str, a: &BigUintTarget);
+
     /// Add two `BigUintTarget`s.
     fn add_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget;
 
@@ -73,6 +85,33 @@ pub trait CircuitBuilderBiguint<F: RichField + Extendable<D>, const D: usize> {
     fn div_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget;
 
     fn rem_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget;
+
+    /// Returns `a * b mod modulus`, with as many limbs as `modulus` and constrained to be less
+    /// than it.
+    fn mod_mul_biguint(
+        &mut self,
+        a: &BigUintTarget,
+        b: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
+
+    /// Returns `base^exponent mod modulus`, for an exponent known when building the circuit. The
+    /// window size is chosen per exponent, up to `MOD_EXP_WINDOW_BITS`.
+    fn mod_exp_biguint(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUint,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
+
+    /// Returns `base^exponent mod modulus`, processing the exponent in windows of
+    /// `MOD_EXP_WINDOW_BITS` bits.
+    fn mod_exp_biguint_variable(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
@@ -137,6 +176,17 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
         BigUintTarget { limbs }
     }
 
+    fn register_named_public_biguint(&mut self, name: &str, a: &BigUintTarget) {
+        let limbs = a.limbs.iter().map(|l| l.0).collect::<Vec<_>>();
+        self.register_named_public_inputs(
+            name,
+            PublicInputType::BigUint {
+                num_limbs: a.num_limbs(),
+            },
+            &limbs,
+        );
+    }
+
     fn add_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget {
         let num_limbs = a.num_limbs().max(b.num_limbs());
 
@@ -192,12 +242,21 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
             }
         }
 
+        // Large operands have more summands per limb than `U32AddManyGate` supports, so those are
+        // added in chunks, each taking the previous partial sum as its carry. The chunks' carries
+        // are small, so their sum is carried into the next limb.
         let mut combined_limbs = vec![];
         let mut carry = self.zero_u32();
         for summands in &mut to_add {
-            let (new_result, new_carry) = self.add_u32s_with_carry(summands, carry);
+            let mut new_result = carry;
+            let mut new_carries = vec![];
+            for chunk in summands.chunks(MAX_ADDENDS_PER_SUM) {
+                let (sum, chunk_carry) = self.add_u32s_with_carry(chunk, new_result);
+                new_result = sum;
+                new_carries.push(chunk_carry.0);
+            }
             combined_limbs.push(new_result);
-            carry = new_carry;
+            carry = U32Target(self.add_many(new_carries));
         }
         combined_limbs.push(carry);
 
@@ -270,6 +329,121 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
         let (_div, rem) = self.div_rem_biguint(a, b);
         rem
     }
+
+    fn mod_mul_biguint(
+        &mut self,
+        a: &BigUintTarget,
+        b: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        let product = self.mul_biguint(a, b);
+        reduce_biguint(self, &product, modulus)
+    }
+
+    fn mod_exp_biguint(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUint,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        if exponent.is_zero() {
+            let one = self.constant_biguint(&BigUint::one());
+            return reduce_biguint(self, &one, modulus);
+        }
+        let base = reduce_biguint(self, base, modulus);
+
+        // The number of squarings barely depends on the window size, so pick the one minimizing
+        // the other multiplications: those building the table of powers, and one per nonzero
+        // digit after the leading one.
+        let (window_bits, digits) = (1..=MOD_EXP_WINDOW_BITS)
+            .map(|w| (w, exponent.to_radix_be(1 << w)))
+            .min_by_key(|(_, digits)| {
+                let max_digit = *digits.iter().max().unwrap() as usize;
+                max_digit + digits.iter().filter(|&&d| d != 0).count()
+            })
+            .unwrap();
+
+        // `powers[i]` is `base^(i + 1)`, up to the largest digit.
+        let max_digit = *digits.iter().max().unwrap() as usize;
+        let mut powers = vec![base.clone()];
+        for _ in 1..max_digit {
+            let power = self.mod_mul_biguint(powers.last().unwrap(), &base, modulus);
+            powers.push(power);
+        }
+
+        // The leading digit is nonzero.
+        let mut result = powers[digits[0] as usize - 1].clone();
+        for &digit in &digits[1..] {
+            for _ in 0..window_bits {
+                result = self.mod_mul_biguint(&result, &result, modulus);
+            }
+            if digit != 0 {
+                result = self.mod_mul_biguint(&result, &powers[digit as usize - 1], modulus);
+            }
+        }
+        result
+    }
+
+    fn mod_exp_biguint_variable(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        let one = self.constant_biguint(&BigUint::one());
+        let one = reduce_biguint(self, &one, modulus);
+        let base = reduce_biguint(self, base, modulus);
+
+        // `table[i]` is `base^i`.
+        let mut table = vec![one, base.clone()];
+        for _ in 2..1 << MOD_EXP_WINDOW_BITS {
+            let power = self.mod_mul_biguint(table.last().unwrap(), &base, modulus);
+            table.push(power);
+        }
+
+        let bits = exponent
+            .limbs
+            .iter()
+            .flat_map(|limb| self.split_le(limb.0, 32))
+            .collect::<Vec<_>>();
+        let mut result = table[0].clone();
+        for (i, window) in bits.chunks(MOD_EXP_WINDOW_BITS).rev().enumerate() {
+            let index = self.le_sum(window.iter());
+            let selected = BigUintTarget {
+                limbs: (0..modulus.num_limbs())
+                    .map(|j| {
+                        let column = table.iter().map(|power| power.limbs[j].0).collect();
+                        U32Target(self.random_access(index, column))
+                    })
+                    .collect(),
+            };
+
+            if i == 0 {
+                result = selected;
+            } else {
+                for _ in 0..MOD_EXP_WINDOW_BITS {
+                    result = self.mod_mul_biguint(&result, &result, modulus);
+                }
+                result = self.mod_mul_biguint(&result, &selected, modulus);
+            }
+        }
+        result
+    }
+}
+
+/// Returns `x mod modulus`, constrained to be less than `modulus`.
+fn reduce_biguint<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: &BigUintTarget,
+    modulus: &BigUintTarget,
+) -> BigUintTarget {
+    let (div, rem) = builder.div_rem_biguint(x, modulus);
+    // `div_rem_biguint` doesn't range-check the quotient, and only constrains the remainder to be
+    // at most `modulus`.
+    range_check_u32_circuit(builder, div.limbs);
+    let modulus_le_rem = builder.cmp_biguint(modulus, &rem);
+    builder.assert_zero(modulus_le_rem.target);
+    rem
 }
 
 pub trait WitnessBigUint<F: PrimeField64>: Witness<F> {
@@ -347,11 +521,12 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use num::{BigUint, FromPrimitive, Integer};
+    use num::{BigUint, FromPrimitive, Integer, Zero};
     use plonky2::iop::witness::PartialWitness;
     use plonky2::plonk::circuit_builder::CircuitBuilder;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2::plonk::public_input_schema::PublicInputValue;
     use rand::rngs::OsRng;
     use rand::Rng;
 
@@ -384,7 +559,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -414,7 +589,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -444,7 +619,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -470,7 +645,33 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_biguint_public_input() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut rng = OsRng;
+
+        let x_value = BigUint::from_u128(rng.gen()).unwrap();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = builder.add_virtual_biguint_target(4);
+        builder.register_named_public_biguint("x", &x);
+        pw.set_biguint_target(&x, &x_value);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        assert_eq!(
+            proof.public_input("x", &data.common)?,
+            PublicInputValue::BigUint(x_value)
+        );
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -503,6 +704,54 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_biguint_mod_exp() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut rng = OsRng;
+
+        let modulus_value = BigUint::from_slice(&rng.gen::<[u32; 8]>());
+        let base_value = BigUint::from_slice(&rng.gen::<[u32; 9]>());
+        let other_value = BigUint::from_slice(&rng.gen::<[u32; 8]>());
+        let exponent_value = BigUint::from_slice(&rng.gen::<[u32; 2]>());
+        let fixed_exponents = [
+            BigUint::zero(),
+            BigUint::from(65537u32),
+            BigUint::from_slice(&rng.gen::<[u32; 2]>()),
+        ];
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let modulus = builder.add_virtual_biguint_target(8);
+        let base = builder.add_virtual_biguint_target(9);
+        let other = builder.constant_biguint(&other_value);
+        let exponent = builder.add_virtual_biguint_target(2);
+        pw.set_biguint_target(&modulus, &modulus_value);
+        pw.set_biguint_target(&base, &base_value);
+        pw.set_biguint_target(&exponent, &exponent_value);
+
+        let mut results = vec![builder.mod_mul_biguint(&base, &other, &modulus)];
+        let mut expected = vec![(&base_value * &other_value) % &modulus_value];
+        for fixed_exponent in &fixed_exponents {
+            results.push(builder.mod_exp_biguint(&base, fixed_exponent, &modulus));
+            expected.push(base_value.modpow(fixed_exponent, &modulus_value));
+        }
+        results.push(builder.mod_exp_biguint_variable(&base, &exponent, &modulus));
+        expected.push(base_value.modpow(&exponent_value, &modulus_value));
+
+        for (result, expected_value) in results.iter().zip(&expected) {
+            let expected = builder.constant_biguint(expected_value);
+            builder.connect_biguint(result, &expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        Ok(data.verify(proof)?)
     }
 }
@@ -294,7 +294,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -359,7 +359,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -389,7 +389,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -421,7 +421,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -454,7 +454,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -481,6 +481,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 }
@@ -113,6 +113,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 }
@@ -1,14 +1,16 @@
 use alloc::vec;
+use alloc::vec::Vec;
 
 use num::BigUint;
 use plonky2::field::extension::Extendable;
 use plonky2::field::types::Field;
 use plonky2::hash::hash_types::RichField;
 use plonky2::hash::keccak::KeccakHash;
+use plonky2::iop::target::Target;
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::plonk::config::{GenericHashOut, Hasher};
 
-use crate::curve::curve_types::{Curve, CurveScalar};
+use crate::curve::curve_types::{AffinePoint, Curve, CurveScalar};
 use crate::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
 use crate::gadgets::curve_windowed_mul::CircuitBuilderWindowedMul;
 use crate::gadgets::nonnative::NonNativeTarget;
@@ -30,18 +32,111 @@ pub fn curve_msm_circuit<C: Curve, F: RichField + Extendable<D>, const D: usize>
     assert_eq!(limbs_n.len(), limbs_m.len());
     let num_limbs = limbs_n.len();
 
+    let rando = msm_starting_point::<C, F>();
+    let precomputation = pair_precomputation(builder, p, q, rando);
+
+    let four = builder.constant(F::from_canonical_usize(4));
+
+    let zero = builder.zero();
+    let mut result = builder.constant_affine_point(rando);
+    for (limb_n, limb_m) in limbs_n.into_iter().zip(limbs_m).rev() {
+        result = builder.curve_repeated_double(&result, 2);
+        let index = builder.mul_add(four, limb_m, limb_n);
+        let r = builder.random_access_curve_points(index, precomputation.clone());
+        let is_zero = builder.is_equal(index, zero);
+        let should_add = builder.not(is_zero);
+        result = builder.curve_conditional_add(&result, &r, should_add);
+    }
+    let starting_point_multiplied = (0..2 * num_limbs).fold(rando, |acc, _| acc.double());
+    let to_add = builder.constant_affine_point(-starting_point_multiplied);
+    result = builder.curve_add(&result, &to_add);
+
+    result
+}
+
+/// Computes `sum_i scalars[i] * points[i]` using windowed MSM, with a 2-bit window. The points are
+/// processed in pairs as in `curve_msm_circuit`, but all the pairs share one chain of doublings,
+/// which makes this much cheaper than calling `curve_msm_circuit` repeatedly.
+/// Note: Doesn't work if `points[2*i] == ±points[2*i + 1]` for some `i`.
+pub fn curve_msm_many_circuit<C: Curve, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    points: &[AffinePointTarget<C>],
+    scalars: &[NonNativeTarget<C::ScalarField>],
+) -> AffinePointTarget<C> {
+    assert_eq!(points.len(), scalars.len());
+    assert!(!points.is_empty());
+    let limbs: Vec<Vec<Target>> = scalars
+        .iter()
+        .map(|scalar| builder.split_nonnative_to_2_bit_limbs(scalar))
+        .collect();
+    let num_limbs = limbs[0].len();
+    assert!(limbs.iter().all(|l| l.len() == num_limbs));
+
+    let rando = msm_starting_point::<C, F>();
+    let four = builder.constant(F::from_canonical_usize(4));
+
+    // For each pair of points, the precomputed table and the index into it for each window. A
+    // trailing unpaired point uses the table `[p, p, 2p, 3p]`, whose first entry is never added.
+    let mut tables = Vec::new();
+    for (pair, pair_limbs) in points.chunks(2).zip(limbs.chunks(2)) {
+        match (pair, pair_limbs) {
+            ([p, q], [limbs_n, limbs_m]) => {
+                let precomputation = pair_precomputation(builder, p, q, rando);
+                let indices: Vec<_> = limbs_n
+                    .iter()
+                    .zip(limbs_m)
+                    .map(|(&limb_n, &limb_m)| builder.mul_add(four, limb_m, limb_n))
+                    .collect();
+                tables.push((precomputation, indices));
+            }
+            ([p], [limbs_n]) => {
+                let double = builder.curve_double(p);
+                let triple = builder.curve_add(&double, p);
+                let precomputation = vec![p.clone(), p.clone(), double, triple];
+                tables.push((precomputation, limbs_n.clone()));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let zero = builder.zero();
+    let mut result = builder.constant_affine_point(rando);
+    for i in (0..num_limbs).rev() {
+        result = builder.curve_repeated_double(&result, 2);
+        for (precomputation, indices) in &tables {
+            let r = builder.random_access_curve_points(indices[i], precomputation.clone());
+            let is_zero = builder.is_equal(indices[i], zero);
+            let should_add = builder.not(is_zero);
+            result = builder.curve_conditional_add(&result, &r, should_add);
+        }
+    }
+    let starting_point_multiplied = (0..2 * num_limbs).fold(rando, |acc, _| acc.double());
+    let to_add = builder.constant_affine_point(-starting_point_multiplied);
+    builder.curve_add(&result, &to_add)
+}
+
+/// The point from which the windowed MSMs start, to avoid adding the point at infinity.
+fn msm_starting_point<C: Curve, F: RichField>() -> AffinePoint<C> {
     let hash_0 = KeccakHash::<32>::hash_no_pad(&[F::ZERO]);
     let hash_0_scalar = C::ScalarField::from_noncanonical_biguint(BigUint::from_bytes_le(
         &GenericHashOut::<F>::to_bytes(&hash_0),
     ));
-    let rando = (CurveScalar(hash_0_scalar) * C::GENERATOR_PROJECTIVE).to_affine();
+    (CurveScalar(hash_0_scalar) * C::GENERATOR_PROJECTIVE).to_affine()
+}
+
+/// Precomputes `precomputation[i + 4*j] = i*p + j*q` for `i,j=0..4`.
+fn pair_precomputation<C: Curve, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    p: &AffinePointTarget<C>,
+    q: &AffinePointTarget<C>,
+    rando: AffinePoint<C>,
+) -> Vec<AffinePointTarget<C>> {
     let rando_t = builder.constant_affine_point(rando);
     let neg_rando = builder.constant_affine_point(-rando);
 
-    // Precomputes `precomputation[i + 4*j] = i*p + j*q` for `i,j=0..4`.
     let mut precomputation = vec![p.clone(); 16];
     let mut cur_p = rando_t.clone();
-    let mut cur_q = rando_t.clone();
+    let mut cur_q = rando_t;
     for i in 0..4 {
         precomputation[i] = cur_p.clone();
         precomputation[4 * i] = cur_q.clone();
@@ -58,24 +153,7 @@ pub fn curve_msm_circuit<C: Curve, F: RichField + Extendable<D>, const D: usize>
                 builder.curve_add(&precomputation[i], &precomputation[4 * j]);
         }
     }
-
-    let four = builder.constant(F::from_canonical_usize(4));
-
-    let zero = builder.zero();
-    let mut result = rando_t;
-    for (limb_n, limb_m) in limbs_n.into_iter().zip(limbs_m).rev() {
-        result = builder.curve_repeated_double(&result, 2);
-        let index = builder.mul_add(four, limb_m, limb_n);
-        let r = builder.random_access_curve_points(index, precomputation.clone());
-        let is_zero = builder.is_equal(index, zero);
-        let should_add = builder.not(is_zero);
-        result = builder.curve_conditional_add(&result, &r, should_add);
-    }
-    let starting_point_multiplied = (0..2 * num_limbs).fold(rando, |acc, _| acc.double());
-    let to_add = builder.constant_affine_point(-starting_point_multiplied);
-    result = builder.curve_add(&result, &to_add);
-
-    result
+    precomputation
 }
 
 #[cfg(test)]
@@ -133,6 +211,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 }
@@ -215,7 +215,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -249,6 +249,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 }
@@ -1,24 +1,44 @@
+use alloc::vec;
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
+use num::BigUint;
 use plonky2::field::extension::Extendable;
+use plonky2::field::secp256k1_base::Secp256K1Base;
 use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2::field::types::{Field, PrimeField};
 use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartitionWitness, Witness};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_derive::CircuitType;
+use plonky2_u32::gadgets::arithmetic_u32::U32Target;
+use plonky2_u32::gadgets::bytes::{ByteArrayTarget, CircuitBuilderBytes};
 
-use crate::curve::curve_types::Curve;
+use crate::curve::curve_types::{AffinePoint, Curve, CurveScalar};
+use crate::curve::ecdsa::{ECDSASecretKey, ECDSASignature};
 use crate::curve::secp256k1::Secp256K1;
+use crate::gadgets::biguint::{
+    BigUintTarget, CircuitBuilderBiguint, GeneratedValuesBigUint, WitnessBigUint,
+};
 use crate::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
 use crate::gadgets::curve_fixed_base::fixed_base_curve_mul_circuit;
+use crate::gadgets::curve_msm::curve_msm_many_circuit;
 use crate::gadgets::glv::CircuitBuilderGlv;
 use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = ECDSASecretKey<C>)]
 pub struct ECDSASecretKeyTarget<C: Curve>(pub NonNativeTarget<C::ScalarField>);
 
 #[derive(Clone, Debug)]
 pub struct ECDSAPublicKeyTarget<C: Curve>(pub AffinePointTarget<C>);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = ECDSASignature<C>)]
 pub struct ECDSASignatureTarget<C: Curve> {
     pub r: NonNativeTarget<C::ScalarField>,
     pub s: NonNativeTarget<C::ScalarField>,
@@ -49,17 +69,275 @@ pub fn verify_message_circuit<F: RichField + Extendable<D>, const D: usize>(
     builder.connect_nonnative(&r, &x);
 }
 
+/// Verifies a batch of signatures by checking a random linear combination of their verification
+/// equations `u1_i G + u2_i Q_i = R_i`, where the nonce points `R_i` are lifted from `r_i`. The
+/// 128-bit coefficients are derived in-circuit from a Poseidon hash of all the inputs, including
+/// the `R_i`. Compared to calling `verify_message_circuit` for each signature, the multiplications
+/// by the generator are merged into one, and all the other multiplications share their doublings.
+/// Like `ecrecover_circuit`, this only supports `R_i.x == r_i`. Signatures may share nonces, e.g. if
+/// a signature appears twice in the batch.
+pub fn batch_verify_message_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msgs: &[NonNativeTarget<Secp256K1Scalar>],
+    sigs: &[ECDSASignatureTarget<Secp256K1>],
+    pks: &[ECDSAPublicKeyTarget<Secp256K1>],
+) {
+    let num_sigs = msgs.len();
+    assert!(num_sigs > 0, "Cannot verify an empty batch");
+    assert_eq!(sigs.len(), num_sigs);
+    assert_eq!(pks.len(), num_sigs);
+
+    let mut nonces = Vec::with_capacity(num_sigs);
+    for ((msg, sig), pk) in msgs.iter().zip(sigs).zip(pks) {
+        builder.curve_assert_valid(&pk.0);
+
+        // As `r < n < p`, it is also a reduced base field element.
+        let x = NonNativeTarget::<Secp256K1Base> {
+            value: sig.r.value.clone(),
+            _phantom: PhantomData,
+        };
+        // `y` is range-checked and reduced, so that `R_i` has a unique encoding in the hash below.
+        let y = NonNativeTarget::<Secp256K1Base>::add_virtual(builder);
+        builder.add_simple_generator(NonceYGenerator::<F, D> {
+            msg: msg.clone(),
+            sig: sig.clone(),
+            pk: pk.clone(),
+            y: y.clone(),
+            _phantom: PhantomData,
+        });
+        let nonce = AffinePointTarget { x, y };
+        builder.curve_assert_valid(&nonce);
+        nonces.push(nonce);
+    }
+
+    let mut inputs = Vec::new();
+    for (((msg, sig), pk), nonce) in msgs.iter().zip(sigs).zip(pks).zip(&nonces) {
+        let values = [
+            &msg.value,
+            &sig.r.value,
+            &sig.s.value,
+            &pk.0.x.value,
+            &pk.0.y.value,
+            &nonce.y.value,
+        ];
+        inputs.extend(values.iter().flat_map(|v| v.limbs.iter().map(|l| l.0)));
+    }
+    let hash = builder.hash_n_to_m_no_pad::<PoseidonHash>(inputs, 2 * num_sigs);
+    let mut coeffs = Vec::with_capacity(num_sigs);
+    for elements in hash.chunks(2) {
+        let mut limbs = Vec::with_capacity(4);
+        for &x in elements {
+            let (low, high) = builder.split_low_high(x, 32, 64);
+            limbs.extend([U32Target(low), U32Target(high)]);
+        }
+        coeffs.push(NonNativeTarget::<Secp256K1Scalar> {
+            value: BigUintTarget { limbs },
+            _phantom: PhantomData,
+        });
+    }
+
+    // Checks `sum_i c_i u2_i Q_i - sum_i c_i R_i = -(sum_i c_i u1_i) G`, with each `c_i u2_i Q_i`
+    // split in two 128-bit multiplications by GLV.
+    let beta = builder.secp256k1_glv_beta();
+    let mut fixed_base_terms = Vec::with_capacity(num_sigs);
+    let mut terms = Vec::with_capacity(num_sigs);
+    for ((((msg, sig), pk), nonce), c) in msgs.iter().zip(sigs).zip(pks).zip(&nonces).zip(coeffs) {
+        let s_inv = builder.inv_nonnative(&sig.s);
+        let u1 = builder.mul_nonnative(msg, &s_inv);
+        let u2 = builder.mul_nonnative(&sig.r, &s_inv);
+        fixed_base_terms.push(builder.mul_nonnative(&c, &u1));
+
+        let k = builder.mul_nonnative(&c, &u2);
+        let (k1, k2, k1_neg, k2_neg) = builder.decompose_secp256k1_scalar(&k);
+        let beta_qx = builder.mul_nonnative(&beta, &pk.0.x);
+        let sq = AffinePointTarget::<Secp256K1> {
+            x: beta_qx,
+            y: pk.0.y.clone(),
+        };
+        terms.push([
+            (builder.curve_conditional_neg(&pk.0, k1_neg), k1),
+            (builder.curve_conditional_neg(&sq, k2_neg), k2),
+            (builder.curve_neg(nonce), c),
+        ]);
+    }
+
+    // The points of each pair in the MSM must not be equal or opposite. Nonces of valid signatures
+    // can be, and so can public keys, so a pair holds either the two GLV points of a signature, or
+    // a GLV point and a nonce, which differ unless a nonce is `±1` or `±λ` times a secret key.
+    let mut points = Vec::with_capacity(3 * num_sigs);
+    let mut scalars = Vec::with_capacity(3 * num_sigs);
+    for chunk in terms.chunks(2) {
+        let ordered = match chunk {
+            [[a_j, b_j, n_j], [a_k, b_k, n_k]] => vec![a_k, b_k, a_j, n_j, b_j, n_k],
+            [[a, b, n]] => vec![a, b, n],
+            _ => unreachable!(),
+        };
+        for (point, scalar) in ordered {
+            points.push(point.clone());
+            scalars.push(scalar.clone());
+        }
+    }
+
+    let fixed_base_scalar = builder.add_many_nonnative(&fixed_base_terms);
+    let fixed_base_point =
+        fixed_base_curve_mul_circuit(builder, Secp256K1::GENERATOR_AFFINE, &fixed_base_scalar);
+    let expected = builder.curve_neg(&fixed_base_point);
+    let sum = curve_msm_many_circuit(builder, &points, &scalars);
+    builder.connect_affine_point(&sum, &expected);
+}
+
+/// Recovers the public key which signed `msg`, given the parity `v` of the y-coordinate of the
+/// signer's nonce point `R`. Like the native `ecrecover`, this only supports `R.x == r`.
+pub fn ecrecover_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: NonNativeTarget<Secp256K1Scalar>,
+    sig: ECDSASignatureTarget<Secp256K1>,
+    v: BoolTarget,
+) -> ECDSAPublicKeyTarget<Secp256K1> {
+    let ECDSASignatureTarget { r, s } = sig;
+
+    // Lift `r` to `R`. As `r < n < p`, it is also a reduced base field element.
+    let x = NonNativeTarget::<Secp256K1Base> {
+        value: r.value.clone(),
+        _phantom: PhantomData,
+    };
+    let y = NonNativeTarget::<Secp256K1Base>::add_virtual(builder);
+    builder.add_simple_generator(LiftXGenerator::<F, D> {
+        x: x.clone(),
+        y: y.clone(),
+        v,
+        _phantom: PhantomData,
+    });
+    let rr = AffinePointTarget { x, y };
+    builder.curve_assert_valid(&rr);
+
+    // `y` must be reduced for its parity to be meaningful.
+    let max_y = builder.constant_biguint(&(Secp256K1Base::order() - 1u32));
+    let y_le_max = builder.cmp_biguint(&rr.y.value, &max_y);
+    builder.assert_one(y_le_max.target);
+    let y_bits = builder.split_le(rr.y.value.limbs[0].0, 32);
+    builder.connect(y_bits[0].target, v.target);
+
+    // `Q = r^-1 (s R - msg G)`.
+    let r_inv = builder.inv_nonnative(&r);
+    let msg_r_inv = builder.mul_nonnative(&msg, &r_inv);
+    let u1 = builder.neg_nonnative(&msg_r_inv);
+    let u2 = builder.mul_nonnative(&s, &r_inv);
+
+    let point1 = fixed_base_curve_mul_circuit(builder, Secp256K1::GENERATOR_AFFINE, &u1);
+    let point2 = builder.glv_mul(&rr, &u2);
+    ECDSAPublicKeyTarget(builder.curve_add(&point1, &point2))
+}
+
+/// Returns the 64 bytes of the big-endian coordinates of `pk`, whose Keccak-256 hash gives its
+/// Ethereum address.
+pub fn public_key_to_bytes_be_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    pk: &ECDSAPublicKeyTarget<Secp256K1>,
+) -> ByteArrayTarget {
+    let mut bytes = Vec::with_capacity(64);
+    for coordinate in [&pk.0.x, &pk.0.y] {
+        let limbs: Vec<_> = coordinate.value.limbs.iter().rev().copied().collect();
+        bytes.extend(builder.u32s_to_bytes_be(&limbs).0);
+    }
+    ByteArrayTarget(bytes)
+}
+
+/// Computes the y-coordinate with parity `v` of the point with x-coordinate `x`.
+#[derive(Debug)]
+struct LiftXGenerator<F: RichField + Extendable<D>, const D: usize> {
+    x: NonNativeTarget<Secp256K1Base>,
+    y: NonNativeTarget<Secp256K1Base>,
+    v: BoolTarget,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for LiftXGenerator<F, D> {
+    fn dependencies(&self) -> Vec<Target> {
+        let mut deps: Vec<_> = self.x.value.limbs.iter().map(|l| l.0).collect();
+        deps.push(self.v.target);
+        deps
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let x = Secp256K1Base::from_noncanonical_biguint(
+            witness.get_biguint_target(self.x.value.clone()),
+        );
+        let v = witness.get_target(self.v.target) == F::ONE;
+
+        // If `x` isn't on the curve, the circuit is unsatisfiable, so any value will do.
+        let y = (x.cube() + Secp256K1::B)
+            .sqrt()
+            .unwrap_or(Secp256K1Base::ZERO);
+        let y = y.to_canonical_biguint();
+        let y = if y.bit(0) == v || y == BigUint::from(0u8) {
+            y
+        } else {
+            Secp256K1Base::order() - y
+        };
+        out_buffer.set_biguint_target(&self.y.value, &y);
+    }
+}
+
+/// Computes the y-coordinate of the nonce point `R = u1 G + u2 Q` of a signature.
+#[derive(Debug)]
+struct NonceYGenerator<F: RichField + Extendable<D>, const D: usize> {
+    msg: NonNativeTarget<Secp256K1Scalar>,
+    sig: ECDSASignatureTarget<Secp256K1>,
+    pk: ECDSAPublicKeyTarget<Secp256K1>,
+    y: NonNativeTarget<Secp256K1Base>,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for NonceYGenerator<F, D> {
+    fn dependencies(&self) -> Vec<Target> {
+        [
+            &self.msg.value,
+            &self.sig.r.value,
+            &self.sig.s.value,
+            &self.pk.0.x.value,
+            &self.pk.0.y.value,
+        ]
+        .iter()
+        .flat_map(|v| v.limbs.iter().map(|l| l.0))
+        .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let get_scalar = |x: &NonNativeTarget<Secp256K1Scalar>| {
+            Secp256K1Scalar::from_noncanonical_biguint(witness.get_biguint_target(x.value.clone()))
+        };
+        let get_base = |x: &NonNativeTarget<Secp256K1Base>| {
+            Secp256K1Base::from_noncanonical_biguint(witness.get_biguint_target(x.value.clone()))
+        };
+        let msg = get_scalar(&self.msg);
+        let r = get_scalar(&self.sig.r);
+        let s = get_scalar(&self.sig.s);
+        let pk = AffinePoint::<Secp256K1>::nonzero(get_base(&self.pk.0.x), get_base(&self.pk.0.y));
+
+        let s_inv = s.inverse();
+        let nonce = (CurveScalar(msg * s_inv) * Secp256K1::GENERATOR_PROJECTIVE
+            + CurveScalar(r * s_inv) * pk.to_projective())
+        .to_affine();
+        out_buffer.set_biguint_target(&self.y.value, &nonce.y.to_canonical_biguint());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use plonky2::field::types::Sample;
-    use plonky2::iop::witness::PartialWitness;
+    use keccak_hash::keccak;
+    use plonky2::field::types::{PrimeField64, Sample};
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
 
     use super::*;
     use crate::curve::curve_types::CurveScalar;
-    use crate::curve::ecdsa::{sign_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature};
+    use crate::curve::ecdsa::{
+        eth_address, sign_message, sign_message_recoverable, ECDSAPublicKey, ECDSASecretKey,
+        ECDSASignature,
+    };
 
     fn test_ecdsa_circuit_with_config(config: CircuitConfig) -> Result<()> {
         const D: usize = 2;
@@ -94,7 +372,36 @@ mod tests {
         dbg!(builder.num_gates());
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_signature_target_witness() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let sig = sign_message(
+            Secp256K1Scalar::rand(),
+            ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::rand()),
+        );
+        let sig_target = ECDSASignatureTarget::<Secp256K1>::add_virtual(&mut builder);
+        let expected = ECDSASignatureTarget {
+            r: builder.constant_nonnative(sig.r),
+            s: builder.constant_nonnative(sig.s),
+        };
+        CircuitType::connect(&mut builder, &sig_target, &expected);
+
+        let mut pw = PartialWitness::new();
+        sig_target.set_witness(&mut pw, &sig);
+        assert_eq!(sig_target.get_witness(&pw), sig);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -108,4 +415,111 @@ mod tests {
     fn test_ecdsa_circuit_wide() -> Result<()> {
         test_ecdsa_circuit_with_config(CircuitConfig::wide_ecc_config())
     }
+
+    /// A message, its signature and the signer's public key.
+    type SignedMessage = (
+        Secp256K1Scalar,
+        ECDSASignature<Secp256K1>,
+        ECDSAPublicKey<Secp256K1>,
+    );
+
+    fn random_signed_message() -> SignedMessage {
+        let msg = Secp256K1Scalar::rand();
+        let sk = ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::rand());
+        (msg, sign_message(msg, sk), sk.to_public())
+    }
+
+    fn test_batch_ecdsa_circuit_with_messages(signed_msgs: &[SignedMessage]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        let mut msg_targets = Vec::new();
+        let mut sig_targets = Vec::new();
+        let mut pk_targets = Vec::new();
+        for (msg, sig, pk) in signed_msgs {
+            let sig_target = ECDSASignatureTarget::<Secp256K1>::add_virtual(&mut builder);
+            sig_target.set_witness(&mut pw, sig);
+            msg_targets.push(builder.constant_nonnative(*msg));
+            sig_targets.push(sig_target);
+            pk_targets.push(ECDSAPublicKeyTarget(builder.constant_affine_point(pk.0)));
+        }
+        batch_verify_message_circuit(&mut builder, &msg_targets, &sig_targets, &pk_targets);
+
+        dbg!(builder.num_gates());
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_batch_ecdsa_circuit() -> Result<()> {
+        // An odd number of signatures, so that the last one isn't paired with another.
+        let signed_msgs: Vec<_> = (0..3).map(|_| random_signed_message()).collect();
+        test_batch_ecdsa_circuit_with_messages(&signed_msgs)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_batch_ecdsa_circuit_shared_nonce() -> Result<()> {
+        let signed_msg = random_signed_message();
+        let signed_msgs = [signed_msg, signed_msg, random_signed_message()];
+        test_batch_ecdsa_circuit_with_messages(&signed_msgs)
+    }
+
+    #[test]
+    #[ignore]
+    #[should_panic]
+    fn test_batch_ecdsa_circuit_tampered() {
+        let mut signed_msgs = [random_signed_message(), random_signed_message()];
+        signed_msgs[1].0 += Secp256K1Scalar::ONE;
+        test_batch_ecdsa_circuit_with_messages(&signed_msgs).unwrap()
+    }
+
+    #[test]
+    #[ignore]
+    fn test_ecrecover_circuit() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let msg = Secp256K1Scalar::rand();
+        let sk = ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::rand());
+        let pk = sk.to_public();
+        let (sig, recovery_id) = sign_message_recoverable(msg, sk);
+        let v = recovery_id & 1 == 1;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let msg_target = builder.constant_nonnative(msg);
+        let sig_target = ECDSASignatureTarget::<Secp256K1>::add_virtual(&mut builder);
+        let v_target = builder.add_virtual_bool_target_safe();
+        let pk_target = ecrecover_circuit(&mut builder, msg_target, sig_target.clone(), v_target);
+        let expected = builder.constant_affine_point(pk.0);
+        builder.connect_affine_point(&pk_target.0, &expected);
+        let pk_bytes = public_key_to_bytes_be_circuit(&mut builder, &pk_target);
+        let pk_byte_targets: Vec<_> = pk_bytes.0.iter().map(|b| b.0).collect();
+        builder.register_public_inputs(&pk_byte_targets);
+
+        let mut pw = PartialWitness::new();
+        sig_target.set_witness(&mut pw, &sig);
+        pw.set_bool_target(v_target, v);
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+
+        // Hashing the public key's bytes gives its Ethereum address.
+        let bytes: Vec<u8> = proof
+            .public_inputs
+            .iter()
+            .map(|x| x.to_canonical_u64() as u8)
+            .collect();
+        assert_eq!(keccak(bytes).0[12..], eth_address(pk));
+        data.verify(proof)?;
+        Ok(())
+    }
 }
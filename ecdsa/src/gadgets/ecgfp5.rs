@@ -0,0 +1,430 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::BigUint;
+use plonky2::field::ecgfp5_scalar::EcGFp5Scalar;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::keccak::KeccakHash;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::config::{GenericHashOut, Hasher};
+
+use crate::curve::ecgfp5::{EcGFp5Point, GFp5, ECGFP5_A, ECGFP5_B, ECGFP5_GENERATOR};
+use crate::gadgets::gfp5::{CircuitBuilderGFp5, GFp5Target};
+
+const WINDOW_SIZE: usize = 4;
+
+/// A Target representing an affine point on ecGFp5. We use incomplete arithmetic for efficiency,
+/// so we assume these points are not zero.
+#[derive(Copy, Clone, Debug)]
+pub struct EcGFp5PointTarget {
+    pub x: GFp5Target,
+    pub y: GFp5Target,
+}
+
+impl<F: RichField> CircuitType<F> for EcGFp5PointTarget {
+    type Native = EcGFp5Point;
+
+    /// Adds a point constrained to be in the subgroup of order `n`.
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        let p = Self {
+            x: builder.add_virtual_gfp5_target(),
+            y: builder.add_virtual_gfp5_target(),
+        };
+        builder.ecgfp5_assert_valid(&p);
+        p
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &EcGFp5Point) {
+        assert!(
+            !value.zero,
+            "Zero points can't be assigned to an EcGFp5PointTarget"
+        );
+        self.x.set_witness(witness, &value.x);
+        self.y.set_witness(witness, &value.y);
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> EcGFp5Point {
+        EcGFp5Point::nonzero(self.x.get_witness(witness), self.y.get_witness(witness))
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect_ecgfp5_point(x, y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.select_ecgfp5_point(b, x, y)
+    }
+}
+
+pub trait CircuitBuilderEcGFp5<F: RichField + Extendable<D>, const D: usize> {
+    fn constant_ecgfp5_point(&mut self, point: EcGFp5Point) -> EcGFp5PointTarget;
+
+    fn connect_ecgfp5_point(&mut self, p1: &EcGFp5PointTarget, p2: &EcGFp5PointTarget);
+
+    /// Asserts that `p` is on the curve and in the subgroup of order `n`.
+    fn ecgfp5_assert_valid(&mut self, p: &EcGFp5PointTarget);
+
+    fn ecgfp5_neg(&mut self, p: &EcGFp5PointTarget) -> EcGFp5PointTarget;
+
+    fn ecgfp5_double(&mut self, p: &EcGFp5PointTarget) -> EcGFp5PointTarget;
+
+    /// Add two points, which are assumed to be non-equal.
+    fn ecgfp5_add(&mut self, p1: &EcGFp5PointTarget, p2: &EcGFp5PointTarget) -> EcGFp5PointTarget;
+
+    fn select_ecgfp5_point(
+        &mut self,
+        b: BoolTarget,
+        p1: &EcGFp5PointTarget,
+        p2: &EcGFp5PointTarget,
+    ) -> EcGFp5PointTarget;
+
+    fn random_access_ecgfp5_points(
+        &mut self,
+        access_index: Target,
+        v: &[EcGFp5PointTarget],
+    ) -> EcGFp5PointTarget;
+
+    /// Computes `n * p` using a 4-bit window, where `n` is given by its little-endian bits.
+    fn ecgfp5_scalar_mul_windowed(
+        &mut self,
+        p: &EcGFp5PointTarget,
+        bits: &[BoolTarget],
+    ) -> EcGFp5PointTarget;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderEcGFp5<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn constant_ecgfp5_point(&mut self, point: EcGFp5Point) -> EcGFp5PointTarget {
+        debug_assert!(!point.zero);
+        EcGFp5PointTarget {
+            x: self.constant_gfp5(point.x),
+            y: self.constant_gfp5(point.y),
+        }
+    }
+
+    fn connect_ecgfp5_point(&mut self, p1: &EcGFp5PointTarget, p2: &EcGFp5PointTarget) {
+        self.connect_gfp5(&p1.x, &p2.x);
+        self.connect_gfp5(&p1.y, &p2.y);
+    }
+
+    fn ecgfp5_assert_valid(&mut self, p: &EcGFp5PointTarget) {
+        let a = self.constant_gfp5(ECGFP5_A);
+        let b = self.constant_gfp5(ECGFP5_B);
+
+        let y_squared = self.square_gfp5(&p.y);
+        let x_squared = self.square_gfp5(&p.x);
+        let x_squared_plus_a = self.add_gfp5(&x_squared, &a);
+        let x_cubed_plus_a_x = self.mul_gfp5(&x_squared_plus_a, &p.x);
+        let rhs = self.add_gfp5(&x_cubed_plus_a_x, &b);
+        self.connect_gfp5(&y_squared, &rhs);
+
+        // The subgroup is made of the points for which `x - 2/3` is a non-zero square.
+        let two_thirds = self.constant_gfp5(GFp5::TWO / GFp5::from_canonical_u64(3));
+        let x = self.sub_gfp5(&p.x, &two_thirds);
+        let root = self.sqrt_gfp5(&x);
+        self.inverse_gfp5(&root);
+    }
+
+    fn ecgfp5_neg(&mut self, p: &EcGFp5PointTarget) -> EcGFp5PointTarget {
+        EcGFp5PointTarget {
+            x: p.x,
+            y: self.neg_gfp5(&p.y),
+        }
+    }
+
+    fn ecgfp5_double(&mut self, p: &EcGFp5PointTarget) -> EcGFp5PointTarget {
+        let EcGFp5PointTarget { x, y } = p;
+        let double_y = self.add_gfp5(y, y);
+        let inv_double_y = self.inverse_gfp5(&double_y);
+        let x_squared = self.square_gfp5(x);
+        let double_x_squared = self.add_gfp5(&x_squared, &x_squared);
+        let triple_x_squared = self.add_gfp5(&double_x_squared, &x_squared);
+
+        let a = self.constant_gfp5(ECGFP5_A);
+        let triple_xx_a = self.add_gfp5(&triple_x_squared, &a);
+        let lambda = self.mul_gfp5(&triple_xx_a, &inv_double_y);
+        let lambda_squared = self.square_gfp5(&lambda);
+        let x_double = self.add_gfp5(x, x);
+
+        let x3 = self.sub_gfp5(&lambda_squared, &x_double);
+
+        let x_diff = self.sub_gfp5(x, &x3);
+        let lambda_x_diff = self.mul_gfp5(&lambda, &x_diff);
+
+        let y3 = self.sub_gfp5(&lambda_x_diff, y);
+
+        EcGFp5PointTarget { x: x3, y: y3 }
+    }
+
+    fn ecgfp5_add(&mut self, p1: &EcGFp5PointTarget, p2: &EcGFp5PointTarget) -> EcGFp5PointTarget {
+        let EcGFp5PointTarget { x: x1, y: y1 } = p1;
+        let EcGFp5PointTarget { x: x2, y: y2 } = p2;
+
+        let u = self.sub_gfp5(y2, y1);
+        let v = self.sub_gfp5(x2, x1);
+        let v_inv = self.inverse_gfp5(&v);
+        let s = self.mul_gfp5(&u, &v_inv);
+        let s_squared = self.square_gfp5(&s);
+        let x_sum = self.add_gfp5(x2, x1);
+        let x3 = self.sub_gfp5(&s_squared, &x_sum);
+        let x_diff = self.sub_gfp5(x1, &x3);
+        let prod = self.mul_gfp5(&s, &x_diff);
+        let y3 = self.sub_gfp5(&prod, y1);
+
+        EcGFp5PointTarget { x: x3, y: y3 }
+    }
+
+    fn select_ecgfp5_point(
+        &mut self,
+        b: BoolTarget,
+        p1: &EcGFp5PointTarget,
+        p2: &EcGFp5PointTarget,
+    ) -> EcGFp5PointTarget {
+        EcGFp5PointTarget {
+            x: self.select_gfp5(b, &p1.x, &p2.x),
+            y: self.select_gfp5(b, &p1.y, &p2.y),
+        }
+    }
+
+    fn random_access_ecgfp5_points(
+        &mut self,
+        access_index: Target,
+        v: &[EcGFp5PointTarget],
+    ) -> EcGFp5PointTarget {
+        let mut select_coordinate = |coordinates: Vec<GFp5Target>| {
+            GFp5Target(core::array::from_fn(|i| {
+                let limbs = coordinates.iter().map(|c| c.0[i]).collect();
+                self.random_access(access_index, limbs)
+            }))
+        };
+
+        let x = select_coordinate(v.iter().map(|p| p.x).collect());
+        let y = select_coordinate(v.iter().map(|p| p.y).collect());
+        EcGFp5PointTarget { x, y }
+    }
+
+    fn ecgfp5_scalar_mul_windowed(
+        &mut self,
+        p: &EcGFp5PointTarget,
+        bits: &[BoolTarget],
+    ) -> EcGFp5PointTarget {
+        let starting_point = starting_point::<F>();
+        let windows: Vec<Target> = bits
+            .chunks(WINDOW_SIZE)
+            .map(|window| self.le_sum(window.iter()))
+            .collect();
+        let starting_point_multiplied =
+            (0..windows.len() * WINDOW_SIZE).fold(starting_point, |acc, _| acc.double());
+
+        // `precomputation[i] = i * p` for `i=1..16`. The window being zero is checked below, so
+        // `precomputation[0]` can be arbitrary.
+        let mut precomputation = vec![*p, *p, self.ecgfp5_double(p)];
+        for i in 3..1 << WINDOW_SIZE {
+            let multiple = self.ecgfp5_add(&precomputation[i - 1], p);
+            precomputation.push(multiple);
+        }
+
+        let zero = self.zero();
+        let mut result = self.constant_ecgfp5_point(starting_point);
+        for &window in windows.iter().rev() {
+            for _ in 0..WINDOW_SIZE {
+                result = self.ecgfp5_double(&result);
+            }
+            let to_add = self.random_access_ecgfp5_points(window, &precomputation);
+            let sum = self.ecgfp5_add(&result, &to_add);
+            let is_zero = self.is_equal(window, zero);
+            result = self.select_ecgfp5_point(is_zero, &result, &sum);
+        }
+
+        let to_add = self.constant_ecgfp5_point(-starting_point_multiplied);
+        self.ecgfp5_add(&result, &to_add)
+    }
+}
+
+/// Computes `scalar * base` for a fixed `base` using a 4-bit window, where `scalar` is given by
+/// its little-endian bits.
+pub fn fixed_base_ecgfp5_mul_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    base: EcGFp5Point,
+    bits: &[BoolTarget],
+) -> EcGFp5PointTarget {
+    let starting_point = starting_point::<F>();
+
+    let zero = builder.zero();
+    let mut result = builder.constant_ecgfp5_point(starting_point);
+    // `s * P = sum s_i * P_i` with `P_i = (16^i) * P` and `s = sum s_i * (16^i)`.
+    let mut point = base;
+    for window in bits.chunks(WINDOW_SIZE) {
+        let limb = builder.le_sum(window.iter());
+        // `muls_point[t] = t * P_i` for `t=1..16`. `limb == zero` is checked below, so the point in
+        // position 0 can be arbitrary.
+        let mut muls_point: Vec<_> = (1..1 << WINDOW_SIZE)
+            .scan(EcGFp5Point::ZERO, |acc, _| {
+                *acc = *acc + point;
+                Some(builder.constant_ecgfp5_point(*acc))
+            })
+            .collect();
+        muls_point.insert(0, muls_point[0]);
+        let r = builder.random_access_ecgfp5_points(limb, &muls_point);
+        let sum = builder.ecgfp5_add(&result, &r);
+        let is_zero = builder.is_equal(limb, zero);
+        result = builder.select_ecgfp5_point(is_zero, &result, &sum);
+
+        for _ in 0..WINDOW_SIZE {
+            point = point.double();
+        }
+    }
+
+    let to_add = builder.constant_ecgfp5_point(-starting_point);
+    builder.ecgfp5_add(&result, &to_add)
+}
+
+/// A point with no known relation to the inputs, from which scalar multiplications start because
+/// we don't support arithmetic with the zero point.
+fn starting_point<F: RichField>() -> EcGFp5Point {
+    let hash_0 = KeccakHash::<32>::hash_no_pad(&[F::ZERO]);
+    let hash_0_scalar = EcGFp5Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(
+        &GenericHashOut::<F>::to_bytes(&hash_0),
+    ));
+    ECGFP5_GENERATOR.mul_scalar(hash_0_scalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::{PrimeField, Sample};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+
+    fn scalar_bits<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        n: EcGFp5Scalar,
+    ) -> Vec<BoolTarget> {
+        let n = n.to_canonical_biguint();
+        (0..EcGFp5Scalar::BITS as u64)
+            .map(|i| builder.constant_bool(n.bit(i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_ecgfp5_point_is_valid() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let p = ECGFP5_GENERATOR.mul_scalar(EcGFp5Scalar::rand());
+        let p_target = EcGFp5PointTarget::add_virtual(&mut builder);
+        let neg_p_target = builder.ecgfp5_neg(&p_target);
+        builder.ecgfp5_assert_valid(&neg_p_target);
+
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        p_target.set_witness(&mut pw, &p);
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ecgfp5_point_is_not_in_subgroup() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // The point of order two is on the curve, but not in the subgroup.
+        let two_thirds = GFp5::TWO / GFp5::from_canonical_u64(3);
+        let p = EcGFp5Point::nonzero(two_thirds, GFp5::ZERO);
+        let p_target = builder.constant_ecgfp5_point(p);
+        builder.ecgfp5_assert_valid(&p_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new()).unwrap();
+
+        data.verify(proof).unwrap()
+    }
+
+    #[test]
+    fn test_ecgfp5_add_double() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let g = ECGFP5_GENERATOR;
+        let p = g.mul_scalar(EcGFp5Scalar::rand());
+        let g_target = builder.constant_ecgfp5_point(g);
+        let p_target = builder.constant_ecgfp5_point(p);
+
+        let sum = builder.ecgfp5_add(&g_target, &p_target);
+        let expected_sum = builder.constant_ecgfp5_point(g + p);
+        builder.connect_ecgfp5_point(&sum, &expected_sum);
+
+        let double = builder.ecgfp5_double(&p_target);
+        let expected_double = builder.constant_ecgfp5_point(p.double());
+        builder.connect_ecgfp5_point(&double, &expected_double);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_ecgfp5_scalar_mul() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let g = ECGFP5_GENERATOR;
+        let p = g.mul_scalar(EcGFp5Scalar::rand());
+        let n = EcGFp5Scalar::rand();
+        let m = EcGFp5Scalar::rand();
+
+        let p_target = builder.constant_ecgfp5_point(p);
+        let n_bits = scalar_bits(&mut builder, n);
+        let m_bits = scalar_bits(&mut builder, m);
+        let np = builder.ecgfp5_scalar_mul_windowed(&p_target, &n_bits);
+        let mg = fixed_base_ecgfp5_mul_circuit(&mut builder, g, &m_bits);
+        let expected_np = builder.constant_ecgfp5_point(p.mul_scalar(n));
+        let expected_mg = builder.constant_ecgfp5_point(g.mul_scalar(m));
+        builder.connect_ecgfp5_point(&np, &expected_np);
+        builder.connect_ecgfp5_point(&mg, &expected_mg);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        Ok(data.verify(proof)?)
+    }
+}
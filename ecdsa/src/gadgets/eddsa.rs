@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+use plonky2::field::ed25519_base::Ed25519Base;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_derive::CircuitType;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use plonky2_u32::gadgets::bytes::{ByteArrayTarget, CircuitBuilderBytes};
+use plonky2_u32::gadgets::sha512::CircuitBuilderSha512;
+
+use crate::curve::ed25519::Ed25519;
+use crate::curve::eddsa::{EdDSAPublicKey, EdDSASignature};
+use crate::curve::twisted_edwards::TwistedEdwardsCurve;
+use crate::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::gadgets::edwards::{
+    fixed_base_edwards_mul_circuit, CircuitBuilderEdwards, EdwardsPointTarget,
+};
+use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = EdDSAPublicKey)]
+pub struct EdDSAPublicKeyTarget(pub EdwardsPointTarget<Ed25519>);
+
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = EdDSASignature)]
+pub struct EdDSASignatureTarget {
+    pub r: EdwardsPointTarget<Ed25519>,
+    pub s: NonNativeTarget<Ed25519Scalar>,
+}
+
+/// Returns the 32-byte RFC 8032 encoding of `p`: the little-endian `y`, with the parity of `x` in
+/// the most significant bit. Both coordinates are constrained to be reduced, so the encoding is
+/// canonical.
+pub fn compress_edwards_point_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    p: &EdwardsPointTarget<Ed25519>,
+) -> ByteArrayTarget {
+    let num_limbs = CircuitBuilder::<F, D>::num_nonnative_limbs::<Ed25519Base>();
+    let max = builder.constant_biguint(&(Ed25519Base::order() - 1u32));
+    for coordinate in [&p.x, &p.y] {
+        let le_max = builder.cmp_biguint(&coordinate.value, &max);
+        builder.assert_one(le_max.target);
+    }
+
+    let zero = builder.zero_u32();
+    let x_low = p.x.value.limbs.first().copied().unwrap_or(zero);
+    let x_bits = builder.split_le(x_low.0, 32);
+    let mut limbs: Vec<U32Target> = (0..num_limbs)
+        .map(|i| p.y.value.limbs.get(i).copied().unwrap_or(zero))
+        .collect();
+    // As `y < 2^255`, the top bit of its last limb is free.
+    let top_bit = builder.constant(F::from_canonical_u32(1 << 31));
+    let top_limb = builder.mul_add(x_bits[0].target, top_bit, limbs[num_limbs - 1].0);
+    limbs[num_limbs - 1] = U32Target(top_limb);
+
+    builder.u32s_to_bytes_le(&limbs)
+}
+
+/// Verifies an Ed25519 signature of `msg` in-circuit, with the challenge computed using the
+/// SHA-512 gadget. This checks the cofactorless equation `S B = R + k A`, like the native
+/// `verify_message`, and requires `S` to be reduced. The message length is fixed by the circuit.
+pub fn verify_eddsa_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: &ByteArrayTarget,
+    sig: &EdDSASignatureTarget,
+    pk: &EdDSAPublicKeyTarget,
+) {
+    let EdDSASignatureTarget { r, s } = sig;
+
+    builder.edwards_assert_valid(r);
+    builder.edwards_assert_valid(&pk.0);
+
+    let max_s = builder.constant_biguint(&(Ed25519Scalar::order() - 1u32));
+    let s_le_max = builder.cmp_biguint(&s.value, &max_s);
+    builder.assert_one(s_le_max.target);
+
+    // `k = SHA-512(R || A || msg) mod l`.
+    let mut preimage = compress_edwards_point_circuit(builder, r).0;
+    preimage.extend(compress_edwards_point_circuit(builder, &pk.0).0);
+    preimage.extend(msg.0.iter().copied());
+    let digest = builder.sha512(&ByteArrayTarget(preimage));
+    let digest_limbs = builder.bytes_to_u32s_le(&digest);
+    let k = builder.reduce::<Ed25519Scalar>(&BigUintTarget {
+        limbs: digest_limbs,
+    });
+
+    let lhs = fixed_base_edwards_mul_circuit(builder, Ed25519::GENERATOR, s);
+    let k_pk = builder.edwards_scalar_mul_windowed(&pk.0, &k);
+    let rhs = builder.edwards_add(r, &k_pk);
+    builder.connect_edwards_point(&lhs, &rhs);
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::circuit_type::CircuitType;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::curve::eddsa::{sign_message, verify_message, EdDSASecretKey};
+    use crate::curve::twisted_edwards::EdwardsPoint;
+
+    #[test]
+    fn test_compress_edwards_point() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let points = [
+            EdwardsPoint::ZERO,
+            Ed25519::GENERATOR,
+            Ed25519::GENERATOR.mul_scalar(Ed25519Scalar::rand()),
+        ];
+        for p in points.into_iter().flat_map(|p| [p, -p]) {
+            let p_target = builder.constant_edwards_point(p);
+            let bytes = compress_edwards_point_circuit(&mut builder, &p_target);
+            let expected = builder.constant_bytes(&p.to_bytes());
+            builder.connect_bytes(&bytes, &expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_eddsa_circuit() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg = b"plonky2 eddsa";
+        let sk = EdDSASecretKey(core::array::from_fn(|i| i as u8));
+        let pk = sk.to_public();
+        let sig = sign_message(msg, sk);
+        assert!(verify_message(msg, sig, pk));
+
+        let msg_target = builder.add_virtual_bytes(msg.len());
+        let sig_target = EdDSASignatureTarget::add_virtual(&mut builder);
+        let pk_target = EdDSAPublicKeyTarget::add_virtual(&mut builder);
+        verify_eddsa_circuit(&mut builder, &msg_target, &sig_target, &pk_target);
+
+        dbg!(builder.num_gates());
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        msg_target.set_witness(&mut pw, msg);
+        sig_target.set_witness(&mut pw, &sig);
+        pk_target.set_witness(&mut pw, &pk);
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+}
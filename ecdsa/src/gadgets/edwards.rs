@@ -0,0 +1,351 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_derive::CircuitType;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+use crate::gadgets::biguint::BigUintTarget;
+use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::gadgets::split_nonnative::CircuitBuilderSplit;
+
+const WINDOW_SIZE: usize = 4;
+
+/// A point on a twisted Edwards curve, represented in affine coordinates. As the addition law is
+/// complete, the identity and doublings need no special handling.
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = EdwardsPoint<C>)]
+pub struct EdwardsPointTarget<C: TwistedEdwardsCurve> {
+    pub x: NonNativeTarget<C::BaseField>,
+    pub y: NonNativeTarget<C::BaseField>,
+}
+
+pub trait CircuitBuilderEdwards<F: RichField + Extendable<D>, const D: usize> {
+    fn constant_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        point: EdwardsPoint<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn connect_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    );
+
+    fn edwards_assert_valid<C: TwistedEdwardsCurve>(&mut self, p: &EdwardsPointTarget<C>);
+
+    fn edwards_neg<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn edwards_double<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn edwards_add<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn random_access_edwards_points<C: TwistedEdwardsCurve>(
+        &mut self,
+        access_index: Target,
+        v: Vec<EdwardsPointTarget<C>>,
+    ) -> EdwardsPointTarget<C>;
+
+    /// Computes `n * p` using a 4-bit window.
+    fn edwards_scalar_mul_windowed<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+        n: &NonNativeTarget<C::ScalarField>,
+    ) -> EdwardsPointTarget<C>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderEdwards<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn constant_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        point: EdwardsPoint<C>,
+    ) -> EdwardsPointTarget<C> {
+        debug_assert!(point.is_valid());
+        EdwardsPointTarget {
+            x: self.constant_nonnative(point.x),
+            y: self.constant_nonnative(point.y),
+        }
+    }
+
+    fn connect_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) {
+        self.connect_nonnative(&p1.x, &p2.x);
+        self.connect_nonnative(&p1.y, &p2.y);
+    }
+
+    fn edwards_assert_valid<C: TwistedEdwardsCurve>(&mut self, p: &EdwardsPointTarget<C>) {
+        let a = self.constant_nonnative(C::A);
+        let d = self.constant_nonnative(C::D);
+        let one = self.constant_nonnative(C::BaseField::ONE);
+
+        let x_squared = self.mul_nonnative(&p.x, &p.x);
+        let y_squared = self.mul_nonnative(&p.y, &p.y);
+        let a_x_squared = self.mul_nonnative(&a, &x_squared);
+        let lhs = self.add_nonnative(&a_x_squared, &y_squared);
+        let x_squared_y_squared = self.mul_nonnative(&x_squared, &y_squared);
+        let d_x_squared_y_squared = self.mul_nonnative(&d, &x_squared_y_squared);
+        let rhs = self.add_nonnative(&one, &d_x_squared_y_squared);
+
+        self.connect_nonnative(&lhs, &rhs);
+    }
+
+    fn edwards_neg<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        EdwardsPointTarget {
+            x: self.neg_nonnative(&p.x),
+            y: p.y.clone(),
+        }
+    }
+
+    fn edwards_double<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        // For a point on the curve, `x3 = 2xy / (a x^2 + y^2)` and
+        // `y3 = (y^2 - a x^2) / (2 - a x^2 - y^2)`.
+        let EdwardsPointTarget { x, y } = p;
+        let a = self.constant_nonnative(C::A);
+        let two = self.constant_nonnative(C::BaseField::TWO);
+
+        let x_squared = self.mul_nonnative(x, x);
+        let y_squared = self.mul_nonnative(y, y);
+        let a_x_squared = self.mul_nonnative(&a, &x_squared);
+        let xy = self.mul_nonnative(x, y);
+        let double_xy = self.add_nonnative(&xy, &xy);
+        let denominator_x = self.add_nonnative(&a_x_squared, &y_squared);
+        let numerator_y = self.sub_nonnative(&y_squared, &a_x_squared);
+        let denominator_y = self.sub_nonnative(&two, &denominator_x);
+
+        let inv_denominator_x = self.inv_nonnative(&denominator_x);
+        let inv_denominator_y = self.inv_nonnative(&denominator_y);
+        let x3 = self.mul_nonnative(&double_xy, &inv_denominator_x);
+        let y3 = self.mul_nonnative(&numerator_y, &inv_denominator_y);
+
+        EdwardsPointTarget { x: x3, y: y3 }
+    }
+
+    fn edwards_add<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        // `x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2)` and
+        // `y3 = (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2)`.
+        let EdwardsPointTarget { x: x1, y: y1 } = p1;
+        let EdwardsPointTarget { x: x2, y: y2 } = p2;
+        let a = self.constant_nonnative(C::A);
+        let d = self.constant_nonnative(C::D);
+        let one = self.constant_nonnative(C::BaseField::ONE);
+
+        let x1y2 = self.mul_nonnative(x1, y2);
+        let y1x2 = self.mul_nonnative(y1, x2);
+        let x1x2 = self.mul_nonnative(x1, x2);
+        let y1y2 = self.mul_nonnative(y1, y2);
+        let x1x2y1y2 = self.mul_nonnative(&x1x2, &y1y2);
+        let t = self.mul_nonnative(&d, &x1x2y1y2);
+        let a_x1x2 = self.mul_nonnative(&a, &x1x2);
+
+        let numerator_x = self.add_nonnative(&x1y2, &y1x2);
+        let numerator_y = self.sub_nonnative(&y1y2, &a_x1x2);
+        let denominator_x = self.add_nonnative(&one, &t);
+        let denominator_y = self.sub_nonnative(&one, &t);
+
+        let inv_denominator_x = self.inv_nonnative(&denominator_x);
+        let inv_denominator_y = self.inv_nonnative(&denominator_y);
+        let x3 = self.mul_nonnative(&numerator_x, &inv_denominator_x);
+        let y3 = self.mul_nonnative(&numerator_y, &inv_denominator_y);
+
+        EdwardsPointTarget { x: x3, y: y3 }
+    }
+
+    fn random_access_edwards_points<C: TwistedEdwardsCurve>(
+        &mut self,
+        access_index: Target,
+        v: Vec<EdwardsPointTarget<C>>,
+    ) -> EdwardsPointTarget<C> {
+        let num_limbs = Self::num_nonnative_limbs::<C::BaseField>();
+        let zero = self.zero_u32();
+        let mut select_coordinate = |coordinates: Vec<&NonNativeTarget<C::BaseField>>| {
+            let limbs = (0..num_limbs)
+                .map(|i| {
+                    let limbs = coordinates
+                        .iter()
+                        .map(|c| c.value.limbs.get(i).unwrap_or(&zero).0)
+                        .collect();
+                    U32Target(self.random_access(access_index, limbs))
+                })
+                .collect();
+            NonNativeTarget {
+                value: BigUintTarget { limbs },
+                _phantom: PhantomData,
+            }
+        };
+
+        let x = select_coordinate(v.iter().map(|p| &p.x).collect());
+        let y = select_coordinate(v.iter().map(|p| &p.y).collect());
+        EdwardsPointTarget { x, y }
+    }
+
+    fn edwards_scalar_mul_windowed<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+        n: &NonNativeTarget<C::ScalarField>,
+    ) -> EdwardsPointTarget<C> {
+        // `precomputation[i] = i * p` for `i=0..16`.
+        let mut precomputation = vec![self.constant_edwards_point(EdwardsPoint::ZERO), p.clone()];
+        for i in 2..1 << WINDOW_SIZE {
+            let multiple = if i % 2 == 0 {
+                self.edwards_double(&precomputation[i / 2])
+            } else {
+                self.edwards_add(&precomputation[i - 1], p)
+            };
+            precomputation.push(multiple);
+        }
+
+        let windows = self.split_nonnative_to_4_bit_limbs(n);
+        let mut result =
+            self.random_access_edwards_points(*windows.last().unwrap(), precomputation.clone());
+        for &window in windows.iter().rev().skip(1) {
+            for _ in 0..WINDOW_SIZE {
+                result = self.edwards_double(&result);
+            }
+            let to_add = self.random_access_edwards_points(window, precomputation.clone());
+            result = self.edwards_add(&result, &to_add);
+        }
+
+        result
+    }
+}
+
+/// Computes `scalar * base` for a fixed `base`, using a 4-bit window.
+pub fn fixed_base_edwards_mul_circuit<
+    C: TwistedEdwardsCurve,
+    F: RichField + Extendable<D>,
+    const D: usize,
+>(
+    builder: &mut CircuitBuilder<F, D>,
+    base: EdwardsPoint<C>,
+    scalar: &NonNativeTarget<C::ScalarField>,
+) -> EdwardsPointTarget<C> {
+    let limbs = builder.split_nonnative_to_4_bit_limbs(scalar);
+
+    let mut result = builder.constant_edwards_point(EdwardsPoint::ZERO);
+    // `s * P = sum s_i * P_i` with `P_i = (16^i) * P` and `s = sum s_i * (16^i)`.
+    let mut point = base;
+    for limb in limbs {
+        // `muls_point[t] = t * P_i` for `t=0..16`.
+        let muls_point = (0..1 << WINDOW_SIZE)
+            .scan(EdwardsPoint::ZERO, |acc, _| {
+                let tmp = *acc;
+                *acc = *acc + point;
+                Some(builder.constant_edwards_point(tmp))
+            })
+            .collect();
+        let r = builder.random_access_edwards_points(limb, muls_point);
+        result = builder.edwards_add(&result, &r);
+        for _ in 0..WINDOW_SIZE {
+            point = point.double();
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::ed25519_scalar::Ed25519Scalar;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::curve::ed25519::Ed25519;
+
+    #[test]
+    fn test_edwards_add_double() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let g = Ed25519::GENERATOR;
+        let p = g.mul_scalar(Ed25519Scalar::rand());
+        let g_target = builder.constant_edwards_point(g);
+        let p_target = builder.constant_edwards_point(p);
+        builder.edwards_assert_valid(&p_target);
+
+        let sum = builder.edwards_add(&g_target, &p_target);
+        let expected_sum = builder.constant_edwards_point(g + p);
+        builder.connect_edwards_point(&sum, &expected_sum);
+
+        let double = builder.edwards_double(&p_target);
+        let expected_double = builder.constant_edwards_point(p.double());
+        builder.connect_edwards_point(&double, &expected_double);
+
+        // The identity needs no special case.
+        let neg_p = builder.edwards_neg(&p_target);
+        let zero = builder.edwards_add(&p_target, &neg_p);
+        let expected_zero = builder.constant_edwards_point(EdwardsPoint::ZERO);
+        builder.connect_edwards_point(&zero, &expected_zero);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_edwards_scalar_mul() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let g = Ed25519::GENERATOR;
+        let p = g.mul_scalar(Ed25519Scalar::rand());
+        let n = Ed25519Scalar::rand();
+        let m = Ed25519Scalar::rand();
+
+        let p_target = builder.constant_edwards_point(p);
+        let n_target = builder.constant_nonnative(n);
+        let m_target = builder.constant_nonnative(m);
+        let np = builder.edwards_scalar_mul_windowed(&p_target, &n_target);
+        let mg = fixed_base_edwards_mul_circuit(&mut builder, g, &m_target);
+        let sum = builder.edwards_add(&np, &mg);
+        let expected = builder.constant_edwards_point(p.mul_scalar(n) + g.mul_scalar(m));
+        builder.connect_edwards_point(&sum, &expected);
+
+        dbg!(builder.num_gates());
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        Ok(data.verify(proof)?)
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::quintic::QuinticExtension;
+use plonky2::field::extension::Extendable;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::curve::ecgfp5::{gfp5_sqrt, GFp5};
+
+/// The constant `W` such that `GFp5 = GF(p)[z] / (z^5 - W)`.
+const GFP5_W: u64 = 3;
+
+/// A Target representing an element of `GFp5`, by its five coefficients. As `GFp5` extends the
+/// Goldilocks field, these gadgets are only meaningful with `F = GoldilocksField`.
+#[derive(Copy, Clone, Debug)]
+pub struct GFp5Target(pub [Target; 5]);
+
+fn gfp5_to_field<F: RichField>(x: GFp5) -> [F; 5] {
+    x.0.map(|c| F::from_canonical_u64(c.to_canonical_u64()))
+}
+
+fn gfp5_from_targets<F: RichField, W: Witness<F>>(witness: &W, x: &GFp5Target) -> GFp5 {
+    QuinticExtension(
+        x.0.map(|t| GoldilocksField::from_canonical_u64(witness.get_target(t).to_canonical_u64())),
+    )
+}
+
+fn set_gfp5_target<F: RichField, W: WitnessWrite<F>>(witness: &mut W, x: &GFp5Target, value: GFp5) {
+    for (&t, c) in x.0.iter().zip(gfp5_to_field(value)) {
+        witness.set_target(t, c);
+    }
+}
+
+impl<F: RichField> CircuitType<F> for GFp5Target {
+    type Native = GFp5;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.add_virtual_gfp5_target()
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &GFp5) {
+        set_gfp5_target(witness, self, *value);
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> GFp5 {
+        gfp5_from_targets(witness, self)
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect_gfp5(x, y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.select_gfp5(b, x, y)
+    }
+}
+
+pub trait CircuitBuilderGFp5<F: RichField + Extendable<D>, const D: usize> {
+    fn add_virtual_gfp5_target(&mut self) -> GFp5Target;
+
+    fn constant_gfp5(&mut self, x: GFp5) -> GFp5Target;
+
+    fn connect_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target);
+
+    fn add_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target;
+
+    fn sub_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target;
+
+    fn neg_gfp5(&mut self, x: &GFp5Target) -> GFp5Target;
+
+    fn mul_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target;
+
+    fn square_gfp5(&mut self, x: &GFp5Target) -> GFp5Target;
+
+    /// Returns the inverse of `x`, making the circuit unsatisfiable if `x` is zero.
+    fn inverse_gfp5(&mut self, x: &GFp5Target) -> GFp5Target;
+
+    /// Returns a square root of `x`, making the circuit unsatisfiable if `x` is not a square.
+    fn sqrt_gfp5(&mut self, x: &GFp5Target) -> GFp5Target;
+
+    /// Returns `x` if `b` is true, and `y` otherwise.
+    fn select_gfp5(&mut self, b: BoolTarget, x: &GFp5Target, y: &GFp5Target) -> GFp5Target;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderGFp5<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn add_virtual_gfp5_target(&mut self) -> GFp5Target {
+        GFp5Target(self.add_virtual_target_arr())
+    }
+
+    fn constant_gfp5(&mut self, x: GFp5) -> GFp5Target {
+        GFp5Target(gfp5_to_field(x).map(|c| self.constant(c)))
+    }
+
+    fn connect_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) {
+        for (&a, &b) in x.0.iter().zip(&y.0) {
+            self.connect(a, b);
+        }
+    }
+
+    fn add_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target {
+        GFp5Target(core::array::from_fn(|i| self.add(x.0[i], y.0[i])))
+    }
+
+    fn sub_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target {
+        GFp5Target(core::array::from_fn(|i| self.sub(x.0[i], y.0[i])))
+    }
+
+    fn neg_gfp5(&mut self, x: &GFp5Target) -> GFp5Target {
+        GFp5Target(x.0.map(|c| self.neg(c)))
+    }
+
+    fn mul_gfp5(&mut self, x: &GFp5Target, y: &GFp5Target) -> GFp5Target {
+        // `z^5 = W`, so the product of the coefficients of `z^i` and `z^j` goes to `z^(i + j - 5)`
+        // with a factor `W` if `i + j >= 5`.
+        let w = F::from_canonical_u64(GFP5_W);
+        let zero = self.zero();
+        let mut result = [zero; 5];
+        for i in 0..5 {
+            for j in 0..5 {
+                let (k, coeff) = if i + j < 5 {
+                    (i + j, F::ONE)
+                } else {
+                    (i + j - 5, w)
+                };
+                result[k] = self.arithmetic(coeff, F::ONE, x.0[i], y.0[j], result[k]);
+            }
+        }
+        GFp5Target(result)
+    }
+
+    fn square_gfp5(&mut self, x: &GFp5Target) -> GFp5Target {
+        self.mul_gfp5(x, x)
+    }
+
+    fn inverse_gfp5(&mut self, x: &GFp5Target) -> GFp5Target {
+        let inv = self.add_virtual_gfp5_target();
+        self.add_simple_generator(GFp5InverseGenerator::<F, D> {
+            x: *x,
+            inv,
+            _phantom: PhantomData,
+        });
+
+        let product = self.mul_gfp5(x, &inv);
+        let one = self.constant_gfp5(GFp5::ONE);
+        self.connect_gfp5(&product, &one);
+
+        inv
+    }
+
+    fn sqrt_gfp5(&mut self, x: &GFp5Target) -> GFp5Target {
+        let root = self.add_virtual_gfp5_target();
+        self.add_simple_generator(GFp5SqrtGenerator::<F, D> {
+            x: *x,
+            root,
+            _phantom: PhantomData,
+        });
+
+        let square = self.square_gfp5(&root);
+        self.connect_gfp5(&square, x);
+
+        root
+    }
+
+    fn select_gfp5(&mut self, b: BoolTarget, x: &GFp5Target, y: &GFp5Target) -> GFp5Target {
+        GFp5Target(core::array::from_fn(|i| self.select(b, x.0[i], y.0[i])))
+    }
+}
+
+#[derive(Debug)]
+struct GFp5InverseGenerator<F: RichField + Extendable<D>, const D: usize> {
+    x: GFp5Target,
+    inv: GFp5Target,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for GFp5InverseGenerator<F, D>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        self.x.0.to_vec()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let x = gfp5_from_targets(witness, &self.x);
+
+        // If `x` is zero, the circuit is unsatisfiable, so any value will do.
+        let inv = x.try_inverse().unwrap_or(GFp5::ZERO);
+        set_gfp5_target(out_buffer, &self.inv, inv);
+    }
+}
+
+#[derive(Debug)]
+struct GFp5SqrtGenerator<F: RichField + Extendable<D>, const D: usize> {
+    x: GFp5Target,
+    root: GFp5Target,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for GFp5SqrtGenerator<F, D> {
+    fn dependencies(&self) -> Vec<Target> {
+        self.x.0.to_vec()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let x = gfp5_from_targets(witness, &self.x);
+
+        // If `x` isn't a square, the circuit is unsatisfiable, so any value will do.
+        let root = gfp5_sqrt(x).unwrap_or(GFp5::ZERO);
+        set_gfp5_target(out_buffer, &self.root, root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::ops::Square;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+
+    #[test]
+    fn test_gfp5_arithmetic() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = GFp5::rand();
+        let y = GFp5::rand();
+        let x_target = GFp5Target::add_virtual(&mut builder);
+        let y_target = builder.constant_gfp5(y);
+
+        let expected = [x + y, x - y, -x, x * y, x.square(), x.inverse()];
+        let results = [
+            builder.add_gfp5(&x_target, &y_target),
+            builder.sub_gfp5(&x_target, &y_target),
+            builder.neg_gfp5(&x_target),
+            builder.mul_gfp5(&x_target, &y_target),
+            builder.square_gfp5(&x_target),
+            builder.inverse_gfp5(&x_target),
+        ];
+        for (result, expected) in results.iter().zip(expected) {
+            let expected = builder.constant_gfp5(expected);
+            builder.connect_gfp5(result, &expected);
+        }
+
+        let x_squared = builder.square_gfp5(&x_target);
+        let root = builder.sqrt_gfp5(&x_squared);
+        let root_squared = builder.square_gfp5(&root);
+        builder.connect_gfp5(&root_squared, &x_squared);
+
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        x_target.set_witness(&mut pw, &x);
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+}
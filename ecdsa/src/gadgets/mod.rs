@@ -4,6 +4,12 @@ pub mod curve_fixed_base;
 pub mod curve_msm;
 pub mod curve_windowed_mul;
 pub mod ecdsa;
+pub mod ecgfp5;
+pub mod eddsa;
+pub mod edwards;
+pub mod gfp5;
 pub mod glv;
 pub mod nonnative;
+pub mod rsa;
+pub mod schnorr;
 pub mod split_nonnative;
@@ -6,9 +6,10 @@ use num::{BigUint, Integer, One, Zero};
 use plonky2::field::extension::Extendable;
 use plonky2::field::types::{Field, PrimeField};
 use plonky2::hash::hash_types::RichField;
+use plonky2::iop::circuit_type::CircuitType;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::{BoolTarget, Target};
-use plonky2::iop::witness::{PartitionWitness, WitnessWrite};
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::util::ceil_div_usize;
 use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
@@ -25,6 +26,55 @@ pub struct NonNativeTarget<FF: Field> {
     pub(crate) _phantom: PhantomData<FF>,
 }
 
+impl<F: RichField, FF: PrimeField> CircuitType<F> for NonNativeTarget<FF> {
+    type Native = FF;
+
+    /// Adds a virtual target whose limbs are range-checked and whose value is constrained to be
+    /// less than the order of `FF`, so it is the canonical encoding of a field element.
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        let x = builder.add_virtual_nonnative_target::<FF>();
+        range_check_u32_circuit(builder, x.value.limbs.clone());
+        let max = builder.constant_biguint(&(FF::order() - 1u32));
+        let x_le_max = builder.cmp_biguint(&x.value, &max);
+        builder.assert_one(x_le_max.target);
+        x
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &FF) {
+        let mut limbs = value.to_canonical_biguint().to_u32_digits();
+        limbs.resize(self.value.num_limbs(), 0);
+        for (target, limb) in self.value.limbs.iter().zip(limbs) {
+            witness.set_target(target.0, F::from_canonical_u32(limb));
+        }
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> FF {
+        FF::from_noncanonical_biguint(witness.get_biguint_target(self.value.clone()))
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect_nonnative(x, y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.if_nonnative(b, x, y)
+    }
+}
+
 pub trait CircuitBuilderNonNative<F: RichField + Extendable<D>, const D: usize> {
     fn num_nonnative_limbs<FF: Field>() -> usize {
         ceil_div_usize(FF::BITS, 32)
@@ -475,7 +525,7 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         let b_biguint = b.to_canonical_biguint();
         let sum_biguint = a_biguint + b_biguint;
         let modulus = FF::order();
-        let (overflow, sum_reduced) = if sum_biguint > modulus {
+        let (overflow, sum_reduced) = if sum_biguint >= modulus {
             (true, sum_biguint - modulus)
         } else {
             (false, sum_biguint)
@@ -524,7 +574,11 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
 
         let modulus = FF::order();
         let (overflow_biguint, sum_reduced) = sum_biguint.div_rem(&modulus);
-        let overflow = overflow_biguint.to_u64_digits()[0] as u32;
+        let overflow = overflow_biguint
+            .to_u32_digits()
+            .first()
+            .copied()
+            .unwrap_or(0);
 
         out_buffer.set_biguint_target(&self.sum.value, &sum_reduced);
         out_buffer.set_u32_target(self.overflow, overflow);
@@ -644,14 +698,16 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use plonky2::field::bn254_base::Bn254Base;
     use plonky2::field::secp256k1_base::Secp256K1Base;
     use plonky2::field::types::{Field, PrimeField, Sample};
-    use plonky2::iop::witness::PartialWitness;
+    use plonky2::iop::circuit_type::CircuitType;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
     use plonky2::plonk::circuit_builder::CircuitBuilder;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
 
-    use crate::gadgets::nonnative::CircuitBuilderNonNative;
+    use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 
     #[test]
     fn test_nonnative_add() -> Result<()> {
@@ -677,7 +733,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -717,7 +773,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -747,7 +803,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -773,7 +829,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -797,7 +853,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -821,6 +877,66 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_nonnative_bn254() -> Result<()> {
+        type FF = Bn254Base;
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let x_ff = FF::rand();
+        let y_ff = FF::rand();
+        let expected_ff = (x_ff + y_ff) * x_ff.inverse();
+
+        let config = CircuitConfig::standard_ecc_config();
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = builder.constant_nonnative(x_ff);
+        let y = builder.constant_nonnative(y_ff);
+        let sum = builder.add_nonnative(&x, &y);
+        let inv_x = builder.inv_nonnative(&x);
+        let result = builder.mul_nonnative(&sum, &inv_x);
+
+        let expected = builder.constant_nonnative(expected_ff);
+        builder.connect_nonnative(&result, &expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        Ok(data.verify(proof)?)
+    }
+    fn test_nonnative_add_virtual_with_limbs(limbs: Vec<u32>) -> Result<()> {
+        type FF = Secp256K1Base;
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x = NonNativeTarget::<FF>::add_virtual(&mut builder);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (target, limb) in x.value.limbs.iter().zip(limbs) {
+            pw.set_target(target.0, F::from_canonical_u32(limb));
+        }
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_nonnative_add_virtual() -> Result<()> {
+        let max = Secp256K1Base::order() - 1u32;
+        test_nonnative_add_virtual_with_limbs(max.to_u32_digits())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nonnative_add_virtual_not_reduced() {
+        // The order fits in the limbs, but isn't the canonical encoding of a field element.
+        let order = Secp256K1Base::order();
+        test_nonnative_add_virtual_with_limbs(order.to_u32_digits()).unwrap()
     }
 }
@@ -0,0 +1,330 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::ceil_div_usize;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use plonky2_u32::gadgets::bytes::{ByteArrayTarget, CircuitBuilderBytes};
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+use plonky2_u32::gadgets::sha256::CircuitBuilderSha256;
+pub use plonky2_u32::gadgets::sha256::SHA256_DIGEST_LEN;
+
+use crate::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint};
+
+/// The public exponent of the RSA keys supported by the verification gadget.
+pub const RSA_PUBLIC_EXPONENT: u32 = 65537;
+
+/// The number of 32-bit limbs of a 2048-bit RSA modulus.
+pub const RSA_2048_NUM_LIMBS: usize = 64;
+
+/// The DER encoding of the SHA-256 `DigestInfo` header, which precedes the digest in PKCS#1 v1.5
+/// signatures.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+/// The last byte of an EMSA-PSS encoded message.
+const PSS_TRAILER: u8 = 0xbc;
+
+/// An RSA public key with exponent `RSA_PUBLIC_EXPONENT`. The modulus must use all the bits of its
+/// limbs, e.g. have its 2048th bit set for a 2048-bit key. Its limbs need not be range-checked, as
+/// the verification gadgets do it.
+#[derive(Clone, Debug)]
+pub struct RsaPublicKeyTarget {
+    pub modulus: BigUintTarget,
+}
+
+/// An RSA signature. Its limbs need not be range-checked, as the verification gadgets do it.
+#[derive(Clone, Debug)]
+pub struct RsaSignatureTarget(pub BigUintTarget);
+
+/// Returns the PKCS#1 v1.5 encoding of a SHA-256 digest for a modulus of `num_bytes` bytes,
+/// without the trailing digest: `0x00 0x01 0xff..0xff 0x00 DigestInfo`.
+pub fn pkcs1v15_sha256_prefix(num_bytes: usize) -> Vec<u8> {
+    let padding_len = num_bytes - 3 - SHA256_DIGEST_INFO_PREFIX.len() - SHA256_DIGEST_LEN;
+    assert!(padding_len >= 8, "The modulus is too short for PKCS#1 v1.5");
+
+    let mut prefix = vec![0x00, 0x01];
+    prefix.extend(vec![0xff; padding_len]);
+    prefix.push(0x00);
+    prefix.extend(SHA256_DIGEST_INFO_PREFIX);
+    prefix
+}
+
+/// Returns the message representative `sig^e mod n` of a signature, after range-checking the limbs
+/// of the signature and the modulus, and checking that the signature is less than the modulus.
+fn rsa_verification_primitive<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    sig: &RsaSignatureTarget,
+    pk: &RsaPublicKeyTarget,
+) -> BigUintTarget {
+    range_check_u32_circuit(builder, sig.0.limbs.clone());
+    range_check_u32_circuit(builder, pk.modulus.limbs.clone());
+
+    // The signature representative must be less than the modulus.
+    let modulus_le_sig = builder.cmp_biguint(&pk.modulus, &sig.0);
+    builder.assert_zero(modulus_le_sig.target);
+
+    let exponent = BigUint::from(RSA_PUBLIC_EXPONENT);
+    builder.mod_exp_biguint(&sig.0, &exponent, &pk.modulus)
+}
+
+/// Verifies an RSASSA-PKCS1-v1_5 signature of the message whose big-endian SHA-256 digest is
+/// `digest`. The digest is computed outside this gadget, and its bytes are assumed to be
+/// range-checked. The limbs of the signature and the public key are range-checked here.
+pub fn verify_rsa_pkcs1v15_sha256_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    digest: &ByteArrayTarget,
+    sig: &RsaSignatureTarget,
+    pk: &RsaPublicKeyTarget,
+) {
+    assert_eq!(digest.len(), SHA256_DIGEST_LEN);
+    let num_limbs = pk.modulus.num_limbs();
+
+    let message = rsa_verification_primitive(builder, sig, pk);
+
+    // The encoded message is the constant prefix followed by the digest, so its low limbs are the
+    // digest's words in reverse order.
+    let mut limbs = builder.bytes_to_u32s_be(digest);
+    limbs.reverse();
+    let prefix = BigUint::from_bytes_be(&pkcs1v15_sha256_prefix(4 * num_limbs));
+    let mut prefix_limbs = prefix.to_u32_digits();
+    prefix_limbs.resize(num_limbs - limbs.len(), 0);
+    limbs.extend(
+        prefix_limbs
+            .into_iter()
+            .map(|limb| builder.constant_u32(limb)),
+    );
+
+    builder.connect_biguint(&message, &BigUintTarget { limbs });
+}
+
+/// Verifies an RSASSA-PSS signature of the message whose big-endian SHA-256 digest is `digest`,
+/// with MGF1 based on SHA-256 and a salt of `salt_len` bytes. The digest is computed outside this
+/// gadget, and its bytes are assumed to be range-checked. The limbs of the signature and the public
+/// key are range-checked here.
+pub fn verify_rsa_pss_sha256_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    digest: &ByteArrayTarget,
+    sig: &RsaSignatureTarget,
+    pk: &RsaPublicKeyTarget,
+    salt_len: usize,
+) {
+    assert_eq!(digest.len(), SHA256_DIGEST_LEN);
+    let num_limbs = pk.modulus.num_limbs();
+    let em_len = 4 * num_limbs;
+    let db_len = em_len - SHA256_DIGEST_LEN - 1;
+    assert!(
+        db_len > salt_len,
+        "The modulus is too short for PSS with a {salt_len}-byte salt"
+    );
+
+    let message = rsa_verification_primitive(builder, sig, pk);
+
+    // The encoded message `EM = maskedDB || H || 0xbc`, as big-endian words. As the modulus uses
+    // all the bits of its limbs, `EM` has one bit less, so its top bit must be zero.
+    let mut words = message.limbs;
+    words.reverse();
+    builder.range_check(words[0].0, 31);
+    let em = builder.u32s_to_bytes_be(&words);
+    let trailer = builder.constant_u8(PSS_TRAILER);
+    builder.connect(em.0[em_len - 1].0, trailer.0);
+    let h = ByteArrayTarget(em.0[db_len..em_len - 1].to_vec());
+
+    // `DB = maskedDB ^ MGF1(H)`, computed on the words which make up `maskedDB` and the first
+    // byte of `H`, with the mask extended by a zero byte.
+    let mut db_mask = mgf1_sha256_circuit(builder, &h, db_len);
+    db_mask.0.push(builder.constant_u8(0));
+    let db_mask_words = builder.bytes_to_u32s_be(&db_mask);
+    let mut db_words: Vec<U32Target> = words
+        .iter()
+        .zip(db_mask_words)
+        .map(|(&word, mask)| builder.xor_u32(word, mask))
+        .collect();
+    // The top bit of `DB` is cleared, as it isn't part of `EM`.
+    let top_bit_mask = builder.constant_u32(u32::MAX >> 1);
+    db_words[0] = builder.and_u32(db_words[0], top_bit_mask);
+    let db = builder.u32s_to_bytes_be(&db_words);
+
+    // `DB = PS || 0x01 || salt`, where `PS` is zeros.
+    let ps_len = db_len - salt_len - 1;
+    for byte in &db.0[..ps_len] {
+        builder.assert_zero(byte.0);
+    }
+    builder.assert_one(db.0[ps_len].0);
+    let salt = &db.0[ps_len + 1..db_len];
+
+    // `H = SHA-256(0x00 * 8 || digest || salt)`.
+    let mut m_prime = builder.constant_bytes(&[0; 8]).0;
+    m_prime.extend_from_slice(&digest.0);
+    m_prime.extend_from_slice(salt);
+    let expected_h = builder.sha256(&ByteArrayTarget(m_prime));
+    builder.connect_bytes(&h, &expected_h);
+}
+
+/// Returns the first `len` bytes of the MGF1 mask generated from `seed` with SHA-256.
+fn mgf1_sha256_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    seed: &ByteArrayTarget,
+    len: usize,
+) -> ByteArrayTarget {
+    let mut mask = Vec::with_capacity(len);
+    for counter in 0..ceil_div_usize(len, SHA256_DIGEST_LEN) as u32 {
+        let mut input = seed.0.clone();
+        input.extend(builder.constant_bytes(&counter.to_be_bytes()).0);
+        mask.extend(builder.sha256(&ByteArrayTarget(input)).0);
+    }
+    mask.truncate(len);
+    ByteArrayTarget(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::Field;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::gadgets::biguint::WitnessBigUint;
+
+    const MODULUS: &[u8] = b"\
+        de6d3a66a73caea19f0a08880b0326053f4b76263655c7ee1caa77351b1e9a8351eb8eb0438a9e5d2f19e073\
+        de10469dc4e7b5d67ad574ec031e7b4400856b98f0bc1b00b690c5e06777dd8dca5d858821b69ff3ba776184\
+        6aaa35e3d60bad551c2b51c0d6aae5898911abdcaed45f0c721faed47a8c718d1861c1cd834e2c3f27b0fa07\
+        4029b1e9afc12d057263a66364197a6bb5a91628e460d1a78bd3d7af7a4a1b7d68502546edb83b6b6822a962\
+        df5ec25fec3737874aefd46f7f942d44e8f317d3979b0e4b363e52e87b5cd794aa84efacfeddcac92602415a\
+        33c8518de60e80b47fee6273d604cdcc093aeb32a7f619371dfd1f88fcff5308094c9f1d";
+    const PRIVATE_EXPONENT: &[u8] = b"\
+        dafc393255d695ac03d3b20096aefbe0d0a72fe4813c4541b631527a138036e9b15eb435efd96aff1d5f61af\
+        51e33e53184874ab7c75e9ce6f67b43e8830d37559162993b32aa7d6bea7b19666b55ac16347d504f601911d\
+        f89229ca6db3436cf6f5e360733900123fc4092b9568fb475a98bcdceee694e9302716d28a430da52e423d4d\
+        957c317452160b8979b59b8699165c4bbec2c704bcb48f120a6d5aab5cdca196e536609c4dc6a96f31f7f6f0\
+        5c8d95c747e695d3e19e10f38100ca5c7414989eabc95adea6d5ca97dbff74e0c7be27c641f12ffc3c39d128\
+        713d9371c89db907ef0b1e2cb838c56c04dd4e4bb6fded6df2c564570073f69881d14875";
+
+    /// Returns a random digest, and the limbs of its PKCS#1 v1.5 signature.
+    fn pkcs1v15_sha256_signature() -> ([u8; SHA256_DIGEST_LEN], Vec<u32>) {
+        let modulus = BigUint::parse_bytes(MODULUS, 16).unwrap();
+        let private_exponent = BigUint::parse_bytes(PRIVATE_EXPONENT, 16).unwrap();
+        let digest: [u8; SHA256_DIGEST_LEN] = OsRng.gen();
+        let mut encoded = pkcs1v15_sha256_prefix(4 * RSA_2048_NUM_LIMBS);
+        encoded.extend(digest);
+        let sig = BigUint::from_bytes_be(&encoded).modpow(&private_exponent, &modulus);
+        let mut sig_limbs = sig.to_u32_digits();
+        sig_limbs.resize(RSA_2048_NUM_LIMBS, 0);
+        (digest, sig_limbs)
+    }
+
+    /// Verifies a signature given by the values of its limbs, which may not be range-checked.
+    fn test_rsa_2048_pkcs1v15_sha256_with_limbs(digest: &[u8], sig_limbs: &[u64]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let modulus = BigUint::parse_bytes(MODULUS, 16).unwrap();
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let digest_target = builder.add_virtual_bytes(SHA256_DIGEST_LEN);
+        let sig_target = RsaSignatureTarget(builder.add_virtual_biguint_target(RSA_2048_NUM_LIMBS));
+        let pk_target = RsaPublicKeyTarget {
+            modulus: builder.constant_biguint(&modulus),
+        };
+        verify_rsa_pkcs1v15_sha256_circuit(&mut builder, &digest_target, &sig_target, &pk_target);
+
+        let mut pw = PartialWitness::new();
+        digest_target.set_witness(&mut pw, digest);
+        for (limb, &value) in sig_target.0.limbs.iter().zip(sig_limbs) {
+            pw.set_target(limb.0, F::from_canonical_u64(value));
+        }
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_rsa_2048_pkcs1v15_sha256() -> Result<()> {
+        let (digest, sig_limbs) = pkcs1v15_sha256_signature();
+        let sig_limbs: Vec<u64> = sig_limbs.into_iter().map(u64::from).collect();
+        test_rsa_2048_pkcs1v15_sha256_with_limbs(&digest, &sig_limbs)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rsa_2048_pkcs1v15_sha256_unreduced_limbs() {
+        let (digest, sig_limbs) = pkcs1v15_sha256_signature();
+        let mut sig_limbs: Vec<u64> = sig_limbs.into_iter().map(u64::from).collect();
+        // Move a unit of the first nonzero higher limb into the lowest limb, which keeps the value
+        // of the signature but takes the lowest limb out of range.
+        let i = (1..RSA_2048_NUM_LIMBS)
+            .find(|&i| sig_limbs[i] != 0)
+            .unwrap();
+        sig_limbs[i] -= 1;
+        for limb in &mut sig_limbs[1..i] {
+            *limb = u64::from(u32::MAX);
+        }
+        sig_limbs[0] += 1 << 32;
+        test_rsa_2048_pkcs1v15_sha256_with_limbs(&digest, &sig_limbs).unwrap()
+    }
+    /// A signature of the SHA-256 digest of `b"plonky2"` by the test key, with a 32-byte salt,
+    /// generated by an independent PSS implementation.
+    const PSS_SIGNATURE: &[u8] = b"\
+        93f60bea6b86b0dab246c69b68fccc18af7949ce4d3c05c6d41f31ccd974187dd1088095d7ecd0f6336e0d7e\
+        ec4921c29b234b5e1ca733767754263e03bf95019d69e784ca33869395fd9ad847be51207ea63a9419a57e6e\
+        6834f966ffc14eb3abcb7e3b47924f290ba6eb61916be397ec614259e0d4be1c7b01d28485f47ad5a477bf4c\
+        159cf7c5f8002bb8df2596dcc3c94a4f39ed6de9e1a8ec916212603f52d135d8ff34538e8f8a5bf452a98098\
+        3d0ce2de08c0763f59ea601b14e02a656421485f173d29ac7a65efc3a29f4377d68ded46c12b6e84321cfa97\
+        641c7f294c5605ae290a9082dff6d7c32076334acb5af2904936c806ce72e740026112a6";
+    const PSS_SALT_LEN: usize = 32;
+
+    fn test_rsa_2048_pss_sha256_with_message(msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let modulus = BigUint::parse_bytes(MODULUS, 16).unwrap();
+        let sig = BigUint::parse_bytes(PSS_SIGNATURE, 16).unwrap();
+        let digest = Sha256::digest(msg);
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let digest_target = builder.add_virtual_bytes(SHA256_DIGEST_LEN);
+        let sig_target = RsaSignatureTarget(builder.add_virtual_biguint_target(RSA_2048_NUM_LIMBS));
+        let pk_target = RsaPublicKeyTarget {
+            modulus: builder.constant_biguint(&modulus),
+        };
+        verify_rsa_pss_sha256_circuit(
+            &mut builder,
+            &digest_target,
+            &sig_target,
+            &pk_target,
+            PSS_SALT_LEN,
+        );
+
+        let mut pw = PartialWitness::new();
+        digest_target.set_witness(&mut pw, &digest);
+        pw.set_biguint_target(&sig_target.0, &sig);
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_rsa_2048_pss_sha256() -> Result<()> {
+        test_rsa_2048_pss_sha256_with_message(b"plonky2")
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rsa_2048_pss_sha256_wrong_message() {
+        test_rsa_2048_pss_sha256_with_message(b"plonky3").unwrap()
+    }
+}
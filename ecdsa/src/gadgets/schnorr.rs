@@ -0,0 +1,144 @@
+use alloc::vec::Vec;
+
+use plonky2::field::ecgfp5_scalar::EcGFp5Scalar;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_derive::CircuitType;
+
+use crate::curve::ecgfp5::ECGFP5_GENERATOR;
+use crate::curve::schnorr::{SchnorrPublicKey, SchnorrSignature};
+use crate::gadgets::biguint::CircuitBuilderBiguint;
+use crate::gadgets::ecgfp5::{
+    fixed_base_ecgfp5_mul_circuit, CircuitBuilderEcGFp5, EcGFp5PointTarget,
+};
+use crate::gadgets::nonnative::NonNativeTarget;
+
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = SchnorrPublicKey)]
+pub struct SchnorrPublicKeyTarget(pub EcGFp5PointTarget);
+
+#[derive(Clone, Debug, CircuitType)]
+#[circuit_type(native = SchnorrSignature)]
+pub struct SchnorrSignatureTarget {
+    pub e: NonNativeTarget<EcGFp5Scalar>,
+    pub s: NonNativeTarget<EcGFp5Scalar>,
+}
+
+fn nonnative_to_bits<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: &NonNativeTarget<EcGFp5Scalar>,
+) -> Vec<BoolTarget> {
+    x.value
+        .limbs
+        .iter()
+        .flat_map(|limb| builder.split_le(limb.0, 32))
+        .collect()
+}
+
+/// Verifies a Schnorr signature of `msg` in-circuit, like the native `verify_message`. The public
+/// key is constrained to be in the subgroup of order `n` when it is created with `add_virtual`,
+/// and `s` is required to be reduced.
+pub fn verify_schnorr_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: &[Target],
+    sig: &SchnorrSignatureTarget,
+    pk: &SchnorrPublicKeyTarget,
+) {
+    let SchnorrSignatureTarget { e, s } = sig;
+
+    let max_s = builder.constant_biguint(&(EcGFp5Scalar::order() - 1u32));
+    let s_le_max = builder.cmp_biguint(&s.value, &max_s);
+    builder.assert_one(s_le_max.target);
+
+    // `R = s G + e pk`.
+    let s_bits = nonnative_to_bits(builder, s);
+    let e_bits = nonnative_to_bits(builder, e);
+    let s_g = fixed_base_ecgfp5_mul_circuit(builder, ECGFP5_GENERATOR, &s_bits);
+    let e_pk = builder.ecgfp5_scalar_mul_windowed(&pk.0, &e_bits[..256]);
+    let r = builder.ecgfp5_add(&s_g, &e_pk);
+
+    // `e` must be the challenge, whose 64-bit limbs are the canonical encodings of the elements of
+    // the Poseidon hash.
+    let mut inputs = Vec::with_capacity(20 + msg.len());
+    for coordinate in [r.x, r.y, pk.0.x, pk.0.y] {
+        inputs.extend(coordinate.0);
+    }
+    inputs.extend_from_slice(msg);
+    let hash = builder.hash_n_to_hash_no_pad::<PoseidonHash>(inputs);
+    for (i, &element) in hash.elements.iter().enumerate() {
+        let element_bits = builder.split_le_canonical(element);
+        for (a, b) in element_bits.iter().zip(&e_bits[64 * i..64 * (i + 1)]) {
+            builder.connect(a.target, b.target);
+        }
+    }
+    for bit in &e_bits[256..] {
+        builder.assert_zero(bit.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::circuit_type::CircuitType;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::curve::schnorr::{sign_message, verify_message, SchnorrSecretKey};
+
+    /// Verifies in-circuit a signature of `signed_msg`, against the message `msg`.
+    fn test_schnorr_circuit_with_messages(
+        msg: &[GoldilocksField],
+        signed_msg: &[GoldilocksField],
+    ) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let sk = SchnorrSecretKey(EcGFp5Scalar::rand());
+        let pk = sk.to_public();
+        let sig = sign_message(signed_msg, sk);
+        assert!(verify_message(signed_msg, sig, pk));
+
+        let msg_target = builder.add_virtual_targets(msg.len());
+        let sig_target = SchnorrSignatureTarget::add_virtual(&mut builder);
+        let pk_target = SchnorrPublicKeyTarget::add_virtual(&mut builder);
+        verify_schnorr_circuit(&mut builder, &msg_target, &sig_target, &pk_target);
+
+        dbg!(builder.num_gates());
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (&t, &x) in msg_target.iter().zip(msg) {
+            pw.set_target(t, x);
+        }
+        sig_target.set_witness(&mut pw, &sig);
+        pk_target.set_witness(&mut pw, &pk);
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_schnorr_circuit() -> Result<()> {
+        let msg = GoldilocksField::rand_vec(5);
+        test_schnorr_circuit_with_messages(&msg, &msg)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_schnorr_circuit_wrong_message() {
+        let msg = GoldilocksField::rand_vec(5);
+        let signed_msg = GoldilocksField::rand_vec(5);
+        test_schnorr_circuit_with_messages(&msg, &signed_msg).unwrap()
+    }
+}
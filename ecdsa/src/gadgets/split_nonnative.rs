@@ -126,6 +126,6 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 }
@@ -336,7 +336,7 @@ where
     }
 
     pub fn verify_root(&self, agg_proof: ProofWithPublicInputs<F, C, D>) -> anyhow::Result<()> {
-        self.root.circuit.verify(agg_proof)
+        Ok(self.root.circuit.verify(agg_proof)?)
     }
 
     pub fn prove_aggregation(
@@ -369,11 +369,11 @@ where
         agg_proof: &ProofWithPublicInputs<F, C, D>,
     ) -> anyhow::Result<()> {
         self.aggregation.circuit.verify(agg_proof.clone())?;
-        check_cyclic_proof_verifier_data(
+        Ok(check_cyclic_proof_verifier_data(
             agg_proof,
             &self.aggregation.circuit.verifier_only,
             &self.aggregation.circuit.common,
-        )
+        )?)
     }
 }
 
@@ -609,6 +609,7 @@ mod tests {
     use plonky2::iop::challenger::Challenger;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
     use plonky2::timed;
+    use plonky2::util::prover_context::ProverContext;
     use plonky2::util::timing::TimingTree;
     use tiny_keccak::keccakf;
 
@@ -741,6 +742,7 @@ mod tests {
             &ctl_data,
             &mut Challenger::new(),
             &mut timing,
+            &ProverContext::default(),
         )?;
 
         timing.print();
@@ -14,6 +14,7 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::iop::challenger::Challenger;
 use plonky2::plonk::config::{GenericConfig, Hasher};
 use plonky2::timed;
+use plonky2::util::prover_context::{ProverContext, ProverPhase};
 use plonky2::util::timing::TimingTree;
 use plonky2::util::transpose;
 use plonky2_util::{log2_ceil, log2_strict};
@@ -54,12 +55,58 @@ where
     [(); LogicStark::<F, D>::COLUMNS]:,
     [(); MemoryStark::<F, D>::COLUMNS]:,
 {
+    prove_with_context(all_stark, config, inputs, timing, &ProverContext::default())
+}
+
+/// Like `prove`, but reports the progress of the prover to `ctx`, and stops with a `Cancelled`
+/// error between phases if `ctx` is cancelled. The phases after trace generation are reported
+/// once per table.
+pub fn prove_with_context<F, C, const D: usize>(
+    all_stark: &AllStark<F, D>,
+    config: &StarkConfig,
+    inputs: GenerationInputs,
+    timing: &mut TimingTree,
+    ctx: &ProverContext,
+) -> Result<AllProof<F, C, D>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    [(); C::Hasher::HASH_SIZE]:,
+    [(); CpuStark::<F, D>::COLUMNS]:,
+    [(); KeccakStark::<F, D>::COLUMNS]:,
+    [(); KeccakSpongeStark::<F, D>::COLUMNS]:,
+    [(); LogicStark::<F, D>::COLUMNS]:,
+    [(); MemoryStark::<F, D>::COLUMNS]:,
+{
+    ctx.enter_phase(ProverPhase::WitnessGeneration)?;
     let (traces, public_values) = timed!(
         timing,
         "generate all traces",
         generate_traces(all_stark, inputs, config, timing)
     );
-    prove_with_traces(all_stark, config, traces, public_values, timing)
+    prove_with_traces(all_stark, config, traces, public_values, timing, ctx)
+}
+
+/// Like `prove`, but runs trace generation and every parallel step of the prover in `pool` rather
+/// than in the global thread pool.
+pub fn prove_with_pool<F, C, const D: usize>(
+    all_stark: &AllStark<F, D>,
+    config: &StarkConfig,
+    inputs: GenerationInputs,
+    timing: &mut TimingTree,
+    pool: &ThreadPool,
+) -> Result<AllProof<F, C, D>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    [(); C::Hasher::HASH_SIZE]:,
+    [(); CpuStark::<F, D>::COLUMNS]:,
+    [(); KeccakStark::<F, D>::COLUMNS]:,
+    [(); KeccakSpongeStark::<F, D>::COLUMNS]:,
+    [(); LogicStark::<F, D>::COLUMNS]:,
+    [(); MemoryStark::<F, D>::COLUMNS]:,
+{
+    pool.install(|| prove(all_stark, config, inputs, timing))
 }
 
 /// Compute all STARK proofs.
@@ -69,6 +116,7 @@ pub(crate) fn prove_with_traces<F, C, const D: usize>(
     trace_poly_values: [Vec<PolynomialValues<F>>; NUM_TABLES],
     public_values: PublicValues,
     timing: &mut TimingTree,
+    ctx: &ProverContext,
 ) -> Result<AllProof<F, C, D>>
 where
     F: RichField + Extendable<D>,
@@ -83,6 +131,7 @@ where
     let rate_bits = config.fri_config.rate_bits;
     let cap_height = config.fri_config.cap_height;
 
+    ctx.enter_phase(ProverPhase::WireCommitment)?;
     let trace_commitments = timed!(
         timing,
         "compute all trace commitments",
@@ -138,7 +187,8 @@ where
             trace_commitments,
             ctl_data_per_table,
             &mut challenger,
-            timing
+            timing,
+            ctx,
         )?
     );
 
@@ -157,6 +207,7 @@ fn prove_with_commitments<F, C, const D: usize>(
     ctl_data_per_table: [CtlData<F>; NUM_TABLES],
     challenger: &mut Challenger<F, C::Hasher>,
     timing: &mut TimingTree,
+    ctx: &ProverContext,
 ) -> Result<[StarkProofWithMetadata<F, C, D>; NUM_TABLES]>
 where
     F: RichField + Extendable<D>,
@@ -179,6 +230,7 @@ where
             &ctl_data_per_table[Table::Cpu as usize],
             challenger,
             timing,
+            ctx,
         )?
     );
     let keccak_proof = timed!(
@@ -192,6 +244,7 @@ where
             &ctl_data_per_table[Table::Keccak as usize],
             challenger,
             timing,
+            ctx,
         )?
     );
     let keccak_sponge_proof = timed!(
@@ -205,6 +258,7 @@ where
             &ctl_data_per_table[Table::KeccakSponge as usize],
             challenger,
             timing,
+            ctx,
         )?
     );
     let logic_proof = timed!(
@@ -218,6 +272,7 @@ where
             &ctl_data_per_table[Table::Logic as usize],
             challenger,
             timing,
+            ctx,
         )?
     );
     let memory_proof = timed!(
@@ -231,6 +286,7 @@ where
             &ctl_data_per_table[Table::Memory as usize],
             challenger,
             timing,
+            ctx,
         )?
     );
     Ok([
@@ -251,6 +307,7 @@ pub(crate) fn prove_single_table<F, C, S, const D: usize>(
     ctl_data: &CtlData<F>,
     challenger: &mut Challenger<F, C::Hasher>,
     timing: &mut TimingTree,
+    ctx: &ProverContext,
 ) -> Result<StarkProofWithMetadata<F, C, D>>
 where
     F: RichField + Extendable<D>,
@@ -271,6 +328,7 @@ where
 
     let init_challenger_state = challenger.compact();
 
+    ctx.enter_phase(ProverPhase::PermutationZs)?;
     // Permutation arguments.
     let permutation_challenges = stark.uses_permutation_args().then(|| {
         get_n_grand_product_challenge_sets(
@@ -313,6 +371,7 @@ where
     let permutation_ctl_zs_cap = permutation_ctl_zs_commitment.merkle_tree.cap.clone();
     challenger.observe_cap(&permutation_ctl_zs_cap);
 
+    ctx.enter_phase(ProverPhase::Quotient)?;
     let alphas = challenger.get_n_challenges(config.num_challenges);
     if cfg!(test) {
         check_constraints(
@@ -383,6 +442,7 @@ where
         "Opening point is in the subgroup."
     );
 
+    ctx.enter_phase(ProverPhase::Fri)?;
     let openings = StarkOpeningSet::new(
         zeta,
         g,
@@ -0,0 +1,297 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use itertools::Itertools;
+use num::bigint::BigUint;
+use num::Integer;
+use serde::{Deserialize, Serialize};
+
+use crate::montgomery;
+use crate::types::{Field, PrimeField, Sample};
+
+/// The base field of the BN254 (alt_bn128) elliptic curve.
+///
+/// Its order is
+/// ```ignore
+/// P = 0x30644E72 E131A029 B85045B6 8181585D 97816A91 6871CA8D 3C208C16 D87CFD47
+///   = 21888242871839275222246405745257275088696311157297823662689037894645226208583
+/// ```
+///
+/// Elements are represented in Montgomery form, i.e. by the canonical little-endian 64-bit limbs
+/// of `x * 2^256 mod P`, so the representation of an element is unique.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Bn254Base([u64; 4]);
+
+/// The order of the field, in little-endian 64-bit limbs.
+const ORDER: [u64; 4] = [
+    0x3C208C16D87CFD47,
+    0x97816A916871CA8D,
+    0xB85045B68181585D,
+    0x30644E72E131A029,
+];
+
+/// `-ORDER^-1 mod 2^64`.
+const INV: u64 = 0x87D20782E4866389;
+
+/// `2^512 mod ORDER`, used to convert into Montgomery form.
+const R2: [u64; 4] = [
+    0xF32CFC5B538AFA89,
+    0xB5E71911D44501FB,
+    0x47AB1EFF0A417FF6,
+    0x06D89F71CAB8351F,
+];
+
+fn biguint_from_array(arr: [u64; 4]) -> BigUint {
+    BigUint::from_slice(&[
+        arr[0] as u32,
+        (arr[0] >> 32) as u32,
+        arr[1] as u32,
+        (arr[1] >> 32) as u32,
+        arr[2] as u32,
+        (arr[2] >> 32) as u32,
+        arr[3] as u32,
+        (arr[3] >> 32) as u32,
+    ])
+}
+
+impl Bn254Base {
+    /// Returns the element with the given canonical limbs, which must be less than the order.
+    #[inline]
+    fn from_canonical_limbs(limbs: [u64; 4]) -> Self {
+        Self(montgomery::to_montgomery(limbs, R2, ORDER, INV))
+    }
+
+    #[inline]
+    fn to_canonical_limbs(self) -> [u64; 4] {
+        montgomery::from_montgomery(self.0, ORDER, INV)
+    }
+}
+
+impl Default for Bn254Base {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Display for Bn254Base {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Debug for Bn254Base {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Sample for Bn254Base {
+    #[inline]
+    fn sample<R>(rng: &mut R) -> Self
+    where
+        R: rand::RngCore + ?Sized,
+    {
+        use num::bigint::RandBigInt;
+        Self::from_noncanonical_biguint(rng.gen_biguint_below(&Self::order()))
+    }
+}
+
+impl Field for Bn254Base {
+    const ZERO: Self = Self([0; 4]);
+    const ONE: Self = Self([
+        0xD35D438DC58F0D9D,
+        0x0A78EB28F5C70B3D,
+        0x666EA36F7879462C,
+        0x0E0A77C19A07DF2F,
+    ]);
+    const TWO: Self = Self([
+        0xA6BA871B8B1E1B3A,
+        0x14F1D651EB8E167B,
+        0xCCDD46DEF0F28C58,
+        0x1C14EF83340FBE5E,
+    ]);
+    const NEG_ONE: Self = Self([
+        0x68C3488912EDEFAA,
+        0x8D087F6872AABF4F,
+        0x51E1A24709081231,
+        0x2259D6B14729C0FA,
+    ]);
+
+    const TWO_ADICITY: usize = 1;
+    const CHARACTERISTIC_TWO_ADICITY: usize = Self::TWO_ADICITY;
+
+    // Sage: `g = GF(p).multiplicative_generator()`
+    // 3
+    const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self([
+        0x7A17CAA950AD28D7,
+        0x1F6AC17AE15521B9,
+        0x334BEA4E696BD284,
+        0x2A1F6744CE179D8E,
+    ]);
+
+    // Sage: `g_2 = power_mod(g, (p - 1) // 2, p)`, which is `-1`.
+    const POWER_OF_TWO_GENERATOR: Self = Self::NEG_ONE;
+
+    const BITS: usize = 254;
+
+    fn order() -> BigUint {
+        biguint_from_array(ORDER)
+    }
+    fn characteristic() -> BigUint {
+        Self::order()
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Fermat's Little Theorem
+        Some(Self(montgomery::inverse(self.0, Self::ONE.0, ORDER, INV)))
+    }
+
+    fn from_noncanonical_biguint(val: BigUint) -> Self {
+        Self::from_canonical_limbs(
+            val.mod_floor(&Self::order())
+                .to_u64_digits()
+                .into_iter()
+                .pad_using(4, |_| 0)
+                .collect::<Vec<_>>()[..]
+                .try_into()
+                .expect("error converting to u64 array"),
+        )
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        Self::from_canonical_limbs([n, 0, 0, 0])
+    }
+
+    #[inline]
+    fn from_noncanonical_u128(n: u128) -> Self {
+        Self::from_canonical_limbs([n as u64, (n >> 64) as u64, 0, 0])
+    }
+}
+
+impl PrimeField for Bn254Base {
+    fn to_canonical_biguint(&self) -> BigUint {
+        biguint_from_array(self.to_canonical_limbs())
+    }
+}
+
+impl Neg for Bn254Base {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(montgomery::neg(self.0, ORDER))
+    }
+}
+
+impl Add for Bn254Base {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(montgomery::add(self.0, rhs.0, ORDER))
+    }
+}
+
+impl AddAssign for Bn254Base {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Bn254Base {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl Sub for Bn254Base {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(montgomery::sub(self.0, rhs.0, ORDER))
+    }
+}
+
+impl SubAssign for Bn254Base {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Bn254Base {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(montgomery::mul(self.0, rhs.0, ORDER, INV))
+    }
+}
+
+impl MulAssign for Bn254Base {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for Bn254Base {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x).unwrap_or(Self::ONE)
+    }
+}
+
+impl Div for Bn254Base {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl DivAssign for Bn254Base {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::bn254_base::Bn254Base;
+    use crate::test_field_arithmetic;
+    use crate::types::{Field, PrimeField};
+
+    test_field_arithmetic!(crate::bn254_base::Bn254Base);
+
+    #[test]
+    fn test_constants() {
+        type F = Bn254Base;
+
+        for (x, expected) in [
+            (F::ZERO, 0u32),
+            (F::ONE, 1),
+            (F::TWO, 2),
+            (F::MULTIPLICATIVE_GROUP_GENERATOR, 3),
+        ] {
+            assert_eq!(x.to_canonical_biguint(), BigUint::from(expected));
+        }
+        assert_eq!(F::NEG_ONE.to_canonical_biguint(), F::order() - 1u32);
+        assert_eq!(
+            F::POWER_OF_TWO_GENERATOR,
+            F::MULTIPLICATIVE_GROUP_GENERATOR.exp_biguint(&(F::order() >> F::TWO_ADICITY))
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use itertools::Itertools;
+use num::bigint::BigUint;
+use num::Integer;
+use serde::{Deserialize, Serialize};
+
+use crate::montgomery;
+use crate::types::{Field, PrimeField, Sample};
+
+/// The scalar field of the BN254 (alt_bn128) elliptic curve.
+///
+/// Its order is
+/// ```ignore
+/// P = 0x30644E72 E131A029 B85045B6 8181585D 2833E848 79B97091 43E1F593 F0000001
+///   = 21888242871839275222246405745257275088548364400416034343698204186575808495617
+/// ```
+///
+/// Elements are represented in Montgomery form, i.e. by the canonical little-endian 64-bit limbs
+/// of `x * 2^256 mod P`, so the representation of an element is unique.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Bn254Scalar([u64; 4]);
+
+/// The order of the field, in little-endian 64-bit limbs.
+const ORDER: [u64; 4] = [
+    0x43E1F593F0000001,
+    0x2833E84879B97091,
+    0xB85045B68181585D,
+    0x30644E72E131A029,
+];
+
+/// `-ORDER^-1 mod 2^64`.
+const INV: u64 = 0xC2E1F593EFFFFFFF;
+
+/// `2^512 mod ORDER`, used to convert into Montgomery form.
+const R2: [u64; 4] = [
+    0x1BB8E645AE216DA7,
+    0x53FE3AB1E35C59E3,
+    0x8C49833D53BB8085,
+    0x0216D0B17F4E44A5,
+];
+
+fn biguint_from_array(arr: [u64; 4]) -> BigUint {
+    BigUint::from_slice(&[
+        arr[0] as u32,
+        (arr[0] >> 32) as u32,
+        arr[1] as u32,
+        (arr[1] >> 32) as u32,
+        arr[2] as u32,
+        (arr[2] >> 32) as u32,
+        arr[3] as u32,
+        (arr[3] >> 32) as u32,
+    ])
+}
+
+impl Bn254Scalar {
+    /// Returns the element with the given canonical limbs, which must be less than the order.
+    #[inline]
+    fn from_canonical_limbs(limbs: [u64; 4]) -> Self {
+        Self(montgomery::to_montgomery(limbs, R2, ORDER, INV))
+    }
+
+    #[inline]
+    fn to_canonical_limbs(self) -> [u64; 4] {
+        montgomery::from_montgomery(self.0, ORDER, INV)
+    }
+}
+
+impl Default for Bn254Scalar {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Display for Bn254Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Debug for Bn254Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_canonical_biguint(), f)
+    }
+}
+
+impl Sample for Bn254Scalar {
+    #[inline]
+    fn sample<R>(rng: &mut R) -> Self
+    where
+        R: rand::RngCore + ?Sized,
+    {
+        use num::bigint::RandBigInt;
+        Self::from_noncanonical_biguint(rng.gen_biguint_below(&Self::order()))
+    }
+}
+
+impl Field for Bn254Scalar {
+    const ZERO: Self = Self([0; 4]);
+    const ONE: Self = Self([
+        0xAC96341C4FFFFFFB,
+        0x36FC76959F60CD29,
+        0x666EA36F7879462E,
+        0x0E0A77C19A07DF2F,
+    ]);
+    const TWO: Self = Self([
+        0x592C68389FFFFFF6,
+        0x6DF8ED2B3EC19A53,
+        0xCCDD46DEF0F28C5C,
+        0x1C14EF83340FBE5E,
+    ]);
+    const NEG_ONE: Self = Self([
+        0x974BC177A0000006,
+        0xF13771B2DA58A367,
+        0x51E1A2470908122E,
+        0x2259D6B14729C0FA,
+    ]);
+
+    const TWO_ADICITY: usize = 28;
+    const CHARACTERISTIC_TWO_ADICITY: usize = Self::TWO_ADICITY;
+
+    // Sage: `g = GF(p).multiplicative_generator()`
+    // 5
+    const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self([
+        0x1B0D0EF99FFFFFE6,
+        0xEABA68A3A32A913F,
+        0x47D8EB76D8DD0689,
+        0x15D0085520F5BBC3,
+    ]);
+
+    // Sage: `g_2 = power_mod(g, (p - 1) // 2^28), p)`
+    // 19103219067921713944291392827692070036145651957329286315305642004821462161904
+    const POWER_OF_TWO_GENERATOR: Self = Self([
+        0x636E735580D13D9C,
+        0xA22BF3742445FFD6,
+        0x56452AC01EB203D8,
+        0x1860EF942963F9E7,
+    ]);
+
+    const BITS: usize = 254;
+
+    fn order() -> BigUint {
+        biguint_from_array(ORDER)
+    }
+    fn characteristic() -> BigUint {
+        Self::order()
+    }
+
+    fn try_inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Fermat's Little Theorem
+        Some(Self(montgomery::inverse(self.0, Self::ONE.0, ORDER, INV)))
+    }
+
+    fn from_noncanonical_biguint(val: BigUint) -> Self {
+        Self::from_canonical_limbs(
+            val.mod_floor(&Self::order())
+                .to_u64_digits()
+                .into_iter()
+                .pad_using(4, |_| 0)
+                .collect::<Vec<_>>()[..]
+                .try_into()
+                .expect("error converting to u64 array"),
+        )
+    }
+
+    #[inline]
+    fn from_canonical_u64(n: u64) -> Self {
+        Self::from_canonical_limbs([n, 0, 0, 0])
+    }
+
+    #[inline]
+    fn from_noncanonical_u128(n: u128) -> Self {
+        Self::from_canonical_limbs([n as u64, (n >> 64) as u64, 0, 0])
+    }
+}
+
+impl PrimeField for Bn254Scalar {
+    fn to_canonical_biguint(&self) -> BigUint {
+        biguint_from_array(self.to_canonical_limbs())
+    }
+}
+
+impl Neg for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(montgomery::neg(self.0, ORDER))
+    }
+}
+
+impl Add for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(montgomery::add(self.0, rhs.0, ORDER))
+    }
+}
+
+impl AddAssign for Bn254Scalar {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Bn254Scalar {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl Sub for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(montgomery::sub(self.0, rhs.0, ORDER))
+    }
+}
+
+impl SubAssign for Bn254Scalar {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Bn254Scalar {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(montgomery::mul(self.0, rhs.0, ORDER, INV))
+    }
+}
+
+impl MulAssign for Bn254Scalar {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Product for Bn254Scalar {
+    #[inline]
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x).unwrap_or(Self::ONE)
+    }
+}
+
+impl Div for Bn254Scalar {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.inverse()
+    }
+}
+
+impl DivAssign for Bn254Scalar {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use crate::bn254_scalar::Bn254Scalar;
+    use crate::test_field_arithmetic;
+    use crate::types::{Field, PrimeField};
+
+    test_field_arithmetic!(crate::bn254_scalar::Bn254Scalar);
+
+    #[test]
+    fn test_constants() {
+        type F = Bn254Scalar;
+
+        for (x, expected) in [
+            (F::ZERO, 0u32),
+            (F::ONE, 1),
+            (F::TWO, 2),
+            (F::MULTIPLICATIVE_GROUP_GENERATOR, 5),
+        ] {
+            assert_eq!(x.to_canonical_biguint(), BigUint::from(expected));
+        }
+        assert_eq!(F::NEG_ONE.to_canonical_biguint(), F::order() - 1u32);
+        assert_eq!(
+            F::POWER_OF_TWO_GENERATOR,
+            F::MULTIPLICATIVE_GROUP_GENERATOR.exp_biguint(&(F::order() >> F::TWO_ADICITY))
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::extension::{Extendable, FieldExtension};
+use crate::ops::Square;
+use crate::packable::Packable;
+use crate::packed::PackedField;
+
+/// `P::WIDTH` elements of the degree `D` extension of `P::Scalar`, stored as `D` packed vectors of
+/// their coefficients (the `i`th vector holds the coefficients of `X^i` of every lane). All
+/// arithmetic thus runs on the packed base field, e.g. with AVX2 or AVX-512 for Goldilocks.
+///
+/// Unlike a `PackedField`, this can't be cast to an array of extension elements; use `from_fn` and
+/// `unpack` to convert.
+#[derive(Copy, Clone, Debug)]
+pub struct PackedExtension<P: PackedField, const D: usize>(pub [P; D]);
+
+/// The packed quadratic extension, using the recommended packing of `F`.
+pub type PackedQuadraticExtension<F> = PackedExtension<<F as Packable>::Packing, 2>;
+
+impl<P: PackedField, const D: usize> PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    pub const ZEROS: Self = Self([P::ZEROS; D]);
+
+    /// Packs `f(0), ..., f(P::WIDTH - 1)`.
+    pub fn from_fn<G>(mut f: G) -> Self
+    where
+        G: FnMut(usize) -> <P::Scalar as Extendable<D>>::Extension,
+    {
+        let mut res = Self::ZEROS;
+        for lane in 0..P::WIDTH {
+            let coeffs = f(lane).to_basefield_array();
+            for (p, c) in res.0.iter_mut().zip(coeffs) {
+                p.as_slice_mut()[lane] = c;
+            }
+        }
+        res
+    }
+
+    /// Packs `xs`, whose length must be `P::WIDTH`.
+    pub fn from_slice(xs: &[<P::Scalar as Extendable<D>>::Extension]) -> Self {
+        assert_eq!(xs.len(), P::WIDTH);
+        Self::from_fn(|lane| xs[lane])
+    }
+
+    /// Returns the value with every lane set to `x`.
+    pub fn broadcast(x: <P::Scalar as Extendable<D>>::Extension) -> Self {
+        Self(x.to_basefield_array().map(P::from))
+    }
+
+    /// Writes the lanes to `out`, whose length must be `P::WIDTH`.
+    pub fn unpack(&self, out: &mut [<P::Scalar as Extendable<D>>::Extension]) {
+        assert_eq!(out.len(), P::WIDTH);
+        for (lane, x) in out.iter_mut().enumerate() {
+            let coeffs = core::array::from_fn(|i| self.0[i].as_slice()[lane]);
+            *x = FieldExtension::from_basefield_array(coeffs);
+        }
+    }
+
+    /// Multiplies each lane by the corresponding lane of `scalar`, a packed base field element.
+    pub fn scalar_mul(&self, scalar: P) -> Self {
+        Self(self.0.map(|c| c * scalar))
+    }
+}
+
+impl<P: PackedField, const D: usize> Default for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    fn default() -> Self {
+        Self::ZEROS
+    }
+}
+
+impl<P: PackedField, const D: usize> Add for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<P: PackedField, const D: usize> AddAssign for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<P: PackedField, const D: usize> Neg for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<P: PackedField, const D: usize> Sub for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<P: PackedField, const D: usize> SubAssign for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<P: PackedField, const D: usize> Mul for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let w = <P::Scalar as Extendable<D>>::W;
+        if D == 2 {
+            // Karatsuba, which saves one packed multiplication.
+            let (a0, a1, b0, b1) = (self.0[0], self.0[1], rhs.0[0], rhs.0[1]);
+            let a0_b0 = a0 * b0;
+            let a1_b1 = a1 * b1;
+            let c1 = (a0 + a1) * (b0 + b1) - a0_b0 - a1_b1;
+            return Self(core::array::from_fn(|k| {
+                if k == 0 {
+                    a0_b0 + a1_b1 * w
+                } else {
+                    c1
+                }
+            }));
+        }
+
+        // Schoolbook multiplication, where the terms of degree `D + k` are multiplied by `W` once
+        // summed, as `X^D = W`.
+        let mut lo = [P::ZEROS; D];
+        let mut hi = [P::ZEROS; D];
+        for i in 0..D {
+            for j in 0..D {
+                let product = self.0[i] * rhs.0[j];
+                if i + j < D {
+                    lo[i + j] += product;
+                } else {
+                    hi[i + j - D] += product;
+                }
+            }
+        }
+        Self(core::array::from_fn(|k| lo[k] + hi[k] * w))
+    }
+}
+
+impl<P: PackedField, const D: usize> MulAssign for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<P: PackedField, const D: usize> Square for PackedExtension<P, D>
+where
+    P::Scalar: Extendable<D>,
+{
+    #[inline]
+    fn square(&self) -> Self {
+        *self * *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::goldilocks_field::GoldilocksField;
+    use crate::types::Sample;
+
+    fn test_packed_extension_arithmetic<P: PackedField, const D: usize>()
+    where
+        P::Scalar: Extendable<D>,
+    {
+        type Ext<P, const D: usize> = <<P as PackedField>::Scalar as Extendable<D>>::Extension;
+
+        let xs = Ext::<P, D>::rand_vec(P::WIDTH);
+        let ys = Ext::<P, D>::rand_vec(P::WIDTH);
+        let scalars = P::Scalar::rand_vec(P::WIDTH);
+        let x = PackedExtension::<P, D>::from_slice(&xs);
+        let y = PackedExtension::<P, D>::from_slice(&ys);
+        let scalar = *P::from_slice(&scalars);
+
+        let check = |packed: PackedExtension<P, D>, expected: Vec<Ext<P, D>>| {
+            let mut unpacked = Ext::<P, D>::rand_vec(P::WIDTH);
+            packed.unpack(&mut unpacked);
+            assert_eq!(unpacked, expected);
+        };
+        let lanes = xs.iter().zip(&ys).zip(&scalars);
+        check(x + y, lanes.clone().map(|((&x, &y), _)| x + y).collect());
+        check(x - y, lanes.clone().map(|((&x, &y), _)| x - y).collect());
+        check(-x, xs.iter().map(|&x| -x).collect());
+        check(x * y, lanes.clone().map(|((&x, &y), _)| x * y).collect());
+        check(x.square(), xs.iter().map(|&x| x * x).collect());
+        check(
+            x.scalar_mul(scalar),
+            lanes.map(|((&x, _), &s)| x.scalar_mul(s)).collect(),
+        );
+        check(
+            PackedExtension::broadcast(ys[0]),
+            (0..P::WIDTH).map(|_| ys[0]).collect(),
+        );
+    }
+
+    #[test]
+    fn test_packed_quadratic_extension() {
+        type F = GoldilocksField;
+        test_packed_extension_arithmetic::<<F as Packable>::Packing, 2>();
+        test_packed_extension_arithmetic::<F, 2>();
+    }
+
+    #[test]
+    fn test_packed_higher_extensions() {
+        type F = GoldilocksField;
+        test_packed_extension_arithmetic::<<F as Packable>::Packing, 4>();
+        test_packed_extension_arithmetic::<<F as Packable>::Packing, 5>();
+    }
+}
@@ -1,7 +1,9 @@
+use alloc::vec;
 use alloc::vec::Vec;
 use core::cmp::{max, min};
+use core::slice;
 
-use plonky2_util::{log2_strict, reverse_index_bits_in_place};
+use plonky2_util::{log2_strict, reverse_index_bits_in_place, transpose_in_place_square_matrix};
 use unroll::unroll_for_loops;
 
 use crate::packable::Packable;
@@ -11,6 +13,18 @@ use crate::types::Field;
 
 pub type FftRootTable<F> = Vec<Vec<F>>;
 
+/// The algorithm used to compute FFTs.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum FftStrategy {
+    /// The radix-2 FFT, applied after a bit-reversal permutation of the input.
+    #[default]
+    Classic,
+    /// A cache-oblivious FFT, which views the input as a matrix, and recursively applies FFTs to
+    /// its columns and rows, transposing it in place in between. This doesn't make use of the zero
+    /// factor.
+    Transpose,
+}
+
 pub fn fft_root_table<F: Field>(n: usize) -> FftRootTable<F> {
     let lg_n = log2_strict(n);
     // bases[i] = g^2^i, for i = 0, ..., lg_n - 1
@@ -37,6 +51,7 @@ fn fft_dispatch<F: Field>(
     input: &mut [F],
     zero_factor: Option<usize>,
     root_table: Option<&FftRootTable<F>>,
+    strategy: FftStrategy,
 ) {
     let computed_root_table = if root_table.is_some() {
         None
@@ -45,12 +60,15 @@ fn fft_dispatch<F: Field>(
     };
     let used_root_table = root_table.or(computed_root_table.as_ref()).unwrap();
 
-    fft_classic(input, zero_factor.unwrap_or(0), used_root_table);
+    match strategy {
+        FftStrategy::Classic => fft_classic(input, zero_factor.unwrap_or(0), used_root_table),
+        FftStrategy::Transpose => fft_transpose(input, used_root_table),
+    }
 }
 
 #[inline]
 pub fn fft<F: Field>(poly: PolynomialCoeffs<F>) -> PolynomialValues<F> {
-    fft_with_options(poly, None, None)
+    fft_with_options(poly, None, None, FftStrategy::default())
 }
 
 #[inline]
@@ -58,28 +76,30 @@ pub fn fft_with_options<F: Field>(
     poly: PolynomialCoeffs<F>,
     zero_factor: Option<usize>,
     root_table: Option<&FftRootTable<F>>,
+    strategy: FftStrategy,
 ) -> PolynomialValues<F> {
     let PolynomialCoeffs { coeffs: mut buffer } = poly;
-    fft_dispatch(&mut buffer, zero_factor, root_table);
+    fft_dispatch(&mut buffer, zero_factor, root_table, strategy);
     PolynomialValues::new(buffer)
 }
 
 #[inline]
 pub fn ifft<F: Field>(poly: PolynomialValues<F>) -> PolynomialCoeffs<F> {
-    ifft_with_options(poly, None, None)
+    ifft_with_options(poly, None, None, FftStrategy::default())
 }
 
 pub fn ifft_with_options<F: Field>(
     poly: PolynomialValues<F>,
     zero_factor: Option<usize>,
     root_table: Option<&FftRootTable<F>>,
+    strategy: FftStrategy,
 ) -> PolynomialCoeffs<F> {
     let n = poly.len();
     let lg_n = log2_strict(n);
     let n_inv = F::inverse_2exp(lg_n);
 
     let PolynomialValues { values: mut buffer } = poly;
-    fft_dispatch(&mut buffer, zero_factor, root_table);
+    fft_dispatch(&mut buffer, zero_factor, root_table, strategy);
 
     // We reverse all values except the first, and divide each by n.
     buffer[0] *= n_inv;
@@ -100,7 +120,7 @@ fn fft_classic_simd<P: PackedField>(
     values: &mut [P::Scalar],
     r: usize,
     lg_n: usize,
-    root_table: &FftRootTable<P::Scalar>,
+    root_table: &[Vec<P::Scalar>],
 ) {
     let lg_packed_width = log2_strict(P::WIDTH); // 0 when P is a scalar.
     let packed_values = P::pack_slice_mut(values);
@@ -166,7 +186,7 @@ fn fft_classic_simd<P: PackedField>(
 /// The parameter r signifies that the first 1/2^r of the entries of
 /// input may be non-zero, but the last 1 - 1/2^r entries are
 /// definitely zero.
-pub(crate) fn fft_classic<F: Field>(values: &mut [F], r: usize, root_table: &FftRootTable<F>) {
+pub(crate) fn fft_classic<F: Field>(values: &mut [F], r: usize, root_table: &[Vec<F>]) {
     reverse_index_bits_in_place(values);
 
     let n = values.len();
@@ -205,16 +225,149 @@ pub(crate) fn fft_classic<F: Field>(values: &mut [F], r: usize, root_table: &Fft
     }
 }
 
+/// Below this size, `fft_transpose` uses `fft_classic`, as the whole input then fits in cache.
+const LG_TRANSPOSE_FFT_BASE_SIZE: usize = 10;
+
+/// Cache-oblivious FFT implementation, which recursively splits the input into a matrix. This is a
+/// port of the prototype in `projects/cache-friendly-fft`.
+pub(crate) fn fft_transpose<F: Field>(values: &mut [F], root_table: &[Vec<F>]) {
+    let lg_n = log2_strict(values.len());
+    assert_eq!(
+        root_table.len(),
+        lg_n,
+        "Expected root table of length {}, but it was {}.",
+        lg_n,
+        root_table.len()
+    );
+
+    // Only inputs of length `2^odd` need scratch space, to deinterleave pairs of columns.
+    let mut scratch = vec![F::ZERO; 1 << (lg_n / 2)];
+    fft_transpose_rec(values, &mut scratch, root_table);
+}
+
+fn fft_transpose_rec<F: Field>(values: &mut [F], scratch: &mut [F], root_table: &[Vec<F>]) {
+    let lg_n = log2_strict(values.len());
+    if lg_n <= LG_TRANSPOSE_FFT_BASE_SIZE {
+        // The root table of a smaller FFT is a prefix of the table of a larger one.
+        fft_classic(values, 0, &root_table[..lg_n]);
+    } else if lg_n & 1 == 0 {
+        fft_transpose_even(values, scratch, root_table);
+    } else {
+        fft_transpose_odd(values, scratch, root_table);
+    }
+}
+
+/// FFT of length `2^even`. We view `values` as a square matrix `M` in row-major order, so that
+/// with `n = m^2` and `w` a primitive `n`-th root of unity,
+/// ```ignore
+/// FFT(values)[c + m d] = sum_b w^(m b d) (w^(b c) sum_a M[a][b] w^(m a c)).
+/// ```
+/// We apply FFTs to the columns of `M`, multiply them by the twiddle factors `w^(b c)`, then apply
+/// FFTs to the rows. The results are then in column-major order.
+fn fft_transpose_even<F: Field>(values: &mut [F], scratch: &mut [F], root_table: &[Vec<F>]) {
+    let lg_n = log2_strict(values.len());
+    let lg_m = lg_n / 2;
+    let m = 1 << lg_m;
+
+    // Transposing makes the columns contiguous. We multiply them by the twiddle factors while they
+    // are still in cache.
+    transpose_in_place_square_matrix(values, lg_m);
+    for (b, column) in values.chunks_exact_mut(m).enumerate() {
+        fft_transpose_rec(column, scratch, root_table);
+        apply_twiddle_factors(column, b, lg_n, root_table);
+    }
+    transpose_in_place_square_matrix(values, lg_m);
+
+    for row in values.chunks_exact_mut(m) {
+        fft_transpose_rec(row, scratch, root_table);
+    }
+    transpose_in_place_square_matrix(values, lg_m);
+}
+
+/// FFT of length `2^odd`. This is `fft_transpose_even` for a matrix `M` with twice as many columns
+/// as rows. As only square matrices can be transposed in place, we view `M` as a square matrix of
+/// pairs. Each row of its transpose holds two columns of `M`, which are interleaved.
+fn fft_transpose_odd<F: Field>(values: &mut [F], scratch: &mut [F], root_table: &[Vec<F>]) {
+    let lg_n = log2_strict(values.len());
+    let lg_rows = lg_n / 2;
+    let rows = 1 << lg_rows;
+    let columns = 2 * rows;
+
+    transpose_in_place_square_matrix(as_pairs_mut(values), lg_rows);
+    for (i, column_pair) in values.chunks_exact_mut(columns).enumerate() {
+        deinterleave(column_pair, scratch);
+        let (column0, column1) = column_pair.split_at_mut(rows);
+        fft_transpose_rec(column0, scratch, root_table);
+        apply_twiddle_factors(column0, 2 * i, lg_n, root_table);
+        fft_transpose_rec(column1, scratch, root_table);
+        apply_twiddle_factors(column1, 2 * i + 1, lg_n, root_table);
+        interleave(column_pair, scratch);
+    }
+    transpose_in_place_square_matrix(as_pairs_mut(values), lg_rows);
+
+    for row in values.chunks_exact_mut(columns) {
+        fft_transpose_rec(row, scratch, root_table);
+    }
+    transpose_in_place_square_matrix(as_pairs_mut(values), lg_rows);
+    for row_pair in values.chunks_exact_mut(columns) {
+        deinterleave(row_pair, scratch);
+    }
+}
+
+/// Multiplies `values[j]` by `w^(i j)`, where `w` is a primitive `2^lg_n`-th root of unity.
+fn apply_twiddle_factors<F: Field>(values: &mut [F], i: usize, lg_n: usize, root_table: &[Vec<F>]) {
+    // `root_table[lg_n - 1]` holds the first `n / 2` powers of `w`, and `w^(n / 2) = -1`.
+    let powers = &root_table[lg_n - 1];
+    let half_n = 1 << (lg_n - 1);
+    let mask = (1 << lg_n) - 1;
+    for (j, v) in values.iter_mut().enumerate() {
+        let exponent = (i * j) & mask;
+        if exponent < half_n {
+            *v *= powers[exponent];
+        } else {
+            *v *= -powers[exponent - half_n];
+        }
+    }
+}
+
+/// Reinterprets a slice of even length as a slice of pairs.
+fn as_pairs_mut<T>(values: &mut [T]) -> &mut [[T; 2]] {
+    debug_assert!(values.len() & 1 == 0);
+    // SAFETY: `[T; 2]` has the alignment of `T` and twice its size.
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut [T; 2], values.len() / 2) }
+}
+
+/// Moves the elements at even indices to the first half of `values`, and the others to the second
+/// half. `scratch` must hold at least `values.len() / 2` elements.
+fn deinterleave<F: Field>(values: &mut [F], scratch: &mut [F]) {
+    let half_n = values.len() / 2;
+    for i in 0..half_n {
+        values[i] = values[2 * i];
+        scratch[i] = values[2 * i + 1];
+    }
+    values[half_n..].copy_from_slice(&scratch[..half_n]);
+}
+
+/// The inverse of `deinterleave`.
+fn interleave<F: Field>(values: &mut [F], scratch: &mut [F]) {
+    let half_n = values.len() / 2;
+    scratch[..half_n].copy_from_slice(&values[..half_n]);
+    for i in 0..half_n {
+        values[2 * i] = scratch[i];
+        values[2 * i + 1] = values[half_n + i];
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec::Vec;
 
     use plonky2_util::{log2_ceil, log2_strict};
 
-    use crate::fft::{fft, fft_with_options, ifft};
+    use crate::fft::{fft, fft_with_options, ifft, ifft_with_options, FftStrategy};
     use crate::goldilocks_field::GoldilocksField;
     use crate::polynomial::{PolynomialCoeffs, PolynomialValues};
-    use crate::types::Field;
+    use crate::types::{Field, Sample};
 
     #[test]
     fn fft_and_ifft() {
@@ -247,7 +400,25 @@ mod tests {
             let zero_tail = coefficients.lde(r);
             assert_eq!(
                 fft(zero_tail.clone()),
-                fft_with_options(zero_tail, Some(r), None)
+                fft_with_options(zero_tail, Some(r), None, FftStrategy::Classic)
+            );
+        }
+    }
+
+    #[test]
+    fn fft_transpose_matches_classic() {
+        type F = GoldilocksField;
+        // Sizes up to `2^14` exercise a few levels of recursion, for both parities of `lg_n`.
+        for lg_n in 0..=14 {
+            let coeffs = PolynomialCoeffs::new(F::rand_vec(1 << lg_n));
+            let values = fft_with_options(coeffs.clone(), None, None, FftStrategy::Classic);
+            assert_eq!(
+                fft_with_options(coeffs.clone(), None, None, FftStrategy::Transpose),
+                values
+            );
+            assert_eq!(
+                ifft_with_options(values, None, None, FftStrategy::Transpose),
+                coeffs
             );
         }
     }
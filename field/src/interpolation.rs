@@ -3,15 +3,25 @@ use alloc::vec::Vec;
 use plonky2_util::log2_ceil;
 
 use crate::fft::ifft;
+use crate::polynomial::multipoint::SubproductTree;
 use crate::polynomial::{PolynomialCoeffs, PolynomialValues};
 use crate::types::Field;
 
+/// From this many points, `interpolant` uses a subproduct tree rather than the quadratic
+/// barycentric formula.
+const SUBPRODUCT_TREE_THRESHOLD: usize = 8;
+
 /// Computes the unique degree < n interpolant of an arbitrary list of n (point, value) pairs.
 ///
 /// Note that the implementation assumes that `F` is two-adic, in particular that
-/// `2^{F::TWO_ADICITY} >= points.len()`. This leads to a simple FFT-based implementation.
+/// `2^{F::TWO_ADICITY} >= 2 * points.len()`. This leads to a simple FFT-based implementation.
 pub fn interpolant<F: Field>(points: &[(F, F)]) -> PolynomialCoeffs<F> {
     let n = points.len();
+    if n >= SUBPRODUCT_TREE_THRESHOLD {
+        let (xs, ys): (Vec<F>, Vec<F>) = points.iter().copied().unzip();
+        return SubproductTree::new(&xs).interpolate(&ys);
+    }
+
     let n_log = log2_ceil(n);
 
     let subgroup = F::two_adic_subgroup(n_log);
@@ -127,6 +137,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn interpolant_random_many_points() {
+        type F = GoldilocksField;
+
+        for (num_points, deg) in [(32, 32), (100, 100), (300, 200)] {
+            let domain = F::rand_vec(num_points);
+            let coeffs = F::rand_vec(deg);
+            let coeffs = PolynomialCoeffs { coeffs };
+
+            let points = eval_naive(&coeffs, &domain);
+            assert_eq!(interpolant(&points), coeffs);
+        }
+    }
+
     fn eval_naive<F: Field>(coeffs: &PolynomialCoeffs<F>, domain: &[F]) -> Vec<(F, F)> {
         domain.iter().map(|&x| (x, coeffs.eval(x))).collect()
     }
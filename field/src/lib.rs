@@ -13,11 +13,18 @@
 extern crate alloc;
 
 mod inversion;
+mod montgomery;
+mod pseudo_mersenne;
 
 pub(crate) mod arch;
 
 pub mod batch_util;
+pub mod bn254_base;
+pub mod bn254_scalar;
 pub mod cosets;
+pub mod ecgfp5_scalar;
+pub mod ed25519_base;
+pub mod ed25519_scalar;
 pub mod extension;
 pub mod fft;
 pub mod goldilocks_extensions;
@@ -0,0 +1,182 @@
+//! Allocation-free Montgomery arithmetic modulo a 256-bit prime `p < 2^255`, as used by the BN254
+//! fields. Elements are given by four little-endian 64-bit limbs holding `a R mod p`, where
+//! `R = 2^256`, and must be canonical, i.e. less than `p`. All functions return canonical limbs.
+//!
+//! Multiplication takes `inv = -p^-1 mod 2^64`.
+
+use crate::pseudo_mersenne::{adc, add_limbs, mac, sub_limbs};
+
+/// Subtracts `p` from `a` if `a >= p`, given that `a < 2p`.
+#[inline]
+fn reduce_once(a: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    let (difference, underflow) = sub_limbs(a, p);
+    if underflow {
+        a
+    } else {
+        difference
+    }
+}
+
+#[inline]
+pub(crate) fn add(a: [u64; 4], b: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    // As `p < 2^255`, the sum can't overflow.
+    reduce_once(add_limbs(a, b).0, p)
+}
+
+#[inline]
+pub(crate) fn sub(a: [u64; 4], b: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    let (difference, underflow) = sub_limbs(a, b);
+    if underflow {
+        add_limbs(difference, p).0
+    } else {
+        difference
+    }
+}
+
+#[inline]
+pub(crate) fn neg(a: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    sub([0; 4], a, p)
+}
+
+/// Computes `a b R^-1 mod p`, by interleaving the schoolbook product with the Montgomery reduction
+/// (the CIOS method).
+#[inline]
+pub(crate) fn mul(a: [u64; 4], b: [u64; 4], p: [u64; 4], inv: u64) -> [u64; 4] {
+    let mut t = [0u64; 5];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (t[j], carry) = mac(t[j], a[j], b[i], carry);
+        }
+        let (t4, t5) = adc(t[4], carry, 0);
+
+        // Adding `m p` makes `t` divisible by `2^64`, so we can shift it by one limb.
+        let m = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], m, p[0], 0);
+        for j in 1..4 {
+            (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+        }
+        (t[3], carry) = adc(t4, carry, 0);
+        t[4] = t5 + carry;
+    }
+    // `t < 2p < 2^256`, so `t[4]` is zero.
+    reduce_once([t[0], t[1], t[2], t[3]], p)
+}
+
+/// Converts `a < p` into Montgomery form, given `r2 = R^2 mod p`.
+#[inline]
+pub(crate) fn to_montgomery(a: [u64; 4], r2: [u64; 4], p: [u64; 4], inv: u64) -> [u64; 4] {
+    mul(a, r2, p, inv)
+}
+
+/// Converts `a` out of Montgomery form.
+#[inline]
+pub(crate) fn from_montgomery(a: [u64; 4], p: [u64; 4], inv: u64) -> [u64; 4] {
+    mul(a, [1, 0, 0, 0], p, inv)
+}
+
+/// Computes `a^e` by square-and-multiply, given `one = R mod p`.
+#[inline]
+pub(crate) fn exp(a: [u64; 4], e: [u64; 4], one: [u64; 4], p: [u64; 4], inv: u64) -> [u64; 4] {
+    let mut result = one;
+    for &limb in e.iter().rev() {
+        for i in (0..64).rev() {
+            result = mul(result, result, p, inv);
+            if (limb >> i) & 1 == 1 {
+                result = mul(result, a, p, inv);
+            }
+        }
+    }
+    result
+}
+
+/// Computes `a^-1` as `a^(p - 2)`, which is zero if `a` is zero.
+#[inline]
+pub(crate) fn inverse(a: [u64; 4], one: [u64; 4], p: [u64; 4], inv: u64) -> [u64; 4] {
+    exp(a, sub_limbs(p, [2, 0, 0, 0]).0, one, p, inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One};
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    const BN254_SCALAR_ORDER: [u64; 4] = [
+        0x43E1F593F0000001,
+        0x2833E84879B97091,
+        0xB85045B68181585D,
+        0x30644E72E131A029,
+    ];
+    const BN254_SCALAR_INV: u64 = 0xC2E1F593EFFFFFFF;
+
+    const BN254_BASE_ORDER: [u64; 4] = [
+        0x3C208C16D87CFD47,
+        0x97816A916871CA8D,
+        0xB85045B68181585D,
+        0x30644E72E131A029,
+    ];
+    const BN254_BASE_INV: u64 = 0x87D20782E4866389;
+
+    fn to_biguint(a: [u64; 4]) -> BigUint {
+        BigUint::from_slice(&a.map(|l| [l as u32, (l >> 32) as u32]).concat())
+    }
+
+    fn from_biguint(a: &BigUint) -> [u64; 4] {
+        let mut limbs = a.to_u64_digits();
+        limbs.resize(4, 0);
+        limbs.try_into().unwrap()
+    }
+
+    fn check_against_biguint(p: [u64; 4], inv: u64) {
+        let p_big = to_biguint(p);
+        assert_eq!(p[0].wrapping_mul(inv), u64::MAX);
+
+        let r: BigUint = (BigUint::one() << 256) % &p_big;
+        let r_inv = r.modpow(&(&p_big - 2u32), &p_big);
+        let r2 = from_biguint(&(&r * &r % &p_big));
+        let one = from_biguint(&r);
+
+        let mut rng = OsRng;
+        let edge_cases = [[0; 4], [1, 0, 0, 0], sub_limbs(p, [1, 0, 0, 0]).0];
+        let random = (0..20).map(|_| from_biguint(&rng.gen_biguint_below(&p_big)));
+        let values: Vec<[u64; 4]> = edge_cases.into_iter().chain(random).collect();
+
+        for &a in &values {
+            let a_big = to_biguint(a);
+            let a_mont = to_montgomery(a, r2, p, inv);
+            assert_eq!(to_biguint(a_mont), &a_big * &r % &p_big);
+            assert_eq!(from_montgomery(a_mont, p, inv), a);
+            assert_eq!(to_biguint(neg(a, p)), (&p_big - &a_big) % &p_big);
+            if a_big != BigUint::default() {
+                let a_inv = from_montgomery(inverse(a_mont, one, p, inv), p, inv);
+                assert_eq!(to_biguint(a_inv) * &a_big % &p_big, BigUint::one());
+            }
+
+            for &b in &values {
+                let b_big = to_biguint(b);
+                assert_eq!(to_biguint(add(a, b, p)), (&a_big + &b_big) % &p_big);
+                assert_eq!(
+                    to_biguint(sub(a, b, p)),
+                    (&p_big + &a_big - &b_big) % &p_big
+                );
+                assert_eq!(
+                    to_biguint(mul(a, b, p, inv)),
+                    &a_big * &b_big * &r_inv % &p_big
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bn254_scalar_against_biguint() {
+        check_against_biguint(BN254_SCALAR_ORDER, BN254_SCALAR_INV);
+    }
+
+    #[test]
+    fn test_bn254_base_against_biguint() {
+        check_against_biguint(BN254_BASE_ORDER, BN254_BASE_INV);
+    }
+}
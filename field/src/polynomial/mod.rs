@@ -1,4 +1,5 @@
 pub(crate) mod division;
+pub mod multipoint;
 
 use alloc::vec;
 use alloc::vec::Vec;
@@ -12,7 +13,7 @@ use plonky2_util::log2_strict;
 use serde::{Deserialize, Serialize};
 
 use crate::extension::{Extendable, FieldExtension};
-use crate::fft::{fft, fft_with_options, ifft, FftRootTable};
+use crate::fft::{fft, fft_with_options, ifft, FftRootTable, FftStrategy};
 use crate::types::Field;
 
 /// A polynomial in point-value form.
@@ -78,7 +79,7 @@ impl<F: Field> PolynomialValues<F> {
 
     pub fn lde(self, rate_bits: usize) -> Self {
         let coeffs = ifft(self).lde(rate_bits);
-        fft_with_options(coeffs, Some(rate_bits), None)
+        fft_with_options(coeffs, Some(rate_bits), None, FftStrategy::default())
     }
 
     /// Low-degree extend `Self` (seen as evaluations over the subgroup) onto a coset.
@@ -194,6 +195,18 @@ impl<F: Field> PolynomialCoeffs<F> {
             .fold(acc, |acc, (&x, &c)| acc + x.scalar_mul(c))
     }
 
+    /// Returns the formal derivative of the polynomial.
+    pub fn derivative(&self) -> Self {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, &c)| F::from_canonical_usize(i) * c)
+            .collect();
+        Self::new(coeffs)
+    }
+
     pub fn lde_multiple(polys: Vec<&Self>, rate_bits: usize) -> Vec<Self> {
         polys.into_iter().map(|p| p.lde(rate_bits)).collect()
     }
@@ -270,7 +283,7 @@ impl<F: Field> PolynomialCoeffs<F> {
         zero_factor: Option<usize>,
         root_table: Option<&FftRootTable<F>>,
     ) -> PolynomialValues<F> {
-        fft_with_options(self, zero_factor, root_table)
+        fft_with_options(self, zero_factor, root_table, FftStrategy::default())
     }
 
     /// Returns the evaluation of the polynomial on the coset `shift*H`.
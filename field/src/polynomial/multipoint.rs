@@ -0,0 +1,194 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::polynomial::PolynomialCoeffs;
+use crate::types::Field;
+
+/// The number of points in each leaf of a `SubproductTree`. Below this size, Horner's method and
+/// schoolbook products are cheaper than FFT-based products and divisions.
+const LEAF_SIZE: usize = 16;
+
+/// The subproduct tree of a list of distinct points `x_0, ..., x_{n-1}`, used for evaluation and
+/// interpolation at arbitrary points in `O(M(n) log n)` field operations, where `M(n)` is the cost
+/// of multiplying two polynomials of degree `n`.
+///
+/// Each node is the product of `X - x_i` over the points below it, so that the root is the
+/// vanishing polynomial of all the points. The leaves cover consecutive chunks of `LEAF_SIZE`
+/// points, and node `j` of a layer is the product of nodes `2j` and `2j + 1` of the layer below.
+///
+/// As products use the FFT, this assumes that `2^{F::TWO_ADICITY} >= 2n`.
+#[derive(Clone, Debug)]
+pub struct SubproductTree<F: Field> {
+    points: Vec<F>,
+    /// The layers of the tree, from the leaves up to the root.
+    layers: Vec<Vec<PolynomialCoeffs<F>>>,
+}
+
+impl<F: Field> SubproductTree<F> {
+    pub fn new(points: &[F]) -> Self {
+        assert!(!points.is_empty(), "No points given");
+
+        let leaves = points
+            .chunks(LEAF_SIZE)
+            .map(|chunk| {
+                let mut leaf = vec![F::ONE];
+                for &x in chunk {
+                    // Multiply by `X - x`.
+                    leaf.insert(0, F::ZERO);
+                    for i in 0..leaf.len() - 1 {
+                        let c = leaf[i + 1];
+                        leaf[i] -= x * c;
+                    }
+                }
+                PolynomialCoeffs::new(leaf)
+            })
+            .collect();
+
+        let mut layers: Vec<Vec<PolynomialCoeffs<F>>> = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => {
+                        let mut product = left * right;
+                        product.trim();
+                        product
+                    }
+                    [node] => node.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        Self {
+            points: points.to_vec(),
+            layers,
+        }
+    }
+
+    pub fn points(&self) -> &[F] {
+        &self.points
+    }
+
+    /// Returns the vanishing polynomial of the points, i.e. the product of all `X - x_i`.
+    pub fn vanishing_poly(&self) -> &PolynomialCoeffs<F> {
+        &self.layers.last().unwrap()[0]
+    }
+
+    /// Evaluates `poly` at each of the points, by reducing it modulo the nodes of the tree from the
+    /// root down to the leaves.
+    pub fn evaluate(&self, poly: &PolynomialCoeffs<F>) -> Vec<F> {
+        let mut remainders = vec![poly.div_rem(self.vanishing_poly()).1];
+        for layer in self.layers.iter().rev().skip(1) {
+            remainders = layer
+                .iter()
+                .enumerate()
+                .map(|(j, node)| remainders[j / 2].div_rem(node).1)
+                .collect();
+        }
+
+        self.points
+            .chunks(LEAF_SIZE)
+            .zip(&remainders)
+            .flat_map(|(chunk, remainder)| chunk.iter().map(|&x| remainder.eval(x)))
+            .collect()
+    }
+
+    /// Computes the unique polynomial of degree less than the number of points which takes the
+    /// given values at the points.
+    pub fn interpolate(&self, values: &[F]) -> PolynomialCoeffs<F> {
+        assert_eq!(values.len(), self.points.len());
+
+        // By Lagrange's formula, the interpolant is `sum_i c_i M(X) / (X - x_i)`, where `M` is the
+        // vanishing polynomial and `c_i = y_i / M'(x_i)`.
+        let derivatives = self.evaluate(&self.vanishing_poly().derivative());
+        let weights = F::batch_multiplicative_inverse(&derivatives);
+
+        // Each partial sum `sum_i c_i N(X) / (X - x_i)`, over the points below a node `N`, is
+        // combined from the partial sums `S_l, S_r` of its children `N_l, N_r` as
+        // `S_l N_r + S_r N_l`.
+        let mut sums: Vec<PolynomialCoeffs<F>> = self
+            .points
+            .chunks(LEAF_SIZE)
+            .zip(values.chunks(LEAF_SIZE).zip(weights.chunks(LEAF_SIZE)))
+            .zip(&self.layers[0])
+            .map(|((chunk, (ys, ws)), leaf)| {
+                let mut sum = PolynomialCoeffs::zero(chunk.len());
+                for ((&x, &y), &w) in chunk.iter().zip(ys).zip(ws) {
+                    // `leaf` vanishes at `x`, so this division is exact.
+                    let quotient = leaf.divide_by_linear(x);
+                    for (s, &q) in sum.coeffs.iter_mut().zip(&quotient.coeffs) {
+                        *s += q * y * w;
+                    }
+                }
+                sum
+            })
+            .collect();
+        for layer in &self.layers[..self.layers.len() - 1] {
+            sums = sums
+                .chunks(2)
+                .zip(layer.chunks(2))
+                .map(|(sums, nodes)| match (sums, nodes) {
+                    ([s_l, s_r], [n_l, n_r]) => &(s_l * n_r) + &(s_r * n_l),
+                    ([s], [_]) => s.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        let mut interpolant = sums.pop().unwrap();
+        interpolant.trim();
+        interpolant
+    }
+}
+
+impl<F: Field> PolynomialCoeffs<F> {
+    /// Evaluates the polynomial at each of `points`, which need not form a subgroup or coset.
+    pub fn eval_many(&self, points: &[F]) -> Vec<F> {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        SubproductTree::new(points).evaluate(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension::quadratic::QuadraticExtension;
+    use crate::goldilocks_field::GoldilocksField;
+    use crate::types::Sample;
+
+    #[test]
+    fn test_eval_many() {
+        type F = GoldilocksField;
+
+        for (num_points, degree) in [(1, 0), (1, 5), (15, 100), (16, 16), (17, 3), (300, 250)] {
+            let poly = PolynomialCoeffs::new(F::rand_vec(degree));
+            let points = F::rand_vec(num_points);
+            let expected = points.iter().map(|&x| poly.eval(x)).collect::<Vec<_>>();
+            assert_eq!(poly.eval_many(&points), expected);
+        }
+    }
+
+    #[test]
+    fn test_subproduct_tree_interpolate() {
+        type F = QuadraticExtension<GoldilocksField>;
+
+        for n in [1, 2, 16, 33, 100, 257] {
+            let points = F::rand_vec(n);
+            let tree = SubproductTree::new(&points);
+
+            let vanishing_poly = tree.vanishing_poly();
+            assert_eq!(vanishing_poly.degree_plus_one(), n + 1);
+            assert!(tree.evaluate(vanishing_poly).iter().all(|y| y.is_zero()));
+
+            let poly = PolynomialCoeffs::new(F::rand_vec(n));
+            let values = tree.evaluate(&poly);
+            assert_eq!(tree.interpolate(&values), poly);
+        }
+    }
+}
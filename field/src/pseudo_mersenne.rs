@@ -0,0 +1,223 @@
+//! Allocation-free arithmetic modulo a 256-bit prime `p = 2^256 - c` with `c < 2^192`, as used by
+//! the secp256k1 fields. Elements are given by four little-endian 64-bit limbs, which may be
+//! non-canonical, i.e. in `[p, 2^256)`. All functions return canonical limbs.
+
+/// Returns `a + b + carry`, and the new carry.
+#[inline(always)]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Returns `a - b - borrow`, and the new borrow.
+#[inline(always)]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// Returns `a + b * c + carry`, and the new carry. This can't overflow, as
+/// `2 (2^64 - 1) + (2^64 - 1)^2 = 2^128 - 1`.
+#[inline(always)]
+pub(crate) fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Returns `a + b mod 2^256`, and whether it overflowed.
+#[inline]
+pub(crate) fn add_limbs(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], bool) {
+    let mut result = [0; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        (result[i], carry) = adc(a[i], b[i], carry);
+    }
+    (result, carry != 0)
+}
+
+/// Returns `a - b mod 2^256`, and whether it underflowed.
+#[inline]
+pub(crate) fn sub_limbs(a: [u64; 4], b: [u64; 4]) -> ([u64; 4], bool) {
+    let mut result = [0; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (result[i], borrow) = sbb(a[i], b[i], borrow);
+    }
+    (result, borrow != 0)
+}
+
+#[inline]
+pub(crate) fn canonicalize(a: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    // As `p > 2^255`, subtracting `p` once is enough.
+    let (difference, underflow) = sub_limbs(a, p);
+    if underflow {
+        a
+    } else {
+        difference
+    }
+}
+
+#[inline]
+pub(crate) fn add(a: [u64; 4], b: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    let (sum, overflow) = add_limbs(canonicalize(a, p), canonicalize(b, p));
+    let (difference, underflow) = sub_limbs(sum, p);
+    // The sum is less than `2p`, and is at least `p` iff it overflowed or `sum - p` didn't.
+    if overflow || !underflow {
+        difference
+    } else {
+        sum
+    }
+}
+
+#[inline]
+pub(crate) fn sub(a: [u64; 4], b: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    let (difference, underflow) = sub_limbs(canonicalize(a, p), canonicalize(b, p));
+    if underflow {
+        add_limbs(difference, p).0
+    } else {
+        difference
+    }
+}
+
+#[inline]
+pub(crate) fn neg(a: [u64; 4], p: [u64; 4]) -> [u64; 4] {
+    sub([0; 4], a, p)
+}
+
+#[inline]
+pub(crate) fn mul(a: [u64; 4], b: [u64; 4], p: [u64; 4], c: [u64; 3]) -> [u64; 4] {
+    let mut product = [0; 8];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (product[i + j], carry) = mac(product[i + j], a[i], b[j], carry);
+        }
+        product[i + 4] = carry;
+    }
+    reduce_wide(product, p, c)
+}
+
+/// Reduces a 512-bit value, using `2^256 = c mod p`. Each round replaces `lo + 2^256 hi` with
+/// `lo + c hi`, which shrinks the value by at least 64 bits until it fits in 256 bits.
+#[inline]
+fn reduce_wide(mut t: [u64; 8], p: [u64; 4], c: [u64; 3]) -> [u64; 4] {
+    while t[4..] != [0; 4] {
+        let mut reduced = [t[0], t[1], t[2], t[3], 0, 0, 0, 0];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..3 {
+                (reduced[i + j], carry) = mac(reduced[i + j], t[4 + i], c[j], carry);
+            }
+            for limb in &mut reduced[i + 3..] {
+                (*limb, carry) = adc(*limb, 0, carry);
+            }
+        }
+        t = reduced;
+    }
+    canonicalize([t[0], t[1], t[2], t[3]], p)
+}
+
+/// Computes `a^e` by square-and-multiply.
+#[inline]
+pub(crate) fn exp(a: [u64; 4], e: [u64; 4], p: [u64; 4], c: [u64; 3]) -> [u64; 4] {
+    let mut result = canonicalize([1, 0, 0, 0], p);
+    for &limb in e.iter().rev() {
+        for i in (0..64).rev() {
+            result = mul(result, result, p, c);
+            if (limb >> i) & 1 == 1 {
+                result = mul(result, a, p, c);
+            }
+        }
+    }
+    result
+}
+
+/// Computes `a^-1` as `a^(p - 2)`, which is zero if `a` is zero.
+#[inline]
+pub(crate) fn inverse(a: [u64; 4], p: [u64; 4], c: [u64; 3]) -> [u64; 4] {
+    exp(a, sub_limbs(p, [2, 0, 0, 0]).0, p, c)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::{BigUint, One};
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    const SECP256K1_BASE_ORDER: [u64; 4] = [
+        0xFFFFFFFEFFFFFC2F,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+    ];
+    const SECP256K1_BASE_C: [u64; 3] = [0x1000003D1, 0, 0];
+
+    const SECP256K1_SCALAR_ORDER: [u64; 4] = [
+        0xBFD25E8CD0364141,
+        0xBAAEDCE6AF48A03B,
+        0xFFFFFFFFFFFFFFFE,
+        0xFFFFFFFFFFFFFFFF,
+    ];
+    const SECP256K1_SCALAR_C: [u64; 3] = [0x402DA1732FC9BEBF, 0x4551231950B75FC4, 1];
+
+    fn to_biguint(a: [u64; 4]) -> BigUint {
+        BigUint::from_slice(&a.map(|l| [l as u32, (l >> 32) as u32]).concat())
+    }
+
+    fn from_biguint(a: &BigUint) -> [u64; 4] {
+        let mut limbs = a.to_u64_digits();
+        limbs.resize(4, 0);
+        limbs.try_into().unwrap()
+    }
+
+    fn check_against_biguint(p: [u64; 4], c: [u64; 3]) {
+        let p_big = to_biguint(p);
+        assert_eq!(
+            (BigUint::one() << 256) - &p_big,
+            to_biguint([c[0], c[1], c[2], 0])
+        );
+
+        let mut rng = OsRng;
+        let edge_cases = [
+            [0; 4],
+            [1, 0, 0, 0],
+            sub_limbs(p, [1, 0, 0, 0]).0,
+            p,
+            [u64::MAX; 4],
+        ];
+        let random = (0..20).map(|_| from_biguint(&rng.gen_biguint(256)));
+        let values: Vec<[u64; 4]> = edge_cases.into_iter().chain(random).collect();
+
+        for &a in &values {
+            let a_big = to_biguint(a) % &p_big;
+            assert_eq!(to_biguint(canonicalize(a, p)), a_big);
+            assert_eq!(to_biguint(neg(a, p)), (&p_big - &a_big) % &p_big);
+            if a_big != BigUint::default() {
+                let inv = to_biguint(inverse(a, p, c));
+                assert_eq!(inv * &a_big % &p_big, BigUint::one());
+            }
+
+            for &b in &values {
+                let b_big = to_biguint(b) % &p_big;
+                assert_eq!(to_biguint(add(a, b, p)), (&a_big + &b_big) % &p_big);
+                assert_eq!(
+                    to_biguint(sub(a, b, p)),
+                    (&p_big + &a_big - &b_big) % &p_big
+                );
+                assert_eq!(to_biguint(mul(a, b, p, c)), &a_big * &b_big % &p_big);
+            }
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_base_against_biguint() {
+        check_against_biguint(SECP256K1_BASE_ORDER, SECP256K1_BASE_C);
+    }
+
+    #[test]
+    fn test_secp256k1_scalar_against_biguint() {
+        check_against_biguint(SECP256K1_SCALAR_ORDER, SECP256K1_SCALAR_C);
+    }
+}
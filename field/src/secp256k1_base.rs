@@ -6,9 +6,9 @@ use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAss
 
 use itertools::Itertools;
 use num::bigint::BigUint;
-use num::{Integer, One};
 use serde::{Deserialize, Serialize};
 
+use crate::pseudo_mersenne::{self, canonicalize};
 use crate::types::{Field, PrimeField, Sample};
 
 /// The base field of the secp256k1 elliptic curve.
@@ -17,9 +17,23 @@ use crate::types::{Field, PrimeField, Sample};
 /// ```ignore
 /// P = 2**256 - 2**32 - 2**9 - 2**8 - 2**7 - 2**6 - 2**4 - 1
 /// ```
+///
+/// Elements are represented by their little-endian 64-bit limbs, which may be non-canonical.
+/// Arithmetic is done with fixed limbs, using the special form of the order for reductions.
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Secp256K1Base(pub [u64; 4]);
 
+/// The order of the field, in little-endian 64-bit limbs.
+const ORDER: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+/// `2^256 - ORDER`, in little-endian 64-bit limbs.
+const ORDER_COMPLEMENT: [u64; 3] = [0x1000003D1, 0, 0];
+
 fn biguint_from_array(arr: [u64; 4]) -> BigUint {
     BigUint::from_slice(&[
         arr[0] as u32,
@@ -41,7 +55,7 @@ impl Default for Secp256K1Base {
 
 impl PartialEq for Secp256K1Base {
     fn eq(&self, other: &Self) -> bool {
-        self.to_canonical_biguint() == other.to_canonical_biguint()
+        canonicalize(self.0, ORDER) == canonicalize(other.0, ORDER)
     }
 }
 
@@ -49,7 +63,7 @@ impl Eq for Secp256K1Base {}
 
 impl Hash for Secp256K1Base {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.to_canonical_biguint().hash(state)
+        canonicalize(self.0, ORDER).hash(state)
     }
 }
 
@@ -99,10 +113,7 @@ impl Field for Secp256K1Base {
     const BITS: usize = 256;
 
     fn order() -> BigUint {
-        BigUint::from_slice(&[
-            0xFFFFFC2F, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
-            0xFFFFFFFF,
-        ])
+        biguint_from_array(ORDER)
     }
     fn characteristic() -> BigUint {
         Self::order()
@@ -114,7 +125,11 @@ impl Field for Secp256K1Base {
         }
 
         // Fermat's Little Theorem
-        Some(self.exp_biguint(&(Self::order() - BigUint::one() - BigUint::one())))
+        Some(Self(pseudo_mersenne::inverse(
+            self.0,
+            ORDER,
+            ORDER_COMPLEMENT,
+        )))
     }
 
     fn from_noncanonical_biguint(val: BigUint) -> Self {
@@ -146,11 +161,7 @@ impl Field for Secp256K1Base {
 
 impl PrimeField for Secp256K1Base {
     fn to_canonical_biguint(&self) -> BigUint {
-        let mut result = biguint_from_array(self.0);
-        if result >= Self::order() {
-            result -= Self::order();
-        }
-        result
+        biguint_from_array(canonicalize(self.0, ORDER))
     }
 }
 
@@ -159,11 +170,7 @@ impl Neg for Secp256K1Base {
 
     #[inline]
     fn neg(self) -> Self {
-        if self.is_zero() {
-            Self::ZERO
-        } else {
-            Self::from_noncanonical_biguint(Self::order() - self.to_canonical_biguint())
-        }
+        Self(pseudo_mersenne::neg(self.0, ORDER))
     }
 }
 
@@ -172,11 +179,7 @@ impl Add for Secp256K1Base {
 
     #[inline]
     fn add(self, rhs: Self) -> Self {
-        let mut result = self.to_canonical_biguint() + rhs.to_canonical_biguint();
-        if result >= Self::order() {
-            result -= Self::order();
-        }
-        Self::from_noncanonical_biguint(result)
+        Self(pseudo_mersenne::add(self.0, rhs.0, ORDER))
     }
 }
 
@@ -197,9 +200,8 @@ impl Sub for Secp256K1Base {
     type Output = Self;
 
     #[inline]
-    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Self) -> Self {
-        self + -rhs
+        Self(pseudo_mersenne::sub(self.0, rhs.0, ORDER))
     }
 }
 
@@ -215,9 +217,7 @@ impl Mul for Secp256K1Base {
 
     #[inline]
     fn mul(self, rhs: Self) -> Self {
-        Self::from_noncanonical_biguint(
-            (self.to_canonical_biguint() * rhs.to_canonical_biguint()).mod_floor(&Self::order()),
-        )
+        Self(pseudo_mersenne::mul(self.0, rhs.0, ORDER, ORDER_COMPLEMENT))
     }
 }
 
@@ -6,9 +6,9 @@ use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAss
 
 use itertools::Itertools;
 use num::bigint::BigUint;
-use num::{Integer, One};
 use serde::{Deserialize, Serialize};
 
+use crate::pseudo_mersenne::{self, canonicalize};
 use crate::types::{Field, PrimeField, Sample};
 
 /// The base field of the secp256k1 elliptic curve.
@@ -19,9 +19,23 @@ use crate::types::{Field, PrimeField, Sample};
 ///   = 115792089237316195423570985008687907852837564279074904382605163141518161494337
 ///   = 2**256 - 432420386565659656852420866394968145599
 /// ```
+///
+/// Elements are represented by their little-endian 64-bit limbs, which may be non-canonical.
+/// Arithmetic is done with fixed limbs, using the special form of the order for reductions.
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Secp256K1Scalar(pub [u64; 4]);
 
+/// The order of the field, in little-endian 64-bit limbs.
+const ORDER: [u64; 4] = [
+    0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+/// `2^256 - ORDER`, in little-endian 64-bit limbs.
+const ORDER_COMPLEMENT: [u64; 3] = [0x402DA1732FC9BEBF, 0x4551231950B75FC4, 1];
+
 fn biguint_from_array(arr: [u64; 4]) -> BigUint {
     BigUint::from_slice(&[
         arr[0] as u32,
@@ -43,7 +57,7 @@ impl Default for Secp256K1Scalar {
 
 impl PartialEq for Secp256K1Scalar {
     fn eq(&self, other: &Self) -> bool {
-        self.to_canonical_biguint() == other.to_canonical_biguint()
+        canonicalize(self.0, ORDER) == canonicalize(other.0, ORDER)
     }
 }
 
@@ -51,7 +65,7 @@ impl Eq for Secp256K1Scalar {}
 
 impl Hash for Secp256K1Scalar {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.to_canonical_biguint().hash(state)
+        canonicalize(self.0, ORDER).hash(state)
     }
 }
 
@@ -107,10 +121,7 @@ impl Field for Secp256K1Scalar {
     const BITS: usize = 256;
 
     fn order() -> BigUint {
-        BigUint::from_slice(&[
-            0xD0364141, 0xBFD25E8C, 0xAF48A03B, 0xBAAEDCE6, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
-            0xFFFFFFFF,
-        ])
+        biguint_from_array(ORDER)
     }
     fn characteristic() -> BigUint {
         Self::order()
@@ -122,7 +133,11 @@ impl Field for Secp256K1Scalar {
         }
 
         // Fermat's Little Theorem
-        Some(self.exp_biguint(&(Self::order() - BigUint::one() - BigUint::one())))
+        Some(Self(pseudo_mersenne::inverse(
+            self.0,
+            ORDER,
+            ORDER_COMPLEMENT,
+        )))
     }
 
     fn from_noncanonical_biguint(val: BigUint) -> Self {
@@ -154,11 +169,7 @@ impl Field for Secp256K1Scalar {
 
 impl PrimeField for Secp256K1Scalar {
     fn to_canonical_biguint(&self) -> BigUint {
-        let mut result = biguint_from_array(self.0);
-        if result >= Self::order() {
-            result -= Self::order();
-        }
-        result
+        biguint_from_array(canonicalize(self.0, ORDER))
     }
 }
 
@@ -167,11 +178,7 @@ impl Neg for Secp256K1Scalar {
 
     #[inline]
     fn neg(self) -> Self {
-        if self.is_zero() {
-            Self::ZERO
-        } else {
-            Self::from_noncanonical_biguint(Self::order() - self.to_canonical_biguint())
-        }
+        Self(pseudo_mersenne::neg(self.0, ORDER))
     }
 }
 
@@ -180,11 +187,7 @@ impl Add for Secp256K1Scalar {
 
     #[inline]
     fn add(self, rhs: Self) -> Self {
-        let mut result = self.to_canonical_biguint() + rhs.to_canonical_biguint();
-        if result >= Self::order() {
-            result -= Self::order();
-        }
-        Self::from_noncanonical_biguint(result)
+        Self(pseudo_mersenne::add(self.0, rhs.0, ORDER))
     }
 }
 
@@ -205,9 +208,8 @@ impl Sub for Secp256K1Scalar {
     type Output = Self;
 
     #[inline]
-    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Self) -> Self {
-        self + -rhs
+        Self(pseudo_mersenne::sub(self.0, rhs.0, ORDER))
     }
 }
 
@@ -223,9 +225,7 @@ impl Mul for Secp256K1Scalar {
 
     #[inline]
     fn mul(self, rhs: Self) -> Self {
-        Self::from_noncanonical_biguint(
-            (self.to_canonical_biguint() * rhs.to_canonical_biguint()).mod_floor(&Self::order()),
-        )
+        Self(pseudo_mersenne::mul(self.0, rhs.0, ORDER, ORDER_COMPLEMENT))
     }
 }
 
@@ -99,7 +99,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     #[test]
@@ -280,3 +280,118 @@ where
 {
     (oper_a(), oper_b())
 }
+
+/// A pool of worker threads, which lets a computation run with fewer threads than the global
+/// pool. Without the `parallel` feature, everything runs on the calling thread.
+#[cfg(feature = "parallel")]
+#[derive(Debug)]
+pub struct ThreadPool(rayon::ThreadPool);
+
+/// A pool of worker threads, which lets a computation run with fewer threads than the global
+/// pool. Without the `parallel` feature, everything runs on the calling thread.
+#[cfg(not(feature = "parallel"))]
+#[derive(Debug)]
+pub struct ThreadPool(());
+
+#[cfg(feature = "parallel")]
+pub use rayon::ThreadPoolBuildError;
+
+/// The error returned when a `ThreadPool` can't be created. It is never returned without the
+/// `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+#[derive(Debug)]
+pub struct ThreadPoolBuildError(());
+
+#[cfg(not(feature = "parallel"))]
+impl core::fmt::Display for ThreadPoolBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "failed to build the thread pool")
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl std::error::Error for ThreadPoolBuildError {}
+
+#[cfg(feature = "parallel")]
+impl ThreadPool {
+    /// Creates a pool with `num_threads` threads. If `num_threads` is zero, rayon picks the
+    /// number of threads, as for the global pool.
+    pub fn new(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map(Self)
+    }
+
+    /// Runs `op` in the pool, so that parallel iterators and `join` within it use the threads of
+    /// the pool.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        self.0.install(op)
+    }
+
+    pub fn current_num_threads(&self) -> usize {
+        self.0.current_num_threads()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl ThreadPool {
+    /// Creates a pool with `num_threads` threads. If `num_threads` is zero, rayon picks the
+    /// number of threads, as for the global pool.
+    pub fn new(_num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        Ok(Self(()))
+    }
+
+    /// Runs `op` in the pool, so that parallel iterators and `join` within it use the threads of
+    /// the pool. The bounds match those with the `parallel` feature, so that code using this
+    /// compiles either way.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        op()
+    }
+
+    pub fn current_num_threads(&self) -> usize {
+        1
+    }
+}
+
+/// Returns the number of threads of the pool the caller is running in, or of the global pool.
+#[cfg(feature = "parallel")]
+pub fn current_num_threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Returns the number of threads of the pool the caller is running in, or of the global pool.
+#[cfg(not(feature = "parallel"))]
+pub fn current_num_threads() -> usize {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_pool() {
+        let pool = ThreadPool::new(2).unwrap();
+        let expected_threads = if cfg!(feature = "parallel") { 2 } else { 1 };
+        assert_eq!(pool.current_num_threads(), expected_threads);
+
+        let (num_threads, sum) = pool.install(|| {
+            let v = (0..1000u64).collect::<Vec<_>>();
+            (
+                current_num_threads(),
+                MaybeParIter::par_iter(&v).sum::<u64>(),
+            )
+        });
+        assert_eq!(num_threads, expected_threads);
+        assert_eq!(sum, 999 * 1000 / 2);
+    }
+}
@@ -1,6 +1,7 @@
 mod allocator;
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use plonky2::field::fft::{fft_root_table, fft_with_options, FftStrategy};
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::field::polynomial::PolynomialCoeffs;
 use plonky2::field::types::Field;
@@ -18,6 +19,22 @@ pub(crate) fn bench_ffts<F: Field>(c: &mut Criterion) {
     }
 }
 
+pub(crate) fn bench_fft_strategies<F: Field>(c: &mut Criterion) {
+    for strategy in [FftStrategy::Classic, FftStrategy::Transpose] {
+        let mut group = c.benchmark_group(format!("fft-{:?}<{}>", strategy, type_name::<F>()));
+        group.sample_size(10);
+
+        for size_log in [16, 18, 20, 22, 24] {
+            let size = 1 << size_log;
+            let root_table = fft_root_table(size);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+                let coeffs = PolynomialCoeffs::new(F::rand_vec(size));
+                b.iter(|| fft_with_options(coeffs.clone(), None, Some(&root_table), strategy));
+            });
+        }
+    }
+}
+
 pub(crate) fn bench_ldes<F: Field>(c: &mut Criterion) {
     const RATE_BITS: usize = 3;
 
@@ -39,6 +56,7 @@ pub(crate) fn bench_ldes<F: Field>(c: &mut Criterion) {
 
 fn criterion_benchmark(c: &mut Criterion) {
     bench_ffts::<GoldilocksField>(c);
+    bench_fft_strategies::<GoldilocksField>(c);
     bench_ldes::<GoldilocksField>(c);
 }
 
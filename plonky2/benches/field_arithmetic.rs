@@ -1,10 +1,14 @@
 mod allocator;
 
 use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use plonky2::field::bn254_base::Bn254Base;
+use plonky2::field::bn254_scalar::Bn254Scalar;
 use plonky2::field::extension::quadratic::QuadraticExtension;
 use plonky2::field::extension::quartic::QuarticExtension;
 use plonky2::field::extension::quintic::QuinticExtension;
 use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::secp256k1_base::Secp256K1Base;
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
 use plonky2::field::types::Field;
 use tynm::type_name;
 
@@ -181,6 +185,10 @@ fn criterion_benchmark(c: &mut Criterion) {
     bench_field::<QuadraticExtension<GoldilocksField>>(c);
     bench_field::<QuarticExtension<GoldilocksField>>(c);
     bench_field::<QuinticExtension<GoldilocksField>>(c);
+    bench_field::<Secp256K1Base>(c);
+    bench_field::<Secp256K1Scalar>(c);
+    bench_field::<Bn254Base>(c);
+    bench_field::<Bn254Scalar>(c);
 }
 
 criterion_group!(benches, criterion_benchmark);
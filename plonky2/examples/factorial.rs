@@ -39,5 +39,5 @@ fn main() -> Result<()> {
         proof.public_inputs[0], proof.public_inputs[1]
     );
 
-    data.verify(proof)
+    Ok(data.verify(proof)?)
 }
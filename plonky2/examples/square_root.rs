@@ -77,5 +77,5 @@ fn main() -> Result<()> {
     let x_squared_actual = proof.public_inputs[0];
     println!("Field element (square): {x_squared_actual}");
 
-    data.verify(proof)
+    Ok(data.verify(proof)?)
 }
@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use plonky2::plonk::proof::CompressedProofWithPublicInputs;
+use plonky2_fuzz::{C, CIRCUIT, D, F};
+
+fuzz_target!(|data: &[u8]| {
+    let circuit = &*CIRCUIT;
+    // Decoding must never panic, and neither may decompressing or verifying what it accepts.
+    if let Ok(proof) =
+        CompressedProofWithPublicInputs::<F, C, D>::from_bytes(data.to_vec(), &circuit.common)
+    {
+        let _ = circuit.decompress(proof.clone());
+        let _ = circuit.verify_compressed(proof);
+    }
+});
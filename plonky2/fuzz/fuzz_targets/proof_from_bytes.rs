@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+use plonky2_fuzz::{C, CIRCUIT, D, F};
+
+fuzz_target!(|data: &[u8]| {
+    let circuit = &*CIRCUIT;
+    // Decoding must never panic, and anything it accepts must be safe to verify.
+    if let Ok(proof) = ProofWithPublicInputs::<F, C, D>::from_bytes(data.to_vec(), &circuit.common)
+    {
+        let _ = circuit.verify(proof);
+    }
+});
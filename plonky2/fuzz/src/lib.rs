@@ -0,0 +1,22 @@
+//! Shared setup for the proof decoding fuzz targets. Run them from the `plonky2` directory with
+//! e.g. `cargo +nightly fuzz run proof_from_bytes`.
+
+use once_cell::sync::Lazy;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+pub const D: usize = 2;
+pub type C = PoseidonGoldilocksConfig;
+pub type F = <C as GenericConfig<D>>::F;
+
+/// A small circuit proving knowledge of `x` such that `x^3 + x = y`, with `y` public.
+pub static CIRCUIT: Lazy<CircuitData<F, C, D>> = Lazy::new(|| {
+    let config = CircuitConfig::standard_recursion_config();
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let x = builder.add_virtual_target();
+    let x2 = builder.mul(x, x);
+    let y = builder.mul_add(x2, x, x);
+    builder.register_public_input(y);
+    builder.build::<C>()
+});
@@ -4,12 +4,13 @@ use crate::fri::reduction_strategies::FriReductionStrategy;
 
 mod challenges;
 pub mod oracle;
+pub mod pcs;
 pub mod proof;
 pub mod prover;
 pub mod recursive_verifier;
 pub mod reduction_strategies;
 pub mod structure;
-mod validate_shape;
+pub(crate) mod validate_shape;
 pub mod verifier;
 pub mod witness_util;
 
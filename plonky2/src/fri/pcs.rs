@@ -0,0 +1,468 @@
+//! A polynomial commitment scheme built from FRI, for IOPs other than Plonk. It commits to
+//! batches of polynomials with `PolynomialBatch`, and proves the values of every committed
+//! polynomial at a list of arbitrary points with a single FRI proof.
+//!
+//! The opening points must be sampled from the transcript after the caps of all batches have
+//! been observed, as `plonk::prover` does for `zeta`.
+
+use alloc::vec::Vec;
+
+use itertools::Itertools;
+use maybe_rayon::*;
+use serde::{Deserialize, Serialize};
+
+use crate::field::extension::Extendable;
+use crate::field::polynomial::{PolynomialCoeffs, PolynomialValues};
+use crate::fri::oracle::PolynomialBatch;
+use crate::fri::proof::{FriProof, FriProofTarget};
+use crate::fri::structure::{
+    FriBatchInfo, FriBatchInfoTarget, FriInstanceInfo, FriInstanceInfoTarget, FriOpeningBatch,
+    FriOpeningBatchTarget, FriOpenings, FriOpeningsTarget, FriOracleInfo, FriPolynomialInfo,
+};
+use crate::fri::verifier::verify_fri_proof;
+use crate::fri::{FriConfig, FriParams};
+use crate::hash::hash_types::{MerkleCapTarget, RichField};
+use crate::hash::merkle_tree::MerkleCap;
+use crate::iop::challenger::{Challenger, RecursiveChallenger};
+use crate::iop::ext_target::ExtensionTarget;
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::plonk::config::{AlgebraicHasher, GenericConfig, Hasher};
+use crate::plonk::plonk_common::salt_size;
+use crate::plonk::verification_error::{ensure_shape, VerificationError};
+use crate::util::timing::TimingTree;
+
+/// A FRI-based polynomial commitment scheme for polynomials of degree less than
+/// `2^params.degree_bits`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FriPcs {
+    pub params: FriParams,
+}
+
+/// The values of the committed polynomials at the opening points, along with a FRI proof of
+/// their correctness.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(bound = "")]
+pub struct FriPcsProof<F: RichField + Extendable<D>, H: Hasher<F>, const D: usize> {
+    /// `values[i]` holds the values at the `i`th point of every polynomial of every batch, in the
+    /// order in which the batches were given.
+    pub values: Vec<Vec<F::Extension>>,
+    pub fri_proof: FriProof<F, H, D>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FriPcsProofTarget<const D: usize> {
+    pub values: Vec<Vec<ExtensionTarget<D>>>,
+    pub fri_proof: FriProofTarget<D>,
+}
+
+impl FriPcs {
+    /// If `hiding` is set, the Merkle leaves of every batch are salted.
+    pub fn new(config: &FriConfig, degree_bits: usize, hiding: bool) -> Self {
+        Self {
+            params: config.fri_params(degree_bits, hiding),
+        }
+    }
+
+    /// Commits to `polynomials`, each of which must have `2^degree_bits` coefficients.
+    pub fn commit<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+        &self,
+        polynomials: Vec<PolynomialCoeffs<F>>,
+        timing: &mut TimingTree,
+    ) -> PolynomialBatch<F, C, D> {
+        assert!(!polynomials.is_empty(), "No polynomials to commit to");
+        assert_eq!(polynomials[0].len(), 1 << self.params.degree_bits);
+        PolynomialBatch::from_coeffs(
+            polynomials,
+            self.params.config.rate_bits,
+            self.params.hiding,
+            self.params.config.cap_height,
+            timing,
+            None,
+        )
+    }
+
+    /// Commits to the polynomials taking the given values on the subgroup of order
+    /// `2^degree_bits`.
+    pub fn commit_values<
+        F: RichField + Extendable<D>,
+        C: GenericConfig<D, F = F>,
+        const D: usize,
+    >(
+        &self,
+        values: Vec<PolynomialValues<F>>,
+        timing: &mut TimingTree,
+    ) -> PolynomialBatch<F, C, D> {
+        let polynomials = values.into_par_iter().map(|v| v.ifft()).collect();
+        self.commit(polynomials, timing)
+    }
+
+    /// Opens every polynomial of `batches` at each of `points`. The points must not be in the
+    /// LDE domain, which happens with negligible probability when they are sampled from the
+    /// extension field.
+    pub fn open<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+        &self,
+        batches: &[&PolynomialBatch<F, C, D>],
+        points: &[F::Extension],
+        challenger: &mut Challenger<F, C::Hasher>,
+        timing: &mut TimingTree,
+    ) -> FriPcsProof<F, C::Hasher, D> {
+        for batch in batches {
+            assert_eq!(batch.degree_log, self.params.degree_bits);
+            assert_eq!(batch.blinding, self.params.hiding);
+        }
+
+        let values = points
+            .iter()
+            .map(|&z| {
+                batches
+                    .iter()
+                    .flat_map(|batch| {
+                        batch
+                            .polynomials
+                            .par_iter()
+                            .map(|p| p.to_extension().eval(z))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let openings = FriOpenings {
+            batches: values
+                .iter()
+                .map(|values| FriOpeningBatch {
+                    values: values.clone(),
+                })
+                .collect(),
+        };
+        challenger.observe_openings(&openings);
+
+        let num_polys = batches.iter().map(|b| b.polynomials.len()).collect_vec();
+        let fri_proof = PolynomialBatch::prove_openings(
+            &self.instance(&num_polys, points),
+            batches,
+            challenger,
+            &self.params,
+            timing,
+        );
+        FriPcsProof { values, fri_proof }
+    }
+
+    /// Verifies that `proof` opens the batches committed to by `caps`, holding `num_polys[i]`
+    /// polynomials for each cap `i`, at `points`.
+    pub fn verify<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+        &self,
+        caps: &[MerkleCap<F, C::Hasher>],
+        num_polys: &[usize],
+        points: &[F::Extension],
+        proof: &FriPcsProof<F, C::Hasher, D>,
+        challenger: &mut Challenger<F, C::Hasher>,
+    ) -> Result<(), VerificationError> {
+        assert_eq!(caps.len(), num_polys.len());
+        let total_polys = num_polys.iter().sum::<usize>();
+        ensure_shape(
+            proof.values.len() == points.len()
+                && proof.values.iter().all(|v| v.len() == total_polys),
+            "opened values",
+        )?;
+
+        let openings = FriOpenings {
+            batches: proof
+                .values
+                .iter()
+                .map(|values| FriOpeningBatch {
+                    values: values.clone(),
+                })
+                .collect(),
+        };
+        challenger.observe_openings(&openings);
+        let challenges = challenger.fri_challenges::<C, D>(
+            &proof.fri_proof.commit_phase_merkle_caps,
+            &proof.fri_proof.final_poly,
+            proof.fri_proof.pow_witness,
+            self.params.degree_bits,
+            &self.params.config,
+        );
+
+        verify_fri_proof::<F, C, D>(
+            &self.instance(num_polys, points),
+            &openings,
+            &challenges,
+            caps,
+            &proof.fri_proof,
+            &self.params,
+        )
+    }
+
+    fn oracles(&self, num_polys: &[usize]) -> Vec<FriOracleInfo> {
+        num_polys
+            .iter()
+            .map(|&num_polys| FriOracleInfo {
+                num_polys,
+                blinding: self.params.hiding,
+            })
+            .collect()
+    }
+
+    fn all_polynomials(num_polys: &[usize]) -> Vec<FriPolynomialInfo> {
+        num_polys
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &n)| FriPolynomialInfo::from_range(i, 0..n))
+            .collect()
+    }
+
+    fn instance<F: RichField + Extendable<D>, const D: usize>(
+        &self,
+        num_polys: &[usize],
+        points: &[F::Extension],
+    ) -> FriInstanceInfo<F, D> {
+        let polynomials = Self::all_polynomials(num_polys);
+        FriInstanceInfo {
+            oracles: self.oracles(num_polys),
+            batches: points
+                .iter()
+                .map(|&point| FriBatchInfo {
+                    point,
+                    polynomials: polynomials.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn instance_target<const D: usize>(
+        &self,
+        num_polys: &[usize],
+        points: &[ExtensionTarget<D>],
+    ) -> FriInstanceInfoTarget<D> {
+        let polynomials = Self::all_polynomials(num_polys);
+        FriInstanceInfoTarget {
+            oracles: self.oracles(num_polys),
+            batches: points
+                .iter()
+                .map(|&point| FriBatchInfoTarget {
+                    point,
+                    polynomials: polynomials.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    /// Adds targets for a `FriPcsProof` opening batches of `num_polys[i]` polynomials at
+    /// `num_points` points.
+    pub fn add_virtual_fri_pcs_proof(
+        &mut self,
+        pcs: &FriPcs,
+        num_polys: &[usize],
+        num_points: usize,
+    ) -> FriPcsProofTarget<D> {
+        let total_polys = num_polys.iter().sum();
+        let values = (0..num_points)
+            .map(|_| self.add_virtual_extension_targets(total_polys))
+            .collect();
+        let num_leaves_per_oracle = num_polys
+            .iter()
+            .map(|&n| n + salt_size(pcs.params.hiding))
+            .collect_vec();
+        let fri_proof = self.add_virtual_fri_proof(&num_leaves_per_oracle, &pcs.params);
+        FriPcsProofTarget { values, fri_proof }
+    }
+
+    /// In-circuit version of `FriPcs::verify`.
+    pub fn verify_fri_pcs_proof<C: GenericConfig<D, F = F>>(
+        &mut self,
+        pcs: &FriPcs,
+        caps: &[MerkleCapTarget],
+        num_polys: &[usize],
+        points: &[ExtensionTarget<D>],
+        proof: &FriPcsProofTarget<D>,
+        challenger: &mut RecursiveChallenger<F, C::Hasher, D>,
+    ) where
+        C::Hasher: AlgebraicHasher<F>,
+    {
+        assert_eq!(caps.len(), num_polys.len());
+        assert_eq!(proof.values.len(), points.len());
+
+        let openings = FriOpeningsTarget {
+            batches: proof
+                .values
+                .iter()
+                .map(|values| FriOpeningBatchTarget {
+                    values: values.clone(),
+                })
+                .collect(),
+        };
+        challenger.observe_openings(&openings);
+        let challenges = challenger.fri_challenges::<C>(
+            self,
+            &proof.fri_proof.commit_phase_merkle_caps,
+            &proof.fri_proof.final_poly,
+            proof.fri_proof.pow_witness,
+            &pcs.params.config,
+        );
+
+        self.verify_fri_proof::<C>(
+            &pcs.instance_target(num_polys, points),
+            &openings,
+            &challenges,
+            caps,
+            &proof.fri_proof,
+            &pcs.params,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::types::Field;
+    use crate::fri::reduction_strategies::FriReductionStrategy;
+    use crate::fri::witness_util::set_fri_pcs_proof_target;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::PoseidonGoldilocksConfig;
+
+    fn test_config() -> FriConfig {
+        FriConfig {
+            rate_bits: 2,
+            cap_height: 2,
+            proof_of_work_bits: 4,
+            reduction_strategy: FriReductionStrategy::ConstantArityBits(2, 3),
+            num_query_rounds: 20,
+        }
+    }
+
+    /// The caps of the committed batches, the opening points and the opening proof.
+    type Opened<F, C, const D: usize> = (
+        Vec<MerkleCap<F, <C as GenericConfig<D>>::Hasher>>,
+        Vec<<F as Extendable<D>>::Extension>,
+        FriPcsProof<F, <C as GenericConfig<D>>::Hasher, D>,
+    );
+
+    fn prove_batches<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+        pcs: &FriPcs,
+        num_polys: &[usize],
+        num_points: usize,
+    ) -> Opened<F, C, D> {
+        let mut timing = TimingTree::default();
+        let polys = num_polys
+            .iter()
+            .map(|&n| {
+                (0..n)
+                    .map(|_| PolynomialCoeffs::new(F::rand_vec(1 << pcs.params.degree_bits)))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let batches = polys
+            .iter()
+            .map(|polys| pcs.commit::<F, C, D>(polys.clone(), &mut timing))
+            .collect_vec();
+        let caps = batches
+            .iter()
+            .map(|b| b.merkle_tree.cap.clone())
+            .collect_vec();
+
+        let mut challenger = Challenger::<F, C::Hasher>::new();
+        for cap in &caps {
+            challenger.observe_cap(cap);
+        }
+        let points = challenger.get_n_extension_challenges::<D>(num_points);
+        let proof = pcs.open(
+            &batches.iter().collect_vec(),
+            &points,
+            &mut challenger,
+            &mut timing,
+        );
+
+        let expected = points
+            .iter()
+            .map(|&z| {
+                polys
+                    .iter()
+                    .flatten()
+                    .map(|p| p.to_extension().eval(z))
+                    .collect_vec()
+            })
+            .collect_vec();
+        assert_eq!(proof.values, expected);
+        (caps, points, proof)
+    }
+
+    #[test]
+    fn test_fri_pcs() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        for hiding in [false, true] {
+            let pcs = FriPcs::new(&test_config(), 8, hiding);
+            let num_polys = [3, 1, 5];
+            let (caps, points, proof) = prove_batches::<F, C, D>(&pcs, &num_polys, 3);
+
+            let mut challenger = Challenger::<F, <C as GenericConfig<D>>::Hasher>::new();
+            for cap in &caps {
+                challenger.observe_cap(cap);
+            }
+            assert_eq!(challenger.get_n_extension_challenges::<D>(3), points);
+            pcs.verify::<F, C, D>(&caps, &num_polys, &points, &proof, &mut challenger)?;
+
+            let mut bad_proof = proof.clone();
+            bad_proof.values[1][2] += <F as Extendable<D>>::Extension::ONE;
+            let mut challenger = Challenger::<F, <C as GenericConfig<D>>::Hasher>::new();
+            for cap in &caps {
+                challenger.observe_cap(cap);
+            }
+            challenger.get_n_extension_challenges::<D>(3);
+            assert!(pcs
+                .verify::<F, C, D>(&caps, &num_polys, &points, &bad_proof, &mut challenger)
+                .is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fri_pcs_circuit() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let pcs = FriPcs::new(&test_config(), 8, false);
+        let num_polys = [2, 4];
+        let (caps, points, proof) = prove_batches::<F, C, D>(&pcs, &num_polys, 2);
+
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        let caps_t = caps
+            .iter()
+            .map(|_| builder.add_virtual_cap(pcs.params.config.cap_height))
+            .collect_vec();
+        let proof_t = builder.add_virtual_fri_pcs_proof(&pcs, &num_polys, points.len());
+        let mut challenger =
+            RecursiveChallenger::<F, <C as GenericConfig<D>>::Hasher, D>::new(&mut builder);
+        for cap in &caps_t {
+            challenger.observe_cap(cap);
+        }
+        let points_t = (0..points.len())
+            .map(|_| challenger.get_extension_challenge(&mut builder))
+            .collect_vec();
+        builder.verify_fri_pcs_proof::<C>(
+            &pcs,
+            &caps_t,
+            &num_polys,
+            &points_t,
+            &proof_t,
+            &mut challenger,
+        );
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (cap_t, cap) in caps_t.iter().zip(&caps) {
+            pw.set_cap_target(cap_t, cap);
+        }
+        set_fri_pcs_proof_target(&mut pw, &proof_t, &proof);
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+}
@@ -18,6 +18,7 @@ use crate::iop::target::Target;
 use crate::plonk::config::{GenericConfig, Hasher};
 use crate::plonk::plonk_common::salt_size;
 use crate::plonk::proof::{FriInferredElements, ProofChallenges};
+use crate::plonk::verification_error::VerificationError;
 
 /// Evaluations and Merkle proof produced by the prover in a FRI query step.
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -246,7 +247,7 @@ impl<F: RichField + Extendable<D>, H: Hasher<F>, const D: usize> CompressedFriPr
         challenges: &ProofChallenges<F, D>,
         fri_inferred_elements: FriInferredElements<F, D>,
         params: &FriParams,
-    ) -> FriProof<F, H, D> {
+    ) -> Result<FriProof<F, H, D>, VerificationError> {
         let CompressedFriProof {
             commit_phase_merkle_caps,
             query_round_proofs,
@@ -326,10 +327,16 @@ impl<F: RichField + Extendable<D>, H: Hasher<F>, const D: usize> CompressedFriPr
             initial_trees_proofs
         )
         .map(|(ls, is, ps)| decompress_merkle_proofs(ls, is, &ps, height, cap_height))
-        .collect::<Vec<_>>();
+        .collect::<Option<Vec<_>>>()
+        .ok_or(VerificationError::ShapeMismatch(
+            "compressed initial Merkle proofs",
+        ))?;
         let steps_proofs = izip!(&steps_evals, &steps_indices, steps_proofs, heights)
             .map(|(ls, is, ps, h)| decompress_merkle_proofs(ls, is, &ps, h, cap_height))
-            .collect::<Vec<_>>();
+            .collect::<Option<Vec<_>>>()
+            .ok_or(VerificationError::ShapeMismatch(
+                "compressed FRI step Merkle proofs",
+            ))?;
 
         let mut decompressed_query_proofs = Vec::with_capacity(num_reductions);
         for i in 0..indices.len() {
@@ -355,12 +362,12 @@ impl<F: RichField + Extendable<D>, H: Hasher<F>, const D: usize> CompressedFriPr
             })
         }
 
-        FriProof {
+        Ok(FriProof {
             commit_phase_merkle_caps,
             query_round_proofs: decompressed_query_proofs,
             final_poly,
             pow_witness,
-        }
+        })
     }
 }
 
@@ -1,9 +1,14 @@
+use alloc::vec;
 use alloc::vec::Vec;
 
 use maybe_rayon::*;
 
+use crate::field::extension::packed::PackedExtension;
 use crate::field::extension::{flatten, unflatten, Extendable};
+use crate::field::packable::Packable;
+use crate::field::packed::PackedField;
 use crate::field::polynomial::{PolynomialCoeffs, PolynomialValues};
+use crate::field::types::Field;
 use crate::fri::proof::{FriInitialTreeProof, FriProof, FriQueryRound, FriQueryStep};
 use crate::fri::{FriConfig, FriParams};
 use crate::hash::hash_types::RichField;
@@ -91,13 +96,7 @@ fn fri_committed_trees<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>,
 
         let beta = challenger.get_extension_challenge::<D>();
         // P(x) = sum_{i<r} x^i * P_i(x^r) becomes sum_{i<r} beta^i * P_i(x).
-        coeffs = PolynomialCoeffs::new(
-            coeffs
-                .coeffs
-                .par_chunks_exact(arity)
-                .map(|chunk| reduce_with_powers(chunk, beta))
-                .collect::<Vec<_>>(),
-        );
+        coeffs = PolynomialCoeffs::new(fold_coeffs::<F, D>(&coeffs.coeffs, arity, beta));
         shift = shift.exp_u64(arity as u64);
         values = coeffs.coset_fft(shift.into())
     }
@@ -111,6 +110,44 @@ fn fri_committed_trees<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>,
     (trees, coeffs)
 }
 
+/// Maps each chunk of `arity` coefficients to `sum_i beta^i chunk_i`. If the base field has a
+/// SIMD packing, chunks are processed `WIDTH` at a time using packed extension arithmetic.
+fn fold_coeffs<F: RichField + Extendable<D>, const D: usize>(
+    coeffs: &[F::Extension],
+    arity: usize,
+    beta: F::Extension,
+) -> Vec<F::Extension> {
+    type P<F> = <F as Packable>::Packing;
+    let width = P::<F>::WIDTH;
+    if width == 1 {
+        return coeffs
+            .par_chunks_exact(arity)
+            .map(|chunk| reduce_with_powers(chunk, beta))
+            .collect();
+    }
+    let beta_packed = PackedExtension::<P<F>, D>::broadcast(beta);
+
+    let mut folded = vec![F::Extension::ZERO; coeffs.len() / arity];
+    folded
+        .par_chunks_mut(width)
+        .zip(coeffs.par_chunks(width * arity))
+        .for_each(|(out, chunks)| {
+            if out.len() == width {
+                let mut sum = PackedExtension::<P<F>, D>::ZEROS;
+                for i in (0..arity).rev() {
+                    let terms = PackedExtension::from_fn(|lane| chunks[lane * arity + i]);
+                    sum = sum * beta_packed + terms;
+                }
+                sum.unpack(out);
+            } else {
+                for (x, chunk) in out.iter_mut().zip(chunks.chunks_exact(arity)) {
+                    *x = reduce_with_powers(chunk, beta);
+                }
+            }
+        });
+    folded
+}
+
 /// Performs the proof-of-work (a.k.a. grinding) step of the FRI protocol. Returns the PoW witness.
 fn fri_proof_of_work<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
     challenger: &mut Challenger<F, C::Hasher>,
@@ -217,3 +254,26 @@ fn fri_prover_query_round<
         steps: query_steps,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::goldilocks_field::GoldilocksField;
+    use crate::field::types::Sample;
+
+    #[test]
+    fn test_fold_coeffs() {
+        type F = GoldilocksField;
+        const D: usize = 2;
+
+        let beta = <F as Extendable<D>>::Extension::rand();
+        for (len, arity) in [(2, 2), (8, 8), (24, 2), (1 << 10, 16)] {
+            let coeffs = <F as Extendable<D>>::Extension::rand_vec(len);
+            let expected = coeffs
+                .chunks(arity)
+                .map(|chunk| reduce_with_powers(chunk, beta))
+                .collect::<Vec<_>>();
+            assert_eq!(fold_coeffs::<F, D>(&coeffs, arity, beta), expected);
+        }
+    }
+}
@@ -1,18 +1,20 @@
-use anyhow::ensure;
-
 use crate::field::extension::Extendable;
-use crate::fri::proof::{FriProof, FriQueryRound, FriQueryStep};
-use crate::fri::structure::FriInstanceInfo;
+use crate::fri::proof::{
+    CompressedFriProof, CompressedFriQueryRounds, FriInitialTreeProof, FriProof, FriQueryRound,
+    FriQueryStep,
+};
+use crate::fri::structure::FriOracleInfo;
 use crate::fri::FriParams;
 use crate::hash::hash_types::RichField;
-use crate::plonk::config::GenericConfig;
+use crate::plonk::config::{GenericConfig, Hasher};
 use crate::plonk::plonk_common::salt_size;
+use crate::plonk::verification_error::{ensure_shape, VerificationError};
 
 pub(crate) fn validate_fri_proof_shape<F, C, const D: usize>(
     proof: &FriProof<F, C::Hasher, D>,
-    instance: &FriInstanceInfo<F, D>,
+    oracles: &[FriOracleInfo],
     params: &FriParams,
-) -> anyhow::Result<()>
+) -> Result<(), VerificationError>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
@@ -25,43 +27,201 @@ where
     } = proof;
 
     let cap_height = params.config.cap_height;
+    ensure_shape(
+        commit_phase_merkle_caps.len() == params.reduction_arity_bits.len(),
+        "number of FRI commit phase caps",
+    )?;
     for cap in commit_phase_merkle_caps {
-        ensure!(cap.height() == cap_height);
+        ensure_shape(cap.len() == 1 << cap_height, "FRI commit phase cap height")?;
     }
 
+    ensure_shape(
+        query_round_proofs.len() == params.config.num_query_rounds,
+        "number of FRI query rounds",
+    )?;
     for query_round in query_round_proofs {
         let FriQueryRound {
             initial_trees_proof,
             steps,
         } = query_round;
 
-        ensure!(initial_trees_proof.evals_proofs.len() == instance.oracles.len());
-        for ((leaf, merkle_proof), oracle) in initial_trees_proof
-            .evals_proofs
-            .iter()
-            .zip(&instance.oracles)
-        {
-            ensure!(leaf.len() == oracle.num_polys + salt_size(oracle.blinding && params.hiding));
-            ensure!(merkle_proof.len() + cap_height == params.lde_bits());
-        }
+        validate_initial_trees_proof_shape(initial_trees_proof, oracles, params, false)?;
 
-        ensure!(steps.len() == params.reduction_arity_bits.len());
+        ensure_shape(
+            steps.len() == params.reduction_arity_bits.len(),
+            "number of FRI query steps",
+        )?;
         let mut codeword_len_bits = params.lde_bits();
         for (step, arity_bits) in steps.iter().zip(&params.reduction_arity_bits) {
-            let FriQueryStep {
-                evals,
-                merkle_proof,
-            } = step;
-
-            let arity = 1 << arity_bits;
             codeword_len_bits -= arity_bits;
+            validate_query_step_shape(step, 1 << arity_bits, codeword_len_bits, params, false)?;
+        }
+    }
+
+    ensure_shape(
+        final_poly.len() == params.final_poly_len(),
+        "final polynomial length",
+    )?;
+
+    Ok(())
+}
+
+/// Checks the shape of a compressed FRI proof, including that its query rounds contain exactly
+/// the entries that decompression will look up.
+pub(crate) fn validate_compressed_fri_proof_shape<F, C, const D: usize>(
+    proof: &CompressedFriProof<F, C::Hasher, D>,
+    oracles: &[FriOracleInfo],
+    params: &FriParams,
+) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    let CompressedFriProof {
+        commit_phase_merkle_caps,
+        query_round_proofs,
+        final_poly,
+        pow_witness: _pow_witness,
+    } = proof;
+    let CompressedFriQueryRounds {
+        indices,
+        initial_trees_proofs,
+        steps,
+    } = query_round_proofs;
+
+    let cap_height = params.config.cap_height;
+    ensure_shape(
+        commit_phase_merkle_caps.len() == params.reduction_arity_bits.len(),
+        "number of FRI commit phase caps",
+    )?;
+    for cap in commit_phase_merkle_caps {
+        ensure_shape(cap.len() == 1 << cap_height, "FRI commit phase cap height")?;
+    }
 
-            ensure!(evals.len() == arity);
-            ensure!(merkle_proof.len() + cap_height == codeword_len_bits);
+    ensure_shape(
+        indices.len() == params.config.num_query_rounds,
+        "number of FRI query rounds",
+    )?;
+    ensure_shape(
+        indices.iter().all(|&i| i < params.lde_size()),
+        "FRI query index out of range",
+    )?;
+
+    // The distinct indices queried at the current reduction depth.
+    let mut level_indices = indices.clone();
+    level_indices.sort_unstable();
+    level_indices.dedup();
+
+    ensure_shape(
+        initial_trees_proofs.len() == level_indices.len()
+            && level_indices
+                .iter()
+                .all(|i| initial_trees_proofs.contains_key(i)),
+        "compressed initial trees proof indices",
+    )?;
+    for initial_trees_proof in initial_trees_proofs.values() {
+        validate_initial_trees_proof_shape(initial_trees_proof, oracles, params, true)?;
+    }
+
+    ensure_shape(
+        steps.len() == params.reduction_arity_bits.len(),
+        "number of FRI query steps",
+    )?;
+    let mut codeword_len_bits = params.lde_bits();
+    for (step_proofs, &arity_bits) in steps.iter().zip(&params.reduction_arity_bits) {
+        level_indices.iter_mut().for_each(|i| *i >>= arity_bits);
+        level_indices.dedup();
+        codeword_len_bits -= arity_bits;
+
+        ensure_shape(
+            step_proofs.len() == level_indices.len()
+                && level_indices.iter().all(|i| step_proofs.contains_key(i)),
+            "compressed FRI query step indices",
+        )?;
+        for step in step_proofs.values() {
+            // One evaluation per coset is inferred by the verifier, so it is omitted.
+            validate_query_step_shape(
+                step,
+                (1 << arity_bits) - 1,
+                codeword_len_bits,
+                params,
+                true,
+            )?;
         }
     }
 
-    ensure!(final_poly.len() == params.final_poly_len());
+    ensure_shape(
+        final_poly.len() == params.final_poly_len(),
+        "final polynomial length",
+    )?;
 
     Ok(())
 }
+
+/// Checks the shape of the openings of the initial oracles. Compressed Merkle proofs may omit
+/// siblings, so their length is only bounded.
+fn validate_initial_trees_proof_shape<F, H, const D: usize>(
+    initial_trees_proof: &FriInitialTreeProof<F, H>,
+    oracles: &[FriOracleInfo],
+    params: &FriParams,
+    compressed: bool,
+) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    H: Hasher<F>,
+{
+    let cap_height = params.config.cap_height;
+    ensure_shape(
+        initial_trees_proof.evals_proofs.len() == oracles.len(),
+        "number of initial trees openings",
+    )?;
+    for ((leaf, merkle_proof), oracle) in initial_trees_proof.evals_proofs.iter().zip(oracles) {
+        ensure_shape(
+            leaf.len() == oracle.num_polys + salt_size(oracle.blinding && params.hiding),
+            "initial tree leaf length",
+        )?;
+        ensure_shape(
+            merkle_proof_len_ok(
+                merkle_proof.len(),
+                params.lde_bits() - cap_height,
+                compressed,
+            ),
+            "initial tree Merkle proof length",
+        )?;
+    }
+    Ok(())
+}
+
+fn validate_query_step_shape<F, H, const D: usize>(
+    step: &FriQueryStep<F, H, D>,
+    num_evals: usize,
+    codeword_len_bits: usize,
+    params: &FriParams,
+    compressed: bool,
+) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    H: Hasher<F>,
+{
+    let FriQueryStep {
+        evals,
+        merkle_proof,
+    } = step;
+    ensure_shape(evals.len() == num_evals, "FRI query step evaluations")?;
+    ensure_shape(
+        merkle_proof_len_ok(
+            merkle_proof.len(),
+            codeword_len_bits - params.config.cap_height,
+            compressed,
+        ),
+        "FRI query step Merkle proof length",
+    )
+}
+
+fn merkle_proof_len_ok(len: usize, expected: usize, compressed: bool) -> bool {
+    if compressed {
+        len <= expected
+    } else {
+        len == expected
+    }
+}
@@ -1,7 +1,5 @@
 use alloc::vec::Vec;
 
-use anyhow::{ensure, Result};
-
 use crate::field::extension::{flatten, Extendable, FieldExtension};
 use crate::field::interpolation::{barycentric_weights, interpolate};
 use crate::field::types::Field;
@@ -13,6 +11,7 @@ use crate::hash::hash_types::RichField;
 use crate::hash::merkle_proofs::verify_merkle_proof_to_cap;
 use crate::hash::merkle_tree::MerkleCap;
 use crate::plonk::config::{GenericConfig, Hasher};
+use crate::plonk::verification_error::VerificationError;
 use crate::util::reducing::ReducingFactor;
 use crate::util::{log2_strict, reverse_bits, reverse_index_bits_in_place};
 
@@ -48,12 +47,12 @@ pub(crate) fn compute_evaluation<F: Field + Extendable<D>, const D: usize>(
 pub(crate) fn fri_verify_proof_of_work<F: RichField + Extendable<D>, const D: usize>(
     fri_pow_response: F,
     config: &FriConfig,
-) -> Result<()> {
-    ensure!(
-        fri_pow_response.to_canonical_u64().leading_zeros()
-            >= config.proof_of_work_bits + (64 - F::order().bits()) as u32,
-        "Invalid proof of work witness."
-    );
+) -> Result<(), VerificationError> {
+    if fri_pow_response.to_canonical_u64().leading_zeros()
+        < config.proof_of_work_bits + (64 - F::order().bits()) as u32
+    {
+        return Err(VerificationError::InvalidProofOfWork);
+    }
 
     Ok(())
 }
@@ -69,8 +68,8 @@ pub fn verify_fri_proof<
     initial_merkle_caps: &[MerkleCap<F, C::Hasher>],
     proof: &FriProof<F, C::Hasher, D>,
     params: &FriParams,
-) -> Result<()> {
-    validate_fri_proof_shape::<F, C, D>(proof, instance, params)?;
+) -> Result<(), VerificationError> {
+    validate_fri_proof_shape::<F, C, D>(proof, &instance.oracles, params)?;
 
     // Size of the LDE domain.
     let n = params.lde_size();
@@ -78,20 +77,16 @@ pub fn verify_fri_proof<
     // Check PoW.
     fri_verify_proof_of_work(challenges.fri_pow_response, &params.config)?;
 
-    // Check that parameters are coherent.
-    ensure!(
-        params.config.num_query_rounds == proof.query_round_proofs.len(),
-        "Number of query rounds does not match config."
-    );
-
     let precomputed_reduced_evals =
         PrecomputedReducedOpenings::from_os_and_alpha(openings, challenges.fri_alpha);
-    for (&x_index, round_proof) in challenges
+    for (query_round, (&x_index, round_proof)) in challenges
         .fri_query_indices
         .iter()
         .zip(&proof.query_round_proofs)
+        .enumerate()
     {
         fri_verifier_query_round::<F, C, D>(
+            query_round,
             instance,
             challenges,
             &precomputed_reduced_evals,
@@ -108,12 +103,23 @@ pub fn verify_fri_proof<
 }
 
 fn fri_verify_initial_proof<F: RichField, H: Hasher<F>>(
+    query_round: usize,
     x_index: usize,
     proof: &FriInitialTreeProof<F, H>,
     initial_merkle_caps: &[MerkleCap<F, H>],
-) -> Result<()> {
-    for ((evals, merkle_proof), cap) in proof.evals_proofs.iter().zip(initial_merkle_caps) {
-        verify_merkle_proof_to_cap::<F, H>(evals.clone(), x_index, cap, merkle_proof)?;
+) -> Result<(), VerificationError> {
+    for (oracle, ((evals, merkle_proof), cap)) in proof
+        .evals_proofs
+        .iter()
+        .zip(initial_merkle_caps)
+        .enumerate()
+    {
+        verify_merkle_proof_to_cap::<F, H>(evals.clone(), x_index, cap, merkle_proof).map_err(
+            |_| VerificationError::InitialMerkleProofInvalid {
+                query_round,
+                oracle,
+            },
+        )?;
     }
 
     Ok(())
@@ -165,6 +171,7 @@ fn fri_verifier_query_round<
     C: GenericConfig<D, F = F>,
     const D: usize,
 >(
+    query_round: usize,
     instance: &FriInstanceInfo<F, D>,
     challenges: &FriChallenges<F, D>,
     precomputed_reduced_evals: &PrecomputedReducedOpenings<F, D>,
@@ -174,8 +181,9 @@ fn fri_verifier_query_round<
     n: usize,
     round_proof: &FriQueryRound<F, C::Hasher, D>,
     params: &FriParams,
-) -> Result<()> {
+) -> Result<(), VerificationError> {
     fri_verify_initial_proof::<F, C::Hasher>(
+        query_round,
         x_index,
         &round_proof.initial_trees_proof,
         initial_merkle_caps,
@@ -205,7 +213,12 @@ fn fri_verifier_query_round<
         let x_index_within_coset = x_index & (arity - 1);
 
         // Check consistency with our old evaluation from the previous round.
-        ensure!(evals[x_index_within_coset] == old_eval);
+        if evals[x_index_within_coset] != old_eval {
+            return Err(VerificationError::FriConsistencyFailed {
+                query_round,
+                step: i,
+            });
+        }
 
         // Infer P(y) from {P(x)}_{x^arity=y}.
         old_eval = compute_evaluation(
@@ -221,7 +234,11 @@ fn fri_verifier_query_round<
             coset_index,
             &proof.commit_phase_merkle_caps[i],
             &round_proof.steps[i].merkle_proof,
-        )?;
+        )
+        .map_err(|_| VerificationError::CommitPhaseMerkleProofInvalid {
+            query_round,
+            step: i,
+        })?;
 
         // Update the point x to x^arity.
         subgroup_x = subgroup_x.exp_power_of_2(arity_bits);
@@ -231,10 +248,9 @@ fn fri_verifier_query_round<
 
     // Final check of FRI. After all the reductions, we check that the final polynomial is equal
     // to the one sent by the prover.
-    ensure!(
-        proof.final_poly.eval(subgroup_x.into()) == old_eval,
-        "Final polynomial evaluation is invalid."
-    );
+    if proof.final_poly.eval(subgroup_x.into()) != old_eval {
+        return Err(VerificationError::FinalPolyMismatch { query_round });
+    }
 
     Ok(())
 }
@@ -1,6 +1,7 @@
 use itertools::Itertools;
 
 use crate::field::extension::Extendable;
+use crate::fri::pcs::{FriPcsProof, FriPcsProofTarget};
 use crate::fri::proof::{FriProof, FriProofTarget};
 use crate::hash::hash_types::RichField;
 use crate::iop::witness::WitnessWrite;
@@ -69,3 +70,21 @@ pub fn set_fri_proof_target<F, W, H, const D: usize>(
         }
     }
 }
+
+/// Set the targets in a `FriPcsProofTarget` to their corresponding values in a `FriPcsProof`.
+pub fn set_fri_pcs_proof_target<F, W, H, const D: usize>(
+    witness: &mut W,
+    proof_target: &FriPcsProofTarget<D>,
+    proof: &FriPcsProof<F, H, D>,
+) where
+    F: RichField + Extendable<D>,
+    W: WitnessWrite<F> + ?Sized,
+    H: AlgebraicHasher<F>,
+{
+    for (ts, xs) in proof_target.values.iter().zip_eq(&proof.values) {
+        for (&t, &x) in ts.iter().zip_eq(xs) {
+            witness.set_extension_target(t, x);
+        }
+    }
+    set_fri_proof_target(witness, &proof_target.fri_proof, &proof.fri_proof);
+}
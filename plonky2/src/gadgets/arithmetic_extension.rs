@@ -612,7 +612,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        verify(proof, &data.verifier_only, &data.common)
+        Ok(verify(proof, &data.verifier_only, &data.common)?)
     }
 
     #[test]
@@ -639,7 +639,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        verify(proof, &data.verifier_only, &data.common)
+        Ok(verify(proof, &data.verifier_only, &data.common)?)
     }
 
     #[test]
@@ -677,6 +677,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        verify(proof, &data.verifier_only, &data.common)
+        Ok(verify(proof, &data.verifier_only, &data.common)?)
     }
 }
@@ -0,0 +1,271 @@
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::hash::hash_types::RichField;
+use crate::iop::target::{BoolTarget, Target};
+use crate::plonk::circuit_builder::CircuitBuilder;
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    /// Returns whether `a < b`, where `a` and `b` are range-checked to fit in `num_bits` bits.
+    pub fn is_less_than(&mut self, a: Target, b: Target, num_bits: usize) -> BoolTarget {
+        self.range_check_comparison_inputs(a, b, num_bits);
+
+        // `a + 2^num_bits - b` fits in `num_bits + 1` bits, and its top bit is set iff `a >= b`.
+        let a_minus_b = self.sub(a, b);
+        let shifted = self.add_const(a_minus_b, F::from_canonical_u64(1 << num_bits));
+        let bits = self.split_le(shifted, num_bits + 1);
+        self.not(bits[num_bits])
+    }
+
+    /// Asserts that `a < b`, where `a` and `b` are range-checked to fit in `num_bits` bits.
+    pub fn assert_less_than(&mut self, a: Target, b: Target, num_bits: usize) {
+        self.range_check_comparison_inputs(a, b, num_bits);
+
+        // `b - a - 1` fits in `num_bits` bits iff `a < b`, and wraps around to a large field
+        // element otherwise.
+        let b_minus_a = self.sub(b, a);
+        let slack = self.add_const(b_minus_a, F::NEG_ONE);
+        self.range_check(slack, num_bits);
+    }
+
+    /// Returns the minimum of `a` and `b`, which are range-checked to fit in `num_bits` bits.
+    pub fn min(&mut self, a: Target, b: Target, num_bits: usize) -> Target {
+        let a_lt_b = self.is_less_than(a, b, num_bits);
+        self.select(a_lt_b, a, b)
+    }
+
+    /// Returns the maximum of `a` and `b`, which are range-checked to fit in `num_bits` bits.
+    pub fn max(&mut self, a: Target, b: Target, num_bits: usize) -> Target {
+        let a_lt_b = self.is_less_than(a, b, num_bits);
+        self.select(a_lt_b, b, a)
+    }
+
+    /// Returns whether `a < b`, comparing the canonical representations of arbitrary field
+    /// elements.
+    pub fn is_less_than_canonical(&mut self, a: Target, b: Target) -> BoolTarget {
+        let a_bits = self.split_le_canonical(a);
+        let b_bits = self.split_le_canonical(b);
+
+        // Compare the bits from the most significant one, keeping track of whether the bits seen
+        // so far are equal.
+        let mut less = self._false().target;
+        let mut prefix_equal = self._true().target;
+        for (&x, &y) in a_bits.iter().zip(&b_bits).rev() {
+            // `x < y` iff `y - x * y == 1`.
+            let xy = self.mul(x.target, y.target);
+            let x_lt_y = self.sub(y.target, xy);
+            less = self.mul_add(prefix_equal, x_lt_y, less);
+            // `x != y` iff `x + y - 2 * x * y == 1`.
+            let x_plus_y = self.add(x.target, y.target);
+            let x_ne_y = self.arithmetic(-F::TWO, F::ONE, x.target, y.target, x_plus_y);
+            prefix_equal = self.arithmetic(-F::ONE, F::ONE, prefix_equal, x_ne_y, prefix_equal);
+        }
+        BoolTarget::new_unsafe(less)
+    }
+
+    /// Asserts that `a < b`, comparing the canonical representations of arbitrary field elements.
+    pub fn assert_less_than_canonical(&mut self, a: Target, b: Target) {
+        let a_lt_b = self.is_less_than_canonical(a, b);
+        self.assert_one(a_lt_b.target);
+    }
+
+    /// Splits `x` into `F::BITS` little-endian bits, constrained to encode an integer less than the
+    /// field order. Unlike `split_le(x, F::BITS)`, this rules out the non-canonical decomposition
+    /// `x + F::ORDER` of small elements.
+    pub fn split_le_canonical(&mut self, x: Target) -> Vec<BoolTarget> {
+        let bits = self.split_le(x, F::BITS);
+        self.assert_le_bits_canonical(&bits);
+        bits
+    }
+
+    /// Asserts that the `F::BITS` little-endian `bits` encode an integer less than the field order.
+    fn assert_le_bits_canonical(&mut self, bits: &[BoolTarget]) {
+        debug_assert_eq!(bits.len(), F::BITS);
+
+        // Compare the bits with those of the order from the most significant one. While they are
+        // equal, a bit of `x` must not be set where the order's is unset, and they can't be equal
+        // all the way.
+        let mut prefix_equal = self._true();
+        for (i, &bit) in bits.iter().enumerate().rev() {
+            if (F::ORDER >> i) & 1 == 1 {
+                prefix_equal = self.and(prefix_equal, bit);
+            } else {
+                let exceeds = self.and(prefix_equal, bit);
+                self.assert_zero(exceeds.target);
+            }
+        }
+        self.assert_zero(prefix_equal.target);
+    }
+
+    fn range_check_comparison_inputs(&mut self, a: Target, b: Target, num_bits: usize) {
+        assert!(
+            num_bits > 0 && num_bits + 1 < F::BITS,
+            "Can't compare {num_bits}-bit integers"
+        );
+        self.range_check(a, num_bits);
+        self.range_check(b, num_bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+    use crate::field::types::{Field, Field64, PrimeField64};
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    #[test]
+    fn test_is_less_than() -> Result<()> {
+        let mut rng = OsRng;
+        for num_bits in [1, 8, 32, 62] {
+            let config = CircuitConfig::standard_recursion_config();
+            let mut builder = CircuitBuilder::<F, D>::new(config);
+            let a = builder.add_virtual_target();
+            let b = builder.add_virtual_target();
+            let lt = builder.is_less_than(a, b, num_bits);
+            let min = builder.min(a, b, num_bits);
+            let max = builder.max(a, b, num_bits);
+            builder.register_public_input(lt.target);
+            builder.register_public_input(min);
+            builder.register_public_input(max);
+            let data = builder.build::<C>();
+
+            let max_value = (1u64 << num_bits) - 1;
+            let pairs = [
+                (0, max_value),
+                (max_value, 0),
+                (max_value, max_value),
+                (rng.gen_range(0..=max_value), rng.gen_range(0..=max_value)),
+            ];
+            for (a_value, b_value) in pairs {
+                let mut pw = PartialWitness::new();
+                pw.set_target(a, F::from_canonical_u64(a_value));
+                pw.set_target(b, F::from_canonical_u64(b_value));
+                let proof = data.prove(pw)?;
+                let expected = [
+                    F::from_bool(a_value < b_value),
+                    F::from_canonical_u64(a_value.min(b_value)),
+                    F::from_canonical_u64(a_value.max(b_value)),
+                ];
+                assert_eq!(proof.public_inputs, expected);
+                data.verify(proof)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn test_assert_less_than_with(a_value: u64, b_value: u64) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let a = builder.constant(F::from_canonical_u64(a_value));
+        let b = builder.constant(F::from_canonical_u64(b_value));
+        builder.assert_less_than(a, b, 6);
+        let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        data.verify(proof)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_less_than() -> Result<()> {
+        test_assert_less_than_with(41, 42)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_less_than_equal() {
+        test_assert_less_than_with(42, 42).unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_less_than_greater() {
+        test_assert_less_than_with(43, 42).unwrap()
+    }
+
+    #[test]
+    fn test_is_less_than_canonical() -> Result<()> {
+        let values = [
+            F::ZERO,
+            F::ONE,
+            F::from_canonical_u64((1 << 32) - 1),
+            F::from_canonical_u64(1 << 32),
+            F::NEG_ONE - F::ONE,
+            F::NEG_ONE,
+            F::from_canonical_u64(OsRng.gen_range(0..F::ORDER)),
+        ];
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let targets = builder.add_virtual_targets(values.len());
+        for &a in &targets {
+            for &b in &targets {
+                let lt = builder.is_less_than_canonical(a, b);
+                builder.register_public_input(lt.target);
+            }
+        }
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (&t, &x) in targets.iter().zip(&values) {
+            pw.set_target(t, x);
+        }
+        let proof = data.prove(pw)?;
+        let mut expected = Vec::new();
+        for a in values {
+            for b in values {
+                expected.push(F::from_bool(a.to_canonical_u64() < b.to_canonical_u64()));
+            }
+        }
+        assert_eq!(proof.public_inputs, expected);
+        data.verify(proof)?;
+        Ok(())
+    }
+    /// Checks bits witnessing the little-endian decomposition of `value`, which may exceed the
+    /// field order.
+    fn test_le_bits_canonical_with(value: u64) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let bits = (0..F::BITS)
+            .map(|_| builder.add_virtual_bool_target_safe())
+            .collect::<Vec<_>>();
+        builder.assert_le_bits_canonical(&bits);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (i, &bit) in bits.iter().enumerate() {
+            pw.set_bool_target(bit, (value >> i) & 1 == 1);
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_le_bits_canonical() -> Result<()> {
+        test_le_bits_canonical_with(5)?;
+        test_le_bits_canonical_with(F::ORDER - 1)
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_le_bits_non_canonical() {
+        // Another decomposition of 5, which `split_le(x, F::BITS)` alone would accept.
+        test_le_bits_canonical_with(5 + F::ORDER).unwrap()
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_le_bits_order() {
+        test_le_bits_canonical_with(F::ORDER).unwrap()
+    }
+}
@@ -1,9 +1,12 @@
 pub mod arithmetic;
 pub mod arithmetic_extension;
+pub mod comparison;
 pub mod hash;
 pub mod polynomial;
+pub mod ram;
 pub mod random_access;
 pub mod range_check;
 pub mod select;
 pub mod split_base;
 pub(crate) mod split_join;
+pub mod var_len_vec;
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::field::types::Field;
+use crate::hash::hash_types::RichField;
+use crate::iop::target::Target;
+use crate::iop::witness::{Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::util::log2_ceil;
+
+/// A read-write memory of a fixed number of cells. Writes replace the targets of the written cell
+/// in place, so a `RamTarget` always holds the current contents of the memory. Reads and writes
+/// constrain their index to be less than the number of cells.
+#[derive(Clone, Debug)]
+pub struct RamTarget {
+    pub cells: Vec<Target>,
+}
+
+impl RamTarget {
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn set_witness<F: Field, W: WitnessWrite<F>>(&self, witness: &mut W, values: &[F]) {
+        assert_eq!(values.len(), self.size());
+        for (&t, &x) in self.cells.iter().zip(values) {
+            witness.set_target(t, x);
+        }
+    }
+
+    pub fn get_witness<F: Field, W: Witness<F>>(&self, witness: &W) -> Vec<F> {
+        witness.get_targets(&self.cells)
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    pub fn add_virtual_ram(&mut self, size: usize) -> RamTarget {
+        assert!(size > 0, "A RAM must have at least one cell");
+        RamTarget {
+            cells: self.add_virtual_targets(size),
+        }
+    }
+
+    /// Returns the contents of the cell at `index`.
+    pub fn ram_read(&mut self, ram: &RamTarget, index: Target) -> Target {
+        self.range_check_ram_index(ram, index);
+        self.random_access(index, ram.cells.clone())
+    }
+
+    /// Sets the cell at `index` to `value`.
+    pub fn ram_write(&mut self, ram: &mut RamTarget, index: Target, value: Target) {
+        // The indicators also constrain the index to be in range.
+        let indicators = self.index_indicators(index, ram.size());
+        for (cell, is_index) in ram.cells.iter_mut().zip(indicators) {
+            *cell = self.select(is_index, value, *cell);
+        }
+    }
+
+    /// Constrains `index` to be less than the size of `ram`.
+    fn range_check_ram_index(&mut self, ram: &RamTarget, index: Target) {
+        if ram.size() == 1 {
+            self.assert_zero(index);
+            return;
+        }
+        // As `2^bits` is much smaller than the field order, `index` and `size - 1 - index` both
+        // fit in `bits` bits iff `index < size`.
+        let bits = log2_ceil(ram.size());
+        self.range_check(index, bits);
+        let max_index = self.constant(F::from_canonical_usize(ram.size() - 1));
+        let slack = self.sub(max_index, index);
+        self.range_check(slack, bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::types::Sample;
+    use crate::iop::witness::PartialWitness;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    #[test]
+    fn test_ram() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let size = 5;
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut ram = builder.add_virtual_ram(size);
+        let initial = ram.clone();
+        let index = builder.add_virtual_target();
+        let value = builder.add_virtual_target();
+        let before = builder.ram_read(&ram, index);
+        builder.ram_write(&mut ram, index, value);
+        let after = builder.ram_read(&ram, index);
+        builder.register_public_input(before);
+        builder.register_public_input(after);
+        builder.register_public_inputs(&ram.cells);
+        let data = builder.build::<C>();
+
+        let initial_values = F::rand_vec(size);
+        let value_value = F::rand();
+        let mut pw = PartialWitness::new();
+        initial.set_witness(&mut pw, &initial_values);
+        pw.set_target(index, F::from_canonical_usize(3));
+        pw.set_target(value, value_value);
+        let proof = data.prove(pw)?;
+
+        let mut final_values = initial_values.clone();
+        final_values[3] = value_value;
+        assert_eq!(proof.public_inputs[0], initial_values[3]);
+        assert_eq!(proof.public_inputs[1], value_value);
+        assert_eq!(&proof.public_inputs[2..], &final_values[..]);
+        data.verify(proof)?;
+        Ok(())
+    }
+}
@@ -1,23 +1,61 @@
+use alloc::vec;
 use alloc::vec::Vec;
 
 use crate::field::extension::Extendable;
 use crate::gates::random_access::RandomAccessGate;
 use crate::hash::hash_types::{HashOutTarget, MerkleCapTarget, RichField};
 use crate::iop::ext_target::ExtensionTarget;
-use crate::iop::target::Target;
+use crate::iop::generator::{GeneratedValues, SimpleGenerator};
+use crate::iop::target::{BoolTarget, Target};
+use crate::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::circuit_data::VerifierCircuitTarget;
-use crate::util::log2_strict;
+use crate::util::log2_ceil;
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
     /// Checks that a `Target` matches a vector at a particular index.
-    pub fn random_access(&mut self, access_index: Target, v: Vec<Target>) -> Target {
+    ///
+    /// Vectors whose length is not a power of two are implicitly padded with zeros, so indices
+    /// between the length and the next power of two read zero; larger indices are rejected. Vectors
+    /// too large for a single `RandomAccessGate` are split into chunks, each accessed with the low
+    /// bits of the index, and the results are then accessed with the high bits.
+    pub fn random_access(&mut self, access_index: Target, mut v: Vec<Target>) -> Target {
         let vec_size = v.len();
-        let bits = log2_strict(vec_size);
         debug_assert!(vec_size > 0);
         if vec_size == 1 {
             return v[0];
         }
+        let bits = log2_ceil(vec_size);
+        let zero = self.zero();
+        v.resize(1 << bits, zero);
+
+        let max_bits = RandomAccessGate::<F, D>::max_bits(&self.config);
+        assert!(
+            max_bits > 0,
+            "Not enough routed wires for a RandomAccessGate"
+        );
+        if bits <= max_bits {
+            return self.random_access_single_gate(access_index, v, bits);
+        }
+
+        let index_bits = self.split_le(access_index, bits);
+        let low = self.le_sum(index_bits[..max_bits].iter());
+        let high = self.le_sum(index_bits[max_bits..].iter());
+        let chunk_elements = v
+            .chunks(1 << max_bits)
+            .map(|chunk| self.random_access_single_gate(low, chunk.to_vec(), max_bits))
+            .collect();
+        self.random_access(high, chunk_elements)
+    }
+
+    /// Accesses a vector of `2^bits` elements with a single copy of `RandomAccessGate`.
+    fn random_access_single_gate(
+        &mut self,
+        access_index: Target,
+        v: Vec<Target>,
+        bits: usize,
+    ) -> Target {
+        debug_assert_eq!(v.len(), 1 << bits);
         let claimed_element = self.add_virtual_target();
 
         let dummy_gate = RandomAccessGate::<F, D>::new_from_config(&self.config, bits);
@@ -38,6 +76,28 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         claimed_element
     }
 
+    /// Returns targets `b_0, ..., b_{n - 1}` such that `b_i` is one iff `index == i`. This
+    /// constrains `index` to be less than `n`.
+    pub fn index_indicators(&mut self, index: Target, n: usize) -> Vec<BoolTarget> {
+        let indicators = (0..n)
+            .map(|_| self.add_virtual_bool_target_safe())
+            .collect::<Vec<_>>();
+        self.add_simple_generator(IndexIndicatorsGenerator {
+            index,
+            indicators: indicators.clone(),
+        });
+
+        let sum = self.add_many(indicators.iter().map(|b| b.target));
+        self.assert_one(sum);
+        let mut weighted_sum = self.zero();
+        for (i, b) in indicators.iter().enumerate() {
+            weighted_sum = self.mul_const_add(F::from_canonical_usize(i), b.target, weighted_sum);
+        }
+        self.connect(weighted_sum, index);
+
+        indicators
+    }
+
     /// Like `random_access`, but with `ExtensionTarget`s rather than simple `Target`s.
     pub fn random_access_extension(
         &mut self,
@@ -99,6 +159,25 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
     }
 }
 
+#[derive(Debug)]
+struct IndexIndicatorsGenerator {
+    index: Target,
+    indicators: Vec<BoolTarget>,
+}
+
+impl<F: RichField> SimpleGenerator<F> for IndexIndicatorsGenerator {
+    fn dependencies(&self) -> Vec<Target> {
+        vec![self.index]
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let index = witness.get_target(self.index).to_canonical_u64();
+        for (i, &b) in self.indicators.iter().enumerate() {
+            out_buffer.set_bool_target(b, i as u64 == index);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
@@ -132,7 +211,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        verify(proof, &data.verifier_only, &data.common)
+        Ok(verify(proof, &data.verifier_only, &data.common)?)
     }
 
     #[test]
@@ -142,4 +221,53 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_random_access_any_len() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        // A non-power-of-two length, and one too large for a single gate.
+        for len in [5, 300] {
+            let config = CircuitConfig::standard_recursion_config();
+            let mut builder = CircuitBuilder::<F, D>::new(config);
+            let v = builder.add_virtual_targets(len);
+            let indices = [0, 1, len / 2, len - 1];
+            for i in indices {
+                let index = builder.constant(F::from_canonical_usize(i));
+                let element = builder.random_access(index, v.clone());
+                builder.register_public_input(element);
+            }
+
+            let values = F::rand_vec(len);
+            let mut pw = PartialWitness::new();
+            for (&t, &x) in v.iter().zip(&values) {
+                pw.set_target(t, x);
+            }
+            let data = builder.build::<C>();
+            let proof = data.prove(pw)?;
+            let expected = indices.map(|i| values[i]);
+            assert_eq!(proof.public_inputs, expected);
+            data.verify(proof)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough routed wires")]
+    fn test_random_access_too_few_wires() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig {
+            num_routed_wires: 3,
+            ..CircuitConfig::standard_recursion_config()
+        };
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let v = builder.add_virtual_targets(2);
+        let index = builder.zero();
+        builder.random_access(index, v);
+    }
 }
@@ -75,6 +75,6 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        verify(proof, &data.verifier_only, &data.common)
+        Ok(verify(proof, &data.verifier_only, &data.common)?)
     }
 }
@@ -0,0 +1,312 @@
+use alloc::vec::Vec;
+
+use crate::field::extension::Extendable;
+use crate::field::types::{Field, PrimeField64};
+use crate::hash::hash_types::{HashOutTarget, RichField};
+use crate::hash::hashing::{SPONGE_RATE, SPONGE_WIDTH};
+use crate::iop::target::{BoolTarget, Target};
+use crate::iop::witness::{Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+use crate::plonk::config::AlgebraicHasher;
+use crate::util::{ceil_div_usize, log2_ceil};
+
+/// A vector of targets whose length is only known when proving, bounded by a capacity fixed when
+/// building the circuit. Elements past `len` are unconstrained, and no gadget depends on them.
+#[derive(Clone, Debug)]
+pub struct VarLenVec {
+    /// The number of elements in use, which is at most `capacity()`.
+    pub len: Target,
+    /// The elements, padded to the capacity.
+    pub data: Vec<Target>,
+}
+
+impl VarLenVec {
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Sets the length and elements of this vector, padding the unused capacity with zeros.
+    pub fn set_witness<F: Field, W: WitnessWrite<F>>(&self, witness: &mut W, values: &[F]) {
+        assert!(
+            values.len() <= self.capacity(),
+            "{} elements don't fit in a VarLenVec of capacity {}",
+            values.len(),
+            self.capacity()
+        );
+        witness.set_target(self.len, F::from_canonical_usize(values.len()));
+        for (i, &t) in self.data.iter().enumerate() {
+            witness.set_target(t, values.get(i).copied().unwrap_or(F::ZERO));
+        }
+    }
+
+    /// Returns the first `len` elements of this vector.
+    pub fn get_witness<F: PrimeField64, W: Witness<F>>(&self, witness: &W) -> Vec<F> {
+        let len = witness.get_target(self.len).to_canonical_u64() as usize;
+        witness.get_targets(&self.data[..len])
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
+    /// Adds a `VarLenVec` of the given capacity, whose length is constrained to be at most the
+    /// capacity.
+    pub fn add_virtual_var_len_vec(&mut self, capacity: usize) -> VarLenVec {
+        let v = VarLenVec {
+            len: self.add_virtual_target(),
+            data: self.add_virtual_targets(capacity),
+        };
+        self.index_indicators(v.len, capacity + 1);
+        v
+    }
+
+    /// Returns the element at `index`, which is constrained to be less than `v.len`.
+    pub fn var_len_vec_get(&mut self, v: &VarLenVec, index: Target) -> Target {
+        let one = self.one();
+        let len_minus_one = self.sub(v.len, one);
+        let slack = self.sub(len_minus_one, index);
+        self.range_check(slack, log2_ceil(v.capacity() + 1));
+        self.random_access(index, v.data.clone())
+    }
+
+    /// Returns `v` with `x` appended. The length of `v` is constrained to be less than its
+    /// capacity.
+    pub fn var_len_vec_push(&mut self, v: &VarLenVec, x: Target) -> VarLenVec {
+        let indicators = self.index_indicators(v.len, v.capacity() + 1);
+        // `v.len == capacity` would mean there is no room left.
+        self.assert_zero(indicators[v.capacity()].target);
+
+        let data = v
+            .data
+            .iter()
+            .zip(&indicators)
+            .map(|(&d, &is_len)| self.select(is_len, x, d))
+            .collect();
+        let one = self.one();
+        VarLenVec {
+            len: self.add(v.len, one),
+            data,
+        }
+    }
+
+    /// Returns the `len` elements of `v` starting at `start`, in a `VarLenVec` of the given
+    /// capacity. `start + len` is constrained to be at most `v.len`, and `len` to be at most
+    /// `capacity`.
+    pub fn var_len_vec_slice(
+        &mut self,
+        v: &VarLenVec,
+        start: Target,
+        len: Target,
+        capacity: usize,
+    ) -> VarLenVec {
+        let end = self.add(start, len);
+        let slack = self.sub(v.len, end);
+        self.range_check(slack, log2_ceil(v.capacity() + 1));
+        self.index_indicators(len, capacity + 1);
+
+        // Pad the table so that `start + i` is always in range, as `start <= v.len`.
+        let mut table = v.data.clone();
+        table.resize(v.capacity() + capacity, self.zero());
+        let data = (0..capacity)
+            .map(|i| {
+                let index = self.add_const(start, F::from_canonical_usize(i));
+                self.random_access(index, table.clone())
+            })
+            .collect();
+        VarLenVec { len, data }
+    }
+
+    /// Returns the concatenation of `a` and `b`, with capacity `a.capacity() + b.capacity()`.
+    pub fn var_len_vec_concat(&mut self, a: &VarLenVec, b: &VarLenVec) -> VarLenVec {
+        let capacity = a.capacity() + b.capacity();
+        let a_indicators = self.index_indicators(a.len, a.capacity() + 1);
+
+        // Pad `b` so that `i - a.len` is always in range for `i >= a.len`.
+        let mut b_table = b.data.clone();
+        b_table.resize(capacity, self.zero());
+
+        let mut in_a = self.one();
+        let data = (0..capacity)
+            .map(|i| {
+                // `in_a` is one iff `i < a.len`.
+                if i <= a.capacity() {
+                    in_a = self.sub(in_a, a_indicators[i].target);
+                }
+                let in_a = BoolTarget::new_unsafe(in_a);
+                let zero = self.zero();
+                let offset = self.add_const(a.len, -F::from_canonical_usize(i));
+                let b_index = self.neg(offset);
+                let b_index = self.select(in_a, zero, b_index);
+                let b_element = self.random_access(b_index, b_table.clone());
+                if i < a.capacity() {
+                    self.select(in_a, a.data[i], b_element)
+                } else {
+                    b_element
+                }
+            })
+            .collect();
+        VarLenVec {
+            len: self.add(a.len, b.len),
+            data,
+        }
+    }
+
+    /// Hashes the first `v.len` elements of `v`, padded with the `pad10*1` rule. The result is the
+    /// same as `H::hash_pad` applied to those elements, and doesn't depend on the others.
+    pub fn hash_var_len_vec_pad<H: AlgebraicHasher<F>>(&mut self, v: &VarLenVec) -> HashOutTarget {
+        let capacity = v.capacity();
+        let len_indicators = self.index_indicators(v.len, capacity + 1);
+
+        // The padded message has `SPONGE_WIDTH * k` elements, where `k` is the smallest integer
+        // such that this leaves room for the two padding ones. `k_indicators[k - 1]` is one iff
+        // the message has `k` blocks.
+        let num_blocks = |len: usize| ceil_div_usize(len + 2, SPONGE_WIDTH);
+        let max_blocks = num_blocks(capacity);
+        let k_indicators = (1..=max_blocks)
+            .map(|k| {
+                let terms = (0..=capacity)
+                    .filter(|&len| num_blocks(len) == k)
+                    .map(|len| len_indicators[len].target)
+                    .collect::<Vec<_>>();
+                self.add_many(terms)
+            })
+            .collect::<Vec<_>>();
+
+        let max_padded_len = max_blocks * SPONGE_WIDTH;
+        let mut in_message = self.one();
+        let mut in_padded = self.one();
+        let mut padded = Vec::with_capacity(max_padded_len);
+        let mut in_padded_flags = Vec::with_capacity(max_padded_len);
+        for i in 0..max_padded_len {
+            // Element `i` is the message element if `i < len`, or one if `i == len`.
+            let mut x = self.zero();
+            if i < capacity {
+                in_message = self.sub(in_message, len_indicators[i].target);
+                x = self.mul(in_message, v.data[i]);
+            }
+            if i <= capacity {
+                x = self.add(x, len_indicators[i].target);
+            }
+            in_padded_flags.push(BoolTarget::new_unsafe(in_padded));
+            // The last element of the last block is one.
+            if (i + 1) % SPONGE_WIDTH == 0 {
+                let k_indicator = k_indicators[(i + 1) / SPONGE_WIDTH - 1];
+                x = self.add(x, k_indicator);
+                in_padded = self.sub(in_padded, k_indicator);
+            }
+            padded.push(x);
+        }
+
+        // Absorb the padded message in overwrite mode, as in `hash_n_to_m_no_pad`. Elements past
+        // the end of the padded message leave the state unchanged, which matches the partial last
+        // chunk of the native sponge, and the states after further chunks are ignored.
+        let zero = self.zero();
+        let mut state = [zero; SPONGE_WIDTH];
+        let mut states = Vec::new();
+        for (chunk, flags) in padded
+            .chunks(SPONGE_RATE)
+            .zip(in_padded_flags.chunks(SPONGE_RATE))
+        {
+            for ((s, &x), &flag) in state.iter_mut().zip(chunk).zip(flags) {
+                *s = self.select(flag, x, *s);
+            }
+            state = self.permute::<H>(state);
+            states.push(state);
+        }
+
+        // Select the state after the last chunk of the padded message.
+        let mut output = [zero; 4];
+        for (k, &k_indicator) in (1..=max_blocks).zip(&k_indicators) {
+            let num_chunks = ceil_div_usize(k * SPONGE_WIDTH, SPONGE_RATE);
+            for (o, &s) in output.iter_mut().zip(&states[num_chunks - 1]) {
+                *o = self.mul_add(k_indicator, s, *o);
+            }
+        }
+        HashOutTarget::from(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::types::Sample;
+    use crate::hash::poseidon::PoseidonHash;
+    use crate::iop::witness::PartialWitness;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    #[test]
+    fn test_var_len_vec_gadgets() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let a = builder.add_virtual_var_len_vec(5);
+        let b = builder.add_virtual_var_len_vec(3);
+        let x = builder.add_virtual_target();
+        let pushed = builder.var_len_vec_push(&a, x);
+        let concat = builder.var_len_vec_concat(&pushed, &b);
+        let start = builder.constant(F::TWO);
+        let slice_len = builder.constant(F::from_canonical_usize(3));
+        let slice = builder.var_len_vec_slice(&concat, start, slice_len, 4);
+        let index = builder.one();
+        let element = builder.var_len_vec_get(&slice, index);
+        builder.register_public_input(concat.len);
+        builder.register_public_input(slice.len);
+        builder.register_public_inputs(&slice.data[..3]);
+        builder.register_public_input(element);
+
+        let a_values = F::rand_vec(2);
+        let b_values = F::rand_vec(3);
+        let x_value = F::rand();
+        let mut pw = PartialWitness::new();
+        a.set_witness(&mut pw, &a_values);
+        b.set_witness(&mut pw, &b_values);
+        pw.set_target(x, x_value);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        // `concat` is `[a0, a1, x, b0, b1, b2]`, so `slice` is `[x, b0, b1]`.
+        let expected = vec![
+            F::from_canonical_usize(6),
+            F::from_canonical_usize(3),
+            x_value,
+            b_values[0],
+            b_values[1],
+            b_values[0],
+        ];
+        assert_eq!(proof.public_inputs, expected);
+        data.verify(proof)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_hash_var_len_vec_pad() -> Result<()> {
+        let capacity = 21;
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let v = builder.add_virtual_var_len_vec(capacity);
+        let hash = builder.hash_var_len_vec_pad::<PoseidonHash>(&v);
+        builder.register_public_inputs(&hash.elements);
+        let data = builder.build::<C>();
+
+        for len in [0, 1, 4, 9, 10, 11, 17, 21] {
+            let values = F::rand_vec(len);
+            let mut pw = PartialWitness::new();
+            v.set_witness(&mut pw, &values);
+            let proof = data.prove(pw)?;
+            assert_eq!(
+                proof.public_inputs,
+                PoseidonHash::hash_pad(&values).elements.to_vec(),
+                "len = {len}"
+            );
+            data.verify(proof)?;
+        }
+        Ok(())
+    }
+}
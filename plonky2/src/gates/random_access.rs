@@ -50,6 +50,15 @@ impl<F: RichField + Extendable<D>, const D: usize> RandomAccessGate<F, D> {
         }
     }
 
+    /// The largest number of index bits for which at least one copy of the gate fits in a row.
+    pub fn max_bits(config: &CircuitConfig) -> usize {
+        let fits = |bits: usize| {
+            let vec_size = 1 << bits;
+            2 + vec_size <= config.num_routed_wires && 2 + vec_size + bits <= config.num_wires
+        };
+        (0..).take_while(|&bits| fits(bits + 1)).count()
+    }
+
     pub fn new_from_config(config: &CircuitConfig, bits: usize) -> Self {
         // We can access a list of 2^bits elements.
         let vec_size = 1 << bits;
@@ -51,7 +51,7 @@ pub(crate) fn compress_merkle_proofs<F: RichField, H: Hasher<F>>(
     compressed_proofs
 }
 
-/// Decompress compressed Merkle proofs.
+/// Decompress compressed Merkle proofs, or return `None` if they are missing siblings.
 /// Note: The data and indices must be in the same order as in `compress_merkle_proofs`.
 pub(crate) fn decompress_merkle_proofs<F: RichField, H: Hasher<F>>(
     leaves_data: &[Vec<F>],
@@ -59,7 +59,7 @@ pub(crate) fn decompress_merkle_proofs<F: RichField, H: Hasher<F>>(
     compressed_proofs: &[MerkleProof<F, H>],
     height: usize,
     cap_height: usize,
-) -> Vec<MerkleProof<F, H>> {
+) -> Option<Vec<MerkleProof<F, H>>> {
     let num_leaves = 1 << height;
     let compressed_proofs = compressed_proofs.to_vec();
     let mut decompressed_proofs = Vec::with_capacity(compressed_proofs.len());
@@ -82,9 +82,14 @@ pub(crate) fn decompress_merkle_proofs<F: RichField, H: Hasher<F>>(
             let index = (i + num_leaves) >> layer_height;
             let current_hash = seen[&index];
             let sibling_index = index ^ 1;
-            let sibling_hash = *seen
-                .entry(sibling_index)
-                .or_insert_with(|| *p.next().unwrap());
+            let sibling_hash = match seen.get(&sibling_index) {
+                Some(&h) => h,
+                None => {
+                    let h = *p.next()?;
+                    seen.insert(sibling_index, h);
+                    h
+                }
+            };
             let parent_hash = if index.is_even() {
                 H::two_to_one(current_hash, sibling_hash)
             } else {
@@ -109,7 +114,7 @@ pub(crate) fn decompress_merkle_proofs<F: RichField, H: Hasher<F>>(
         decompressed_proofs.push(decompressed_proof);
     }
 
-    decompressed_proofs
+    Some(decompressed_proofs)
 }
 
 #[cfg(test)]
@@ -144,10 +149,28 @@ mod tests {
             &compressed_proofs,
             h,
             cap_height,
-        );
+        )
+        .unwrap();
 
         assert_eq!(proofs, decompressed_proofs);
 
+        // Dropping a sibling must be detected rather than panic.
+        if let Some(p) = compressed_proofs
+            .iter()
+            .position(|p| !p.siblings.is_empty())
+        {
+            let mut truncated_proofs = compressed_proofs.clone();
+            truncated_proofs[p].siblings.pop();
+            assert!(decompress_merkle_proofs(
+                &indices.iter().map(|&i| vs[i].clone()).collect::<Vec<_>>(),
+                &indices,
+                &truncated_proofs,
+                h,
+                cap_height,
+            )
+            .is_none());
+        }
+
         let compressed_proof_bytes = serde_cbor::to_vec(&compressed_proofs).unwrap();
         println!(
             "Compressed proof length: {} bytes",
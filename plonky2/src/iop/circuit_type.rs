@@ -0,0 +1,239 @@
+use core::fmt::Debug;
+
+use crate::field::extension::Extendable;
+use crate::hash::hash_types::{HashOut, HashOutTarget, RichField};
+use crate::iop::target::{BoolTarget, Target};
+use crate::iop::witness::{Witness, WitnessWrite};
+use crate::plonk::circuit_builder::CircuitBuilder;
+
+/// A type of targets which can be allocated in a circuit and assigned from a native value as a
+/// whole. Compound structures can implement it with `#[derive(CircuitType)]` from the
+/// `plonky2_derive` crate, which delegates to the implementations of their fields.
+pub trait CircuitType<F: RichField>: Sized {
+    /// The type of values this type of targets is assigned in a witness.
+    type Native: Clone + Debug;
+
+    /// Adds fresh virtual targets, constrained to be a valid encoding of some `Self::Native`.
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>;
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &Self::Native);
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> Self::Native;
+
+    /// Constrains `x` and `y` to be equal.
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>;
+
+    /// Returns `x` if `b` is true, and `y` otherwise.
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>;
+}
+
+impl<F: RichField> CircuitType<F> for Target {
+    type Native = F;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.add_virtual_target()
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &F) {
+        witness.set_target(*self, *value);
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> F {
+        witness.get_target(*self)
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect(*x, *y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.select(b, *x, *y)
+    }
+}
+
+impl<F: RichField> CircuitType<F> for BoolTarget {
+    type Native = bool;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.add_virtual_bool_target_safe()
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &bool) {
+        witness.set_bool_target(*self, *value);
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> bool {
+        witness.get_bool_target(*self)
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect(x.target, y.target);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        // A selection between two booleans is itself a boolean.
+        BoolTarget::new_unsafe(builder.select(b, x.target, y.target))
+    }
+}
+
+impl<F: RichField> CircuitType<F> for HashOutTarget {
+    type Native = HashOut<F>;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.add_virtual_hash()
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &HashOut<F>) {
+        witness.set_hash_target(*self, *value);
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> HashOut<F> {
+        witness.get_hash_target(*self)
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect_hashes(*x, *y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        builder.select_hash(b, *x, *y)
+    }
+}
+
+impl<F: RichField, T: CircuitType<F>, const N: usize> CircuitType<F> for [T; N] {
+    type Native = [T::Native; N];
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        core::array::from_fn(|_| T::add_virtual(builder))
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &Self::Native) {
+        for (t, v) in self.iter().zip(value) {
+            t.set_witness(witness, v);
+        }
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> Self::Native {
+        core::array::from_fn(|i| self[i].get_witness(witness))
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        for (x, y) in x.iter().zip(y) {
+            T::connect(builder, x, y);
+        }
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        core::array::from_fn(|i| T::select(builder, b, &x[i], &y[i]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::types::{Field, Sample};
+    use crate::iop::witness::PartialWitness;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    #[test]
+    fn test_select_and_connect() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = <[HashOutTarget; 2]>::add_virtual(&mut builder);
+        let y = <[HashOutTarget; 2]>::add_virtual(&mut builder);
+        let b = BoolTarget::add_virtual(&mut builder);
+        let selected = <[HashOutTarget; 2]>::select(&mut builder, b, &x, &y);
+        let expected = <[HashOutTarget; 2]>::add_virtual(&mut builder);
+        CircuitType::connect(&mut builder, &selected, &expected);
+
+        let x_value = [HashOut::rand(), HashOut::rand()];
+        let y_value = [HashOut::rand(), HashOut::rand()];
+        let mut pw = PartialWitness::new();
+        x.set_witness(&mut pw, &x_value);
+        y.set_witness(&mut pw, &y_value);
+        b.set_witness(&mut pw, &false);
+        expected.set_witness(&mut pw, &y_value);
+        assert_eq!(x.get_witness(&pw), x_value);
+        assert_eq!(b.target.get_witness(&pw), F::ZERO);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Logic common to multiple IOPs.
 
 pub mod challenger;
+pub mod circuit_type;
 pub mod ext_target;
 pub mod generator;
 pub mod target;
@@ -44,6 +44,7 @@ use crate::plonk::config::{AlgebraicHasher, GenericConfig, GenericHashOut, Hashe
 use crate::plonk::copy_constraint::CopyConstraint;
 use crate::plonk::permutation_argument::Forest;
 use crate::plonk::plonk_common::PlonkOracle;
+use crate::plonk::public_input_schema::{PublicInputSchema, PublicInputType};
 use crate::timed;
 use crate::util::context_tree::ContextTree;
 use crate::util::partial_products::num_partial_products;
@@ -67,6 +68,9 @@ pub struct CircuitBuilder<F: RichField + Extendable<D>, const D: usize> {
     /// Targets to be made public.
     public_inputs: Vec<Target>,
 
+    /// The named fields of the public inputs.
+    pub(crate) public_input_schema: PublicInputSchema,
+
     /// The next available index for a `VirtualTarget`.
     virtual_target_index: usize,
 
@@ -111,6 +115,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             gates: HashSet::new(),
             gate_instances: Vec::new(),
             public_inputs: Vec::new(),
+            public_input_schema: PublicInputSchema::default(),
             virtual_target_index: 0,
             copy_constraints: Vec::new(),
             context_log: ContextTree::new(),
@@ -170,10 +175,47 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
         targets.iter().for_each(|&t| self.register_public_input(t));
     }
 
+    /// Registers the given targets as public inputs, and records them in the public input schema
+    /// as a field with the given name and type.
+    pub fn register_named_public_inputs(
+        &mut self,
+        name: &str,
+        ty: PublicInputType,
+        targets: &[Target],
+    ) {
+        assert_eq!(
+            targets.len(),
+            ty.len(),
+            "Public input field {name} has the wrong number of targets"
+        );
+        self.public_input_schema
+            .add_field(name, ty, self.public_inputs.len());
+        self.register_public_inputs(targets);
+    }
+
+    /// Registers the given target as a public input named `name`.
+    pub fn register_named_public_input(&mut self, name: &str, target: Target) {
+        self.register_named_public_inputs(name, PublicInputType::Target, &[target]);
+    }
+
+    /// Registers the given boolean as a public input named `name`.
+    pub fn register_named_public_bool(&mut self, name: &str, b: BoolTarget) {
+        self.register_named_public_inputs(name, PublicInputType::Bool, &[b.target]);
+    }
+
+    /// Registers the given hash as public inputs named `name`.
+    pub fn register_named_public_hash(&mut self, name: &str, hash: HashOutTarget) {
+        self.register_named_public_inputs(name, PublicInputType::HashOut, &hash.elements);
+    }
+
     pub fn num_public_inputs(&self) -> usize {
         self.public_inputs.len()
     }
 
+    pub fn public_input_schema(&self) -> &PublicInputSchema {
+        &self.public_input_schema
+    }
+
     /// Adds a new "virtual" target. This is not an actual wire in the witness, but just a target
     /// that help facilitate witness generation. In particular, a generator can assign a values to a
     /// virtual target, which can then be copied to other (virtual or concrete) targets. When we
@@ -906,6 +948,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilder<F, D> {
             num_gate_constraints,
             num_constants,
             num_public_inputs,
+            public_input_schema: self.public_input_schema,
             k_is,
             num_partial_products,
         };
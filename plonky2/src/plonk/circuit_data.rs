@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 use core::ops::{Range, RangeFrom};
 
 use anyhow::Result;
+use maybe_rayon::ThreadPool;
 
 use crate::field::extension::Extendable;
 use crate::field::fft::FftRootTable;
@@ -28,8 +29,11 @@ use crate::plonk::circuit_builder::CircuitBuilder;
 use crate::plonk::config::{GenericConfig, Hasher};
 use crate::plonk::plonk_common::PlonkOracle;
 use crate::plonk::proof::{CompressedProofWithPublicInputs, ProofWithPublicInputs};
-use crate::plonk::prover::prove;
+use crate::plonk::prover::{prove, prove_with_context, prove_with_pool};
+use crate::plonk::public_input_schema::PublicInputSchema;
+use crate::plonk::verification_error::VerificationError;
 use crate::plonk::verifier::verify;
+use crate::util::prover_context::ProverContext;
 use crate::util::timing::TimingTree;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -124,14 +128,47 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         )
     }
 
-    pub fn verify(&self, proof_with_pis: ProofWithPublicInputs<F, C, D>) -> Result<()> {
+    /// Like `prove`, but reports the progress of the prover to `ctx`, which can cancel it.
+    pub fn prove_with_context(
+        &self,
+        inputs: PartialWitness<F>,
+        ctx: &ProverContext,
+    ) -> Result<ProofWithPublicInputs<F, C, D>> {
+        prove_with_context(
+            &self.prover_only,
+            &self.common,
+            inputs,
+            &mut TimingTree::default(),
+            ctx,
+        )
+    }
+
+    /// Like `prove`, but runs the prover in `pool` rather than in the global thread pool.
+    pub fn prove_with_pool(
+        &self,
+        inputs: PartialWitness<F>,
+        pool: &ThreadPool,
+    ) -> Result<ProofWithPublicInputs<F, C, D>> {
+        prove_with_pool(
+            &self.prover_only,
+            &self.common,
+            inputs,
+            &mut TimingTree::default(),
+            pool,
+        )
+    }
+
+    pub fn verify(
+        &self,
+        proof_with_pis: ProofWithPublicInputs<F, C, D>,
+    ) -> Result<(), VerificationError> {
         verify(proof_with_pis, &self.verifier_only, &self.common)
     }
 
     pub fn verify_compressed(
         &self,
         compressed_proof_with_pis: CompressedProofWithPublicInputs<F, C, D>,
-    ) -> Result<()> {
+    ) -> Result<(), VerificationError> {
         compressed_proof_with_pis.verify(&self.verifier_only, &self.common)
     }
 
@@ -201,6 +238,36 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
             &mut TimingTree::default(),
         )
     }
+
+    /// Like `prove`, but reports the progress of the prover to `ctx`, which can cancel it.
+    pub fn prove_with_context(
+        &self,
+        inputs: PartialWitness<F>,
+        ctx: &ProverContext,
+    ) -> Result<ProofWithPublicInputs<F, C, D>> {
+        prove_with_context(
+            &self.prover_only,
+            &self.common,
+            inputs,
+            &mut TimingTree::default(),
+            ctx,
+        )
+    }
+
+    /// Like `prove`, but runs the prover in `pool` rather than in the global thread pool.
+    pub fn prove_with_pool(
+        &self,
+        inputs: PartialWitness<F>,
+        pool: &ThreadPool,
+    ) -> Result<ProofWithPublicInputs<F, C, D>> {
+        prove_with_pool(
+            &self.prover_only,
+            &self.common,
+            inputs,
+            &mut TimingTree::default(),
+            pool,
+        )
+    }
 }
 
 /// Circuit data required by the prover.
@@ -217,14 +284,17 @@ pub struct VerifierCircuitData<
 impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
     VerifierCircuitData<F, C, D>
 {
-    pub fn verify(&self, proof_with_pis: ProofWithPublicInputs<F, C, D>) -> Result<()> {
+    pub fn verify(
+        &self,
+        proof_with_pis: ProofWithPublicInputs<F, C, D>,
+    ) -> Result<(), VerificationError> {
         verify(proof_with_pis, &self.verifier_only, &self.common)
     }
 
     pub fn verify_compressed(
         &self,
         compressed_proof_with_pis: CompressedProofWithPublicInputs<F, C, D>,
-    ) -> Result<()> {
+    ) -> Result<(), VerificationError> {
         compressed_proof_with_pis.verify(&self.verifier_only, &self.common)
     }
 }
@@ -268,7 +338,7 @@ pub struct VerifierOnlyCircuitData<C: GenericConfig<D>, const D: usize> {
 }
 
 /// Circuit data required by both the prover and the verifier.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq)]
 pub struct CommonCircuitData<F: RichField + Extendable<D>, const D: usize> {
     pub config: CircuitConfig,
 
@@ -291,6 +361,9 @@ pub struct CommonCircuitData<F: RichField + Extendable<D>, const D: usize> {
 
     pub num_public_inputs: usize,
 
+    /// The named fields of the public inputs.
+    pub public_input_schema: PublicInputSchema,
+
     /// The `{k_i}` valued used in `S_ID_i` in Plonk's permutation argument.
     pub(crate) k_is: Vec<F>,
 
@@ -298,6 +371,38 @@ pub struct CommonCircuitData<F: RichField + Extendable<D>, const D: usize> {
     pub(crate) num_partial_products: usize,
 }
 
+/// The public input schema only names public inputs and doesn't affect the circuit itself, so it is
+/// ignored here. This lets a cyclic circuit with named public inputs match the `CommonCircuitData`
+/// it was built against, which has no schema.
+impl<F: RichField + Extendable<D>, const D: usize> PartialEq for CommonCircuitData<F, D> {
+    fn eq(&self, other: &Self) -> bool {
+        // Destructured exhaustively so that new fields can't be left out by accident.
+        let Self {
+            config,
+            fri_params,
+            gates,
+            selectors_info,
+            quotient_degree_factor,
+            num_gate_constraints,
+            num_constants,
+            num_public_inputs,
+            public_input_schema: _,
+            k_is,
+            num_partial_products,
+        } = self;
+        *config == other.config
+            && *fri_params == other.fri_params
+            && *gates == other.gates
+            && *selectors_info == other.selectors_info
+            && *quotient_degree_factor == other.quotient_degree_factor
+            && *num_gate_constraints == other.num_gate_constraints
+            && *num_constants == other.num_constants
+            && *num_public_inputs == other.num_public_inputs
+            && *k_is == other.k_is
+            && *num_partial_products == other.num_partial_products
+    }
+}
+
 impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
     pub const fn degree_bits(&self) -> usize {
         self.fri_params.degree_bits
@@ -395,7 +500,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CommonCircuitData<F, D> {
         }
     }
 
-    fn fri_oracles(&self) -> Vec<FriOracleInfo> {
+    pub(crate) fn fri_oracles(&self) -> Vec<FriOracleInfo> {
         vec![
             FriOracleInfo {
                 num_polys: self.num_preprocessed_polys(),
@@ -33,7 +33,7 @@ fn get_challenges<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, cons
     pow_witness: F,
     circuit_digest: &<<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
     common_data: &CommonCircuitData<F, D>,
-) -> anyhow::Result<ProofChallenges<F, D>> {
+) -> ProofChallenges<F, D> {
     let config = &common_data.config;
     let num_challenges = config.num_challenges;
 
@@ -55,7 +55,7 @@ fn get_challenges<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, cons
 
     challenger.observe_openings(&openings.to_fri_openings());
 
-    Ok(ProofChallenges {
+    ProofChallenges {
         plonk_betas,
         plonk_gammas,
         plonk_alphas,
@@ -67,7 +67,7 @@ fn get_challenges<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, cons
             common_data.degree_bits(),
             &config.fri_config,
         ),
-    })
+    }
 }
 
 impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
@@ -77,11 +77,10 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         &self,
         circuit_digest: &<<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
         common_data: &CommonCircuitData<F, D>,
-    ) -> anyhow::Result<Vec<usize>> {
-        Ok(self
-            .get_challenges(self.get_public_inputs_hash(), circuit_digest, common_data)?
+    ) -> Vec<usize> {
+        self.get_challenges(self.get_public_inputs_hash(), circuit_digest, common_data)
             .fri_challenges
-            .fri_query_indices)
+            .fri_query_indices
     }
 
     /// Computes all Fiat-Shamir challenges used in the Plonk proof.
@@ -90,7 +89,7 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         public_inputs_hash: <<C as GenericConfig<D>>::InnerHasher as Hasher<F>>::Hash,
         circuit_digest: &<<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
         common_data: &CommonCircuitData<F, D>,
-    ) -> anyhow::Result<ProofChallenges<F, D>> {
+    ) -> ProofChallenges<F, D> {
         let Proof {
             wires_cap,
             plonk_zs_partial_products_cap,
@@ -129,7 +128,7 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         public_inputs_hash: <<C as GenericConfig<D>>::InnerHasher as Hasher<F>>::Hash,
         circuit_digest: &<<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
         common_data: &CommonCircuitData<F, D>,
-    ) -> anyhow::Result<ProofChallenges<F, D>> {
+    ) -> ProofChallenges<F, D> {
         let CompressedProof {
             wires_cap,
             plonk_zs_partial_products_cap,
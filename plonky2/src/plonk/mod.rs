@@ -7,7 +7,9 @@ pub(crate) mod permutation_argument;
 pub mod plonk_common;
 pub mod proof;
 pub mod prover;
+pub mod public_input_schema;
 mod validate_shape;
 pub(crate) mod vanishing_poly;
 pub mod vars;
+pub mod verification_error;
 pub mod verifier;
@@ -1,7 +1,6 @@
 use alloc::vec;
 use alloc::vec::Vec;
 
-use anyhow::ensure;
 use maybe_rayon::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +12,8 @@ use crate::fri::proof::{
 use crate::fri::structure::{
     FriOpeningBatch, FriOpeningBatchTarget, FriOpenings, FriOpeningsTarget,
 };
+#[cfg(feature = "std")]
+use crate::fri::validate_shape::validate_fri_proof_shape;
 use crate::fri::FriParams;
 use crate::hash::hash_types::{MerkleCapTarget, RichField};
 use crate::hash::merkle_tree::MerkleCap;
@@ -20,6 +21,11 @@ use crate::iop::ext_target::ExtensionTarget;
 use crate::iop::target::Target;
 use crate::plonk::circuit_data::{CommonCircuitData, VerifierOnlyCircuitData};
 use crate::plonk::config::{GenericConfig, Hasher};
+use crate::plonk::public_input_schema::PublicInputValue;
+use crate::plonk::validate_shape::validate_compressed_proof_with_pis_shape;
+#[cfg(feature = "std")]
+use crate::plonk::validate_shape::validate_proof_with_pis_shape;
+use crate::plonk::verification_error::VerificationError;
 use crate::plonk::verifier::verify_with_challenges;
 use crate::util::serialization::Write;
 #[cfg(feature = "std")]
@@ -89,7 +95,7 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         circuit_digest: &<<C as GenericConfig<D>>::Hasher as Hasher<C::F>>::Hash,
         common_data: &CommonCircuitData<F, D>,
     ) -> anyhow::Result<CompressedProofWithPublicInputs<F, C, D>> {
-        let indices = self.fri_query_indices(circuit_digest, common_data)?;
+        let indices = self.fri_query_indices(circuit_digest, common_data);
         let compressed_proof = self.proof.compress(&indices, &common_data.fri_params);
         Ok(CompressedProofWithPublicInputs {
             public_inputs: self.public_inputs,
@@ -103,6 +109,30 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         C::InnerHasher::hash_no_pad(&self.public_inputs)
     }
 
+    /// Decodes the public input field `name`, as described by the circuit's public input schema.
+    pub fn public_input(
+        &self,
+        name: &str,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> anyhow::Result<PublicInputValue<F>> {
+        common_data
+            .public_input_schema
+            .decode(&self.public_inputs, name)
+    }
+
+    /// Encodes `value` into the public input field `name`, as described by the circuit's public
+    /// input schema.
+    pub fn set_public_input(
+        &mut self,
+        name: &str,
+        value: &PublicInputValue<F>,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> anyhow::Result<()> {
+        common_data
+            .public_input_schema
+            .encode(&mut self.public_inputs, name, value)
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buffer = Vec::new();
         buffer
@@ -111,6 +141,8 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         buffer
     }
 
+    /// Deserializes a proof, rejecting any input that isn't a well-formed proof for
+    /// `common_data`. The size of every allocation is bounded by `common_data`.
     #[cfg(feature = "std")]
     pub fn from_bytes(
         bytes: Vec<u8>,
@@ -120,6 +152,13 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         let proof = buffer
             .read_proof_with_public_inputs(common_data)
             .map_err(anyhow::Error::msg)?;
+        validate_proof_with_pis_shape(&proof, common_data).map_err(anyhow::Error::msg)?;
+        validate_fri_proof_shape::<F, C, D>(
+            &proof.proof.opening_proof,
+            &common_data.fri_oracles(),
+            &common_data.fri_params,
+        )
+        .map_err(anyhow::Error::msg)?;
         Ok(proof)
     }
 }
@@ -149,7 +188,7 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         challenges: &ProofChallenges<F, D>,
         fri_inferred_elements: FriInferredElements<F, D>,
         params: &FriParams,
-    ) -> Proof<F, C, D> {
+    ) -> Result<Proof<F, C, D>, VerificationError> {
         let CompressedProof {
             wires_cap,
             plonk_zs_partial_products_cap,
@@ -158,13 +197,17 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
             opening_proof,
         } = self;
 
-        Proof {
+        Ok(Proof {
             wires_cap,
             plonk_zs_partial_products_cap,
             quotient_polys_cap,
             openings,
-            opening_proof: opening_proof.decompress::<C>(challenges, fri_inferred_elements, params),
-        }
+            opening_proof: opening_proof.decompress::<C>(
+                challenges,
+                fri_inferred_elements,
+                params,
+            )?,
+        })
     }
 }
 
@@ -188,11 +231,14 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         common_data: &CommonCircuitData<F, D>,
     ) -> anyhow::Result<ProofWithPublicInputs<F, C, D>> {
         let challenges =
-            self.get_challenges(self.get_public_inputs_hash(), circuit_digest, common_data)?;
+            self.get_challenges(self.get_public_inputs_hash(), circuit_digest, common_data);
+        validate_compressed_proof_with_pis_shape(&self, &challenges, common_data)
+            .map_err(anyhow::Error::msg)?;
         let fri_inferred_elements = self.get_inferred_elements(&challenges, common_data);
-        let decompressed_proof =
-            self.proof
-                .decompress(&challenges, fri_inferred_elements, &common_data.fri_params);
+        let decompressed_proof = self
+            .proof
+            .decompress(&challenges, fri_inferred_elements, &common_data.fri_params)
+            .map_err(anyhow::Error::msg)?;
         Ok(ProofWithPublicInputs {
             public_inputs: self.public_inputs,
             proof: decompressed_proof,
@@ -203,21 +249,18 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         self,
         verifier_data: &VerifierOnlyCircuitData<C, D>,
         common_data: &CommonCircuitData<F, D>,
-    ) -> anyhow::Result<()> {
-        ensure!(
-            self.public_inputs.len() == common_data.num_public_inputs,
-            "Number of public inputs doesn't match circuit data."
-        );
+    ) -> Result<(), VerificationError> {
         let public_inputs_hash = self.get_public_inputs_hash();
         let challenges = self.get_challenges(
             public_inputs_hash,
             &verifier_data.circuit_digest,
             common_data,
-        )?;
+        );
+        validate_compressed_proof_with_pis_shape(&self, &challenges, common_data)?;
         let fri_inferred_elements = self.get_inferred_elements(&challenges, common_data);
         let decompressed_proof =
             self.proof
-                .decompress(&challenges, fri_inferred_elements, &common_data.fri_params);
+                .decompress(&challenges, fri_inferred_elements, &common_data.fri_params)?;
         verify_with_challenges(
             decompressed_proof,
             public_inputs_hash,
@@ -241,6 +284,9 @@ impl<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>
         buffer
     }
 
+    /// Deserializes a compressed proof, rejecting malformed input. The size of every allocation
+    /// is bounded by `common_data`. Since the layout of the query rounds depends on the
+    /// Fiat-Shamir challenges, it is only checked when the proof is verified or decompressed.
     #[cfg(feature = "std")]
     pub fn from_bytes(
         bytes: Vec<u8>,
@@ -392,13 +438,16 @@ impl<const D: usize> OpeningSetTarget<D> {
 mod tests {
     use anyhow::Result;
 
-    use crate::field::types::Sample;
+    use crate::field::extension::Extendable;
+    use crate::field::types::{Field, Sample};
     use crate::fri::reduction_strategies::FriReductionStrategy;
     use crate::gates::noop::NoopGate;
     use crate::iop::witness::PartialWitness;
     use crate::plonk::circuit_builder::CircuitBuilder;
     use crate::plonk::circuit_data::CircuitConfig;
     use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use crate::plonk::proof::{CompressedProofWithPublicInputs, ProofWithPublicInputs};
+    use crate::plonk::verification_error::VerificationError;
     use crate::plonk::verifier::verify;
 
     #[test]
@@ -436,6 +485,144 @@ mod tests {
         assert_eq!(proof, decompressed_compressed_proof);
 
         verify(proof, &data.verifier_only, &data.common)?;
-        data.verify_compressed(compressed_proof)
+        Ok(data.verify_compressed(compressed_proof)?)
+    }
+
+    #[test]
+    fn test_verification_errors() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let xt = builder.constant(F::rand());
+        builder.register_public_input(xt);
+        for _ in 0..100 {
+            builder.add_gate(NoopGate, vec![]);
+        }
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+
+        let mut extra_public_input = proof.clone();
+        extra_public_input.public_inputs.push(F::ONE);
+        assert_eq!(
+            data.verify(extra_public_input),
+            Err(VerificationError::PublicInputsLengthMismatch {
+                expected: 1,
+                actual: 2
+            })
+        );
+
+        let mut missing_wire = proof.clone();
+        missing_wire.proof.openings.wires.pop();
+        assert_eq!(
+            data.verify(missing_wire),
+            Err(VerificationError::ShapeMismatch("number of wires openings"))
+        );
+
+        let mut wrong_wire = proof.clone();
+        wrong_wire.proof.openings.wires[0] += <F as Extendable<D>>::Extension::ONE;
+        assert_eq!(
+            data.verify(wrong_wire),
+            Err(VerificationError::VanishingCheckFailed { challenge: 0 })
+        );
+
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_malformed_proof_bytes() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let xt = builder.constant(F::rand());
+        builder.register_public_input(xt);
+        for _ in 0..100 {
+            builder.add_gate(NoopGate, vec![]);
+        }
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        let common_data = &data.common;
+
+        let bytes = proof.to_bytes();
+        assert_eq!(
+            ProofWithPublicInputs::<F, C, D>::from_bytes(bytes.clone(), common_data)?,
+            proof
+        );
+
+        // Truncated input and trailing bytes are rejected.
+        assert!(ProofWithPublicInputs::<F, C, D>::from_bytes(
+            bytes[..bytes.len() - 1].to_vec(),
+            common_data
+        )
+        .is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ProofWithPublicInputs::<F, C, D>::from_bytes(trailing, common_data).is_err());
+
+        // The public input is the last field element; a non-canonical encoding is rejected.
+        let mut non_canonical = bytes.clone();
+        let len = non_canonical.len();
+        non_canonical[len - 8..].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ProofWithPublicInputs::<F, C, D>::from_bytes(non_canonical, common_data).is_err());
+
+        // Corrupt Merkle proof lengths are rejected by shape validation.
+        let mut long_merkle_proof = proof.clone();
+        let sibling = proof.proof.wires_cap.0[0];
+        long_merkle_proof.proof.opening_proof.query_round_proofs[0]
+            .initial_trees_proof
+            .evals_proofs[0]
+            .1
+            .siblings
+            .push(sibling);
+        assert!(ProofWithPublicInputs::<F, C, D>::from_bytes(
+            long_merkle_proof.to_bytes(),
+            common_data
+        )
+        .is_err());
+
+        let compressed_proof = data.compress(proof)?;
+        let compressed_bytes = compressed_proof.to_bytes();
+        assert_eq!(
+            CompressedProofWithPublicInputs::<F, C, D>::from_bytes(
+                compressed_bytes.clone(),
+                common_data
+            )?,
+            compressed_proof
+        );
+        assert!(CompressedProofWithPublicInputs::<F, C, D>::from_bytes(
+            compressed_bytes[..compressed_bytes.len() - 1].to_vec(),
+            common_data
+        )
+        .is_err());
+
+        // Compressed proofs whose query rounds don't match the challenges are rejected without
+        // panicking during decompression.
+        let mut wrong_indices = compressed_proof.clone();
+        let query_rounds = &mut wrong_indices.proof.opening_proof.query_round_proofs;
+        query_rounds.indices[0] = (query_rounds.indices[0] + 1) % common_data.fri_params.lde_size();
+        assert!(data.verify_compressed(wrong_indices.clone()).is_err());
+        assert!(data.decompress(wrong_indices).is_err());
+
+        let mut missing_sibling = compressed_proof.clone();
+        for initial_trees_proof in missing_sibling
+            .proof
+            .opening_proof
+            .query_round_proofs
+            .initial_trees_proofs
+            .values_mut()
+        {
+            initial_trees_proof.evals_proofs[0].1.siblings.clear();
+        }
+        assert!(data.verify_compressed(missing_sibling.clone()).is_err());
+        assert!(data.decompress(missing_sibling).is_err());
+
+        Ok(data.verify_compressed(compressed_proof)?)
     }
 }
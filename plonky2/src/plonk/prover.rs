@@ -22,6 +22,7 @@ use crate::plonk::vanishing_poly::eval_vanishing_poly_base_batch;
 use crate::plonk::vars::EvaluationVarsBaseBatch;
 use crate::timed;
 use crate::util::partial_products::{partial_products_and_z_gx, quotient_chunk_products};
+use crate::util::prover_context::{ProverContext, ProverPhase};
 use crate::util::timing::TimingTree;
 use crate::util::{ceil_div_usize, log2_ceil, transpose};
 
@@ -30,12 +31,35 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
     common_data: &CommonCircuitData<F, D>,
     inputs: PartialWitness<F>,
     timing: &mut TimingTree,
+) -> Result<ProofWithPublicInputs<F, C, D>> {
+    prove_with_context(
+        prover_data,
+        common_data,
+        inputs,
+        timing,
+        &ProverContext::default(),
+    )
+}
+
+/// Like `prove`, but reports the progress of the prover to `ctx`, and stops with a `Cancelled`
+/// error between phases if `ctx` is cancelled.
+pub fn prove_with_context<
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    const D: usize,
+>(
+    prover_data: &ProverOnlyCircuitData<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+    inputs: PartialWitness<F>,
+    timing: &mut TimingTree,
+    ctx: &ProverContext,
 ) -> Result<ProofWithPublicInputs<F, C, D>> {
     let config = &common_data.config;
     let num_challenges = config.num_challenges;
     let quotient_degree = common_data.quotient_degree();
     let degree = common_data.degree();
 
+    ctx.enter_phase(ProverPhase::WitnessGeneration)?;
     let partition_witness = timed!(
         timing,
         &format!("run {} generators", prover_data.generators.len()),
@@ -61,6 +85,7 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
             .collect()
     );
 
+    ctx.enter_phase(ProverPhase::WireCommitment)?;
     let wires_commitment = timed!(
         timing,
         "compute wires commitment",
@@ -88,6 +113,7 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
         common_data.quotient_degree_factor < common_data.config.num_routed_wires,
         "When the number of routed wires is smaller that the degree, we should change the logic to avoid computing partial products."
     );
+    ctx.enter_phase(ProverPhase::PermutationZs)?;
     let mut partial_products_and_zs = timed!(
         timing,
         "compute partial products",
@@ -118,6 +144,7 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
 
     let alphas = challenger.get_n_challenges(num_challenges);
 
+    ctx.enter_phase(ProverPhase::Quotient)?;
     let quotient_polys = timed!(
         timing,
         "compute quotient polys",
@@ -174,6 +201,7 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
         "Opening point is in the subgroup."
     );
 
+    ctx.enter_phase(ProverPhase::Fri)?;
     let openings = timed!(
         timing,
         "construct the opening set",
@@ -219,6 +247,18 @@ pub fn prove<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D:
     })
 }
 
+/// Like `prove`, but runs every parallel step of the prover in `pool` rather than in the global
+/// thread pool.
+pub fn prove_with_pool<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
+    prover_data: &ProverOnlyCircuitData<F, C, D>,
+    common_data: &CommonCircuitData<F, D>,
+    inputs: PartialWitness<F>,
+    timing: &mut TimingTree,
+    pool: &ThreadPool,
+) -> Result<ProofWithPublicInputs<F, C, D>> {
+    pool.install(|| prove(prover_data, common_data, inputs, timing))
+}
+
 /// Compute the partial products used in the `Z` polynomials.
 fn all_wires_permutation_partial_products<
     F: RichField + Extendable<D>,
@@ -452,3 +492,79 @@ fn compute_quotient_polys<
         .map(|values| values.coset_ifft(F::coset_shift()))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::Result;
+    use maybe_rayon::ThreadPool;
+
+    use crate::field::types::{Field, Sample};
+    use crate::iop::target::Target;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_builder::CircuitBuilder;
+    use crate::plonk::circuit_data::{CircuitConfig, CircuitData};
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use crate::util::prover_context::{CancellationToken, Cancelled, ProverContext, ProverPhase};
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    /// Builds a circuit with a single public input, the cube of the returned target.
+    fn cube_circuit() -> (CircuitData<F, C, D>, Target) {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x = builder.add_virtual_target();
+        let x_cubed = builder.cube(x);
+        builder.register_public_input(x_cubed);
+        (builder.build::<C>(), x)
+    }
+
+    #[test]
+    fn test_prove_with_pool() -> Result<()> {
+        let (data, x) = cube_circuit();
+
+        let x_value = F::rand();
+        let pool = ThreadPool::new(1)?;
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, x_value);
+        let proof = data.prove_with_pool(pw, &pool)?;
+        assert_eq!(proof.public_inputs, vec![x_value.cube()]);
+
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    fn test_prove_with_context() -> Result<()> {
+        let (data, x) = cube_circuit();
+
+        let token = CancellationToken::new();
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let phases_clone = phases.clone();
+        let ctx = ProverContext::new()
+            .with_progress(move |phase| phases_clone.lock().unwrap().push(phase))
+            .with_cancellation(token.clone());
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, F::rand());
+        let proof = data.prove_with_context(pw.clone(), &ctx)?;
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![
+                ProverPhase::WitnessGeneration,
+                ProverPhase::WireCommitment,
+                ProverPhase::PermutationZs,
+                ProverPhase::Quotient,
+                ProverPhase::Fri,
+            ]
+        );
+        data.verify(proof)?;
+
+        token.cancel();
+        let err = data.prove_with_context(pw, &ctx).unwrap_err();
+        assert_eq!(err.downcast_ref::<Cancelled>(), Some(&Cancelled));
+        Ok(())
+    }
+}
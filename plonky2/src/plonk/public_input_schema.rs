@@ -0,0 +1,270 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use anyhow::{anyhow, ensure, Result};
+use num::BigUint;
+
+use crate::field::types::{Field, PrimeField64};
+use crate::hash::hash_types::{HashOut, RichField};
+
+/// The type of a named public input field, which determines how its value is laid out as field
+/// elements.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PublicInputType {
+    /// A single field element.
+    Target,
+    /// A single field element, which is either 0 or 1.
+    Bool,
+    /// A hash output, made of four field elements.
+    HashOut,
+    /// A single field element holding a 32-bit unsigned integer.
+    U32,
+    /// An unsigned integer made of `num_limbs` 32-bit limbs, least significant limb first.
+    BigUint { num_limbs: usize },
+}
+
+impl PublicInputType {
+    /// The number of field elements used by a value of this type.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Target | Self::Bool | Self::U32 => 1,
+            Self::HashOut => 4,
+            Self::BigUint { num_limbs } => *num_limbs,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A named range of a circuit's public inputs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicInputField {
+    pub name: String,
+    pub ty: PublicInputType,
+    /// The index of the field's first element within the public inputs.
+    pub start: usize,
+}
+
+impl PublicInputField {
+    /// The indices of the field's elements within the public inputs.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.ty.len()
+    }
+}
+
+/// The native value of a public input field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PublicInputValue<F: Field> {
+    Target(F),
+    Bool(bool),
+    HashOut(HashOut<F>),
+    U32(u32),
+    BigUint(BigUint),
+}
+
+/// Describes the layout of a circuit's public inputs as a list of named, typed fields. It is
+/// recorded by `CircuitBuilder` as public inputs are registered with a name, and stored in
+/// `CommonCircuitData`. Public inputs registered without a name are not part of any field.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PublicInputSchema {
+    fields: Vec<PublicInputField>,
+}
+
+impl PublicInputSchema {
+    pub fn fields(&self) -> &[PublicInputField] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&PublicInputField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub(crate) fn add_field(&mut self, name: &str, ty: PublicInputType, start: usize) {
+        assert!(
+            self.field(name).is_none(),
+            "Public input field {name} is already registered"
+        );
+        self.fields.push(PublicInputField {
+            name: name.to_string(),
+            ty,
+            start,
+        });
+    }
+
+    fn field_or_err(&self, name: &str) -> Result<&PublicInputField> {
+        self.field(name)
+            .ok_or_else(|| anyhow!("No public input field named {name}"))
+    }
+
+    /// Decodes the value of the field `name` from a proof's public inputs.
+    pub fn decode<F: RichField>(
+        &self,
+        public_inputs: &[F],
+        name: &str,
+    ) -> Result<PublicInputValue<F>> {
+        let field = self.field_or_err(name)?;
+        let range = field.range();
+        ensure!(
+            range.end <= public_inputs.len(),
+            "Public inputs are too short to contain field {name}"
+        );
+        let elements = &public_inputs[range];
+
+        Ok(match field.ty {
+            PublicInputType::Target => PublicInputValue::Target(elements[0]),
+            PublicInputType::Bool => {
+                let x = elements[0];
+                ensure!(x == F::ZERO || x == F::ONE, "Field {name} is not a boolean");
+                PublicInputValue::Bool(x == F::ONE)
+            }
+            PublicInputType::HashOut => {
+                PublicInputValue::HashOut(HashOut::from_vec(elements.to_vec()))
+            }
+            PublicInputType::U32 => PublicInputValue::U32(to_u32(elements[0], name)?),
+            PublicInputType::BigUint { .. } => {
+                let limbs = elements
+                    .iter()
+                    .map(|&x| to_u32(x, name))
+                    .collect::<Result<Vec<_>>>()?;
+                PublicInputValue::BigUint(BigUint::from_slice(&limbs))
+            }
+        })
+    }
+
+    /// Encodes `value` into the elements of `public_inputs` belonging to the field `name`.
+    pub fn encode<F: RichField>(
+        &self,
+        public_inputs: &mut [F],
+        name: &str,
+        value: &PublicInputValue<F>,
+    ) -> Result<()> {
+        let field = self.field_or_err(name)?;
+        let range = field.range();
+        ensure!(
+            range.end <= public_inputs.len(),
+            "Public inputs are too short to contain field {name}"
+        );
+        let elements = &mut public_inputs[range];
+
+        match (field.ty, value) {
+            (PublicInputType::Target, PublicInputValue::Target(x)) => elements[0] = *x,
+            (PublicInputType::Bool, PublicInputValue::Bool(b)) => elements[0] = F::from_bool(*b),
+            (PublicInputType::HashOut, PublicInputValue::HashOut(h)) => {
+                elements.copy_from_slice(&h.elements)
+            }
+            (PublicInputType::U32, PublicInputValue::U32(x)) => {
+                elements[0] = F::from_canonical_u32(*x)
+            }
+            (PublicInputType::BigUint { num_limbs }, PublicInputValue::BigUint(x)) => {
+                let limbs = x.to_u32_digits();
+                ensure!(
+                    limbs.len() <= num_limbs,
+                    "Value of field {name} doesn't fit in {num_limbs} limbs"
+                );
+                for (i, element) in elements.iter_mut().enumerate() {
+                    *element = F::from_canonical_u32(limbs.get(i).copied().unwrap_or(0));
+                }
+            }
+            (ty, _) => return Err(anyhow!("Field {name} has type {ty:?}, got {value:?}")),
+        }
+        Ok(())
+    }
+}
+
+fn to_u32<F: PrimeField64>(x: F, name: &str) -> Result<u32> {
+    x.to_canonical_u64()
+        .try_into()
+        .map_err(|_| anyhow!("Field {name} has an element which is not a u32"))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+    use crate::field::goldilocks_field::GoldilocksField;
+    use crate::field::types::Sample;
+    use crate::iop::witness::{PartialWitness, WitnessWrite};
+    use crate::plonk::circuit_builder::CircuitBuilder;
+    use crate::plonk::circuit_data::CircuitConfig;
+    use crate::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    #[test]
+    fn test_encode_decode() -> Result<()> {
+        type F = GoldilocksField;
+        let mut schema = PublicInputSchema::default();
+        schema.add_field("flag", PublicInputType::Bool, 0);
+        schema.add_field("root", PublicInputType::HashOut, 1);
+        schema.add_field("amount", PublicInputType::BigUint { num_limbs: 3 }, 5);
+        schema.add_field("nonce", PublicInputType::U32, 8);
+
+        let values = [
+            ("flag", PublicInputValue::Bool(true)),
+            ("root", PublicInputValue::HashOut(HashOut::rand())),
+            (
+                "amount",
+                PublicInputValue::BigUint(BigUint::from(u64::MAX) * 3u32),
+            ),
+            ("nonce", PublicInputValue::U32(u32::MAX)),
+        ];
+        let mut public_inputs = vec![F::ZERO; 9];
+        for (name, value) in &values {
+            schema.encode(&mut public_inputs, name, value)?;
+        }
+        for (name, value) in &values {
+            assert_eq!(&schema.decode(&public_inputs, name)?, value);
+        }
+
+        assert!(schema
+            .encode(&mut public_inputs, "flag", &PublicInputValue::U32(1))
+            .is_err());
+        assert!(schema
+            .encode(
+                &mut public_inputs,
+                "amount",
+                &PublicInputValue::BigUint(BigUint::from(1u8) << 96)
+            )
+            .is_err());
+        assert!(schema.decode(&public_inputs, "missing").is_err());
+        public_inputs[0] = F::TWO;
+        assert!(schema.decode(&public_inputs, "flag").is_err());
+        public_inputs[8] = F::from_canonical_u64(1 << 32);
+        assert!(schema.decode(&public_inputs, "nonce").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_in_circuit_data() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x = builder.add_virtual_target();
+        builder.register_public_input(x);
+        let hash = builder.hash_n_to_hash_no_pad::<crate::hash::poseidon::PoseidonHash>(vec![x]);
+        builder.register_named_public_hash("hash", hash);
+        let zero = builder.zero();
+        let is_zero = builder.is_equal(x, zero);
+        builder.register_named_public_bool("is_zero", is_zero);
+
+        let data = builder.build::<C>();
+        let schema = &data.common.public_input_schema;
+        assert_eq!(schema.field("hash").unwrap().range(), 1..5);
+        assert_eq!(schema.field("is_zero").unwrap().range(), 5..6);
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, F::ZERO);
+        let proof = data.prove(pw)?;
+        assert_eq!(
+            proof.public_input("is_zero", &data.common)?,
+            PublicInputValue::Bool(true)
+        );
+        data.verify(proof)?;
+        Ok(())
+    }
+}
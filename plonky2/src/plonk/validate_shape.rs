@@ -1,15 +1,19 @@
-use anyhow::ensure;
-
 use crate::field::extension::Extendable;
+use crate::fri::validate_shape::validate_compressed_fri_proof_shape;
 use crate::hash::hash_types::RichField;
+use crate::hash::merkle_tree::MerkleCap;
 use crate::plonk::circuit_data::CommonCircuitData;
 use crate::plonk::config::GenericConfig;
-use crate::plonk::proof::{OpeningSet, Proof, ProofWithPublicInputs};
+use crate::plonk::proof::{
+    CompressedProof, CompressedProofWithPublicInputs, OpeningSet, Proof, ProofChallenges,
+    ProofWithPublicInputs,
+};
+use crate::plonk::verification_error::{ensure_shape, VerificationError};
 
 pub(crate) fn validate_proof_with_pis_shape<F, C, const D: usize>(
     proof_with_pis: &ProofWithPublicInputs<F, C, D>,
     common_data: &CommonCircuitData<F, D>,
-) -> anyhow::Result<()>
+) -> Result<(), VerificationError>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
@@ -19,22 +23,71 @@ where
         public_inputs,
     } = proof_with_pis;
     validate_proof_shape(proof, common_data)?;
-    ensure!(
-        public_inputs.len() == common_data.num_public_inputs,
-        "Number of public inputs doesn't match circuit data."
-    );
+    validate_public_inputs_len(public_inputs.len(), common_data)
+}
+
+/// Checks the shape of a compressed proof. Since the layout of the compressed FRI query rounds
+/// depends on the query indices, the proof's `challenges` are needed as well.
+pub(crate) fn validate_compressed_proof_with_pis_shape<F, C, const D: usize>(
+    proof_with_pis: &CompressedProofWithPublicInputs<F, C, D>,
+    challenges: &ProofChallenges<F, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    let CompressedProofWithPublicInputs {
+        proof,
+        public_inputs,
+    } = proof_with_pis;
+    let CompressedProof {
+        wires_cap,
+        plonk_zs_partial_products_cap,
+        quotient_polys_cap,
+        openings,
+        opening_proof,
+    } = proof;
+    validate_caps_and_openings_shape::<F, C, D>(
+        wires_cap,
+        plonk_zs_partial_products_cap,
+        quotient_polys_cap,
+        openings,
+        common_data,
+    )?;
+    validate_compressed_fri_proof_shape::<F, C, D>(
+        opening_proof,
+        &common_data.fri_oracles(),
+        &common_data.fri_params,
+    )?;
+    ensure_shape(
+        opening_proof.query_round_proofs.indices == challenges.fri_challenges.fri_query_indices,
+        "compressed FRI query indices",
+    )?;
+    validate_public_inputs_len(public_inputs.len(), common_data)
+}
+
+fn validate_public_inputs_len<F: RichField + Extendable<D>, const D: usize>(
+    len: usize,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<(), VerificationError> {
+    if len != common_data.num_public_inputs {
+        return Err(VerificationError::PublicInputsLengthMismatch {
+            expected: common_data.num_public_inputs,
+            actual: len,
+        });
+    }
     Ok(())
 }
 
 fn validate_proof_shape<F, C, const D: usize>(
     proof: &Proof<F, C, D>,
     common_data: &CommonCircuitData<F, D>,
-) -> anyhow::Result<()>
+) -> Result<(), VerificationError>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
 {
-    let config = &common_data.config;
     let Proof {
         wires_cap,
         plonk_zs_partial_products_cap,
@@ -44,6 +97,27 @@ where
         // validate_fri_proof_shape), so we ignore it here.
         opening_proof: _,
     } = proof;
+    validate_caps_and_openings_shape::<F, C, D>(
+        wires_cap,
+        plonk_zs_partial_products_cap,
+        quotient_polys_cap,
+        openings,
+        common_data,
+    )
+}
+
+fn validate_caps_and_openings_shape<F, C, const D: usize>(
+    wires_cap: &MerkleCap<F, C::Hasher>,
+    plonk_zs_partial_products_cap: &MerkleCap<F, C::Hasher>,
+    quotient_polys_cap: &MerkleCap<F, C::Hasher>,
+    openings: &OpeningSet<F, D>,
+    common_data: &CommonCircuitData<F, D>,
+) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+{
+    let config = &common_data.config;
     let OpeningSet {
         constants,
         plonk_sigmas,
@@ -54,15 +128,39 @@ where
         quotient_polys,
     } = openings;
     let cap_height = common_data.fri_params.config.cap_height;
-    ensure!(wires_cap.height() == cap_height);
-    ensure!(plonk_zs_partial_products_cap.height() == cap_height);
-    ensure!(quotient_polys_cap.height() == cap_height);
-    ensure!(constants.len() == common_data.num_constants);
-    ensure!(plonk_sigmas.len() == config.num_routed_wires);
-    ensure!(wires.len() == config.num_wires);
-    ensure!(plonk_zs.len() == config.num_challenges);
-    ensure!(plonk_zs_next.len() == config.num_challenges);
-    ensure!(partial_products.len() == config.num_challenges * common_data.num_partial_products);
-    ensure!(quotient_polys.len() == common_data.num_quotient_polys());
+    ensure_shape(wires_cap.len() == 1 << cap_height, "wires cap height")?;
+    ensure_shape(
+        plonk_zs_partial_products_cap.len() == 1 << cap_height,
+        "Zs and partial products cap height",
+    )?;
+    ensure_shape(
+        quotient_polys_cap.len() == 1 << cap_height,
+        "quotient polynomials cap height",
+    )?;
+    ensure_shape(
+        constants.len() == common_data.num_constants,
+        "number of constants openings",
+    )?;
+    ensure_shape(
+        plonk_sigmas.len() == config.num_routed_wires,
+        "number of sigmas openings",
+    )?;
+    ensure_shape(wires.len() == config.num_wires, "number of wires openings")?;
+    ensure_shape(
+        plonk_zs.len() == config.num_challenges,
+        "number of Zs openings",
+    )?;
+    ensure_shape(
+        plonk_zs_next.len() == config.num_challenges,
+        "number of next Zs openings",
+    )?;
+    ensure_shape(
+        partial_products.len() == config.num_challenges * common_data.num_partial_products,
+        "number of partial products openings",
+    )?;
+    ensure_shape(
+        quotient_polys.len() == common_data.num_quotient_polys(),
+        "number of quotient polynomials openings",
+    )?;
     Ok(())
 }
@@ -2,7 +2,10 @@ use alloc::vec::Vec;
 use alloc::{format, vec};
 
 use crate::field::batch_util::batch_add_inplace;
+use crate::field::extension::packed::PackedExtension;
 use crate::field::extension::{Extendable, FieldExtension};
+use crate::field::packable::Packable;
+use crate::field::packed::PackedField;
 use crate::field::types::Field;
 use crate::field::zero_poly_coset::ZeroPolyOnCoset;
 use crate::hash::hash_types::RichField;
@@ -55,21 +58,17 @@ pub(crate) fn eval_vanishing_poly<
         let z_gx = next_zs[i];
         vanishing_z_1_terms.push(l_0_x * (z_x - F::Extension::ONE));
 
-        let numerator_values = (0..common_data.config.num_routed_wires)
-            .map(|j| {
-                let wire_value = vars.local_wires[j];
-                let k_i = common_data.k_is[j];
-                let s_id = x.scalar_mul(k_i);
-                wire_value + s_id.scalar_mul(betas[i]) + gammas[i].into()
-            })
-            .collect::<Vec<_>>();
-        let denominator_values = (0..common_data.config.num_routed_wires)
-            .map(|j| {
-                let wire_value = vars.local_wires[j];
-                let s_sigma = s_sigmas[j];
-                wire_value + s_sigma.scalar_mul(betas[i]) + gammas[i].into()
-            })
-            .collect::<Vec<_>>();
+        let routed_wires = &vars.local_wires[..common_data.config.num_routed_wires];
+        // `wire_value + beta * s_id + gamma`, with `s_id = k_i * x`.
+        let numerator_values = permutation_terms::<F, D>(
+            routed_wires,
+            |_| x,
+            |j| common_data.k_is[j] * betas[i],
+            gammas[i],
+        );
+        // `wire_value + beta * s_sigma + gamma`.
+        let denominator_values =
+            permutation_terms::<F, D>(routed_wires, |j| s_sigmas[j], |_| betas[i], gammas[i]);
 
         // The partial products considered for this iteration of `i`.
         let current_partial_products = &partial_products[i * num_prods..(i + 1) * num_prods];
@@ -96,6 +95,39 @@ pub(crate) fn eval_vanishing_poly<
     plonk_common::reduce_with_powers_multi(&vanishing_terms, alphas)
 }
 
+/// Returns `wires[j] + scalars(j) * terms(j) + gamma` for each `j`. If the base field has a SIMD
+/// packing, wires are processed `WIDTH` at a time using packed extension arithmetic.
+fn permutation_terms<F: RichField + Extendable<D>, const D: usize>(
+    wires: &[F::Extension],
+    terms: impl Fn(usize) -> F::Extension,
+    scalars: impl Fn(usize) -> F,
+    gamma: F,
+) -> Vec<F::Extension> {
+    type P<F> = <F as Packable>::Packing;
+    let width = P::<F>::WIDTH;
+    let gamma_packed = PackedExtension::<P<F>, D>::broadcast(gamma.into());
+
+    let mut res = vec![F::Extension::ZERO; wires.len()];
+    for (chunk_index, out) in res.chunks_mut(width).enumerate() {
+        let start = chunk_index * width;
+        if width > 1 && out.len() == width {
+            let wires_packed = PackedExtension::<P<F>, D>::from_slice(&wires[start..start + width]);
+            let terms_packed = PackedExtension::<P<F>, D>::from_fn(|lane| terms(start + lane));
+            let mut scalars_packed = P::<F>::ZEROS;
+            for (lane, s) in scalars_packed.as_slice_mut().iter_mut().enumerate() {
+                *s = scalars(start + lane);
+            }
+            (wires_packed + terms_packed.scalar_mul(scalars_packed) + gamma_packed).unpack(out);
+        } else {
+            for (lane, x) in out.iter_mut().enumerate() {
+                let j = start + lane;
+                *x = wires[j] + terms(j).scalar_mul(scalars(j)) + gamma.into();
+            }
+        }
+    }
+    res
+}
+
 /// Like `eval_vanishing_poly`, but specialized for base field points. Batched.
 pub(crate) fn eval_vanishing_poly_base_batch<
     F: RichField + Extendable<D>,
@@ -0,0 +1,85 @@
+use core::fmt::{Display, Formatter};
+
+/// The reason a proof was rejected by one of the native verifiers (Plonk, FRI or STARK).
+///
+/// Every verifier in this crate and in `starky` returns this error, so callers can tell a
+/// malformed proof apart from one that is well-formed but invalid. When the `std` feature is
+/// enabled it implements `std::error::Error`, so it converts into an `anyhow::Error` with `?`
+/// and can be recovered from one with `anyhow::Error::downcast_ref`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerificationError {
+    /// The proof does not have the shape prescribed by the circuit or STARK data. The payload
+    /// names the offending component.
+    ShapeMismatch(&'static str),
+    /// The number of public inputs doesn't match the circuit data.
+    PublicInputsLengthMismatch { expected: usize, actual: usize },
+    /// The public inputs are inconsistent with the data they are expected to commit to. The
+    /// payload names the offending component.
+    PublicInputsMismatch(&'static str),
+    /// The identity `vanishing(zeta) = Z_H(zeta) quotient(zeta)` doesn't hold for the given
+    /// challenge.
+    VanishingCheckFailed { challenge: usize },
+    /// The FRI proof-of-work witness doesn't have enough leading zeros.
+    InvalidProofOfWork,
+    /// A Merkle proof of an initial oracle opening is invalid in the given query round.
+    InitialMerkleProofInvalid { query_round: usize, oracle: usize },
+    /// A Merkle proof of a FRI commit-phase opening is invalid in the given query round.
+    CommitPhaseMerkleProofInvalid { query_round: usize, step: usize },
+    /// In the given query round, the evaluation derived from the previous FRI step doesn't match
+    /// the one opened in this step.
+    FriConsistencyFailed { query_round: usize, step: usize },
+    /// In the given query round, the final polynomial sent by the prover doesn't match the
+    /// evaluation derived from the last FRI step.
+    FinalPolyMismatch { query_round: usize },
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ShapeMismatch(what) => write!(f, "Proof shape mismatch: {what}."),
+            Self::PublicInputsLengthMismatch { expected, actual } => write!(
+                f,
+                "Number of public inputs doesn't match circuit data: expected {expected}, got {actual}."
+            ),
+            Self::PublicInputsMismatch(what) => {
+                write!(f, "Public inputs don't match {what}.")
+            }
+            Self::VanishingCheckFailed { challenge } => write!(
+                f,
+                "Mismatch between evaluation and opening of quotient polynomial for challenge {challenge}."
+            ),
+            Self::InvalidProofOfWork => write!(f, "Invalid proof of work witness."),
+            Self::InitialMerkleProofInvalid {
+                query_round,
+                oracle,
+            } => write!(
+                f,
+                "Invalid Merkle proof for oracle {oracle} in query round {query_round}."
+            ),
+            Self::CommitPhaseMerkleProofInvalid { query_round, step } => write!(
+                f,
+                "Invalid Merkle proof for FRI step {step} in query round {query_round}."
+            ),
+            Self::FriConsistencyFailed { query_round, step } => write!(
+                f,
+                "Inconsistent FRI evaluation at step {step} in query round {query_round}."
+            ),
+            Self::FinalPolyMismatch { query_round } => write!(
+                f,
+                "Final polynomial evaluation is invalid in query round {query_round}."
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {}
+
+/// Returns `Err(VerificationError::ShapeMismatch(what))` unless `condition` holds.
+pub fn ensure_shape(condition: bool, what: &'static str) -> Result<(), VerificationError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(VerificationError::ShapeMismatch(what))
+    }
+}
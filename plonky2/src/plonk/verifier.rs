@@ -1,5 +1,3 @@
-use anyhow::{ensure, Result};
-
 use crate::field::extension::Extendable;
 use crate::field::types::Field;
 use crate::fri::verifier::verify_fri_proof;
@@ -11,12 +9,13 @@ use crate::plonk::proof::{Proof, ProofChallenges, ProofWithPublicInputs};
 use crate::plonk::validate_shape::validate_proof_with_pis_shape;
 use crate::plonk::vanishing_poly::eval_vanishing_poly;
 use crate::plonk::vars::EvaluationVars;
+use crate::plonk::verification_error::VerificationError;
 
 pub(crate) fn verify<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, const D: usize>(
     proof_with_pis: ProofWithPublicInputs<F, C, D>,
     verifier_data: &VerifierOnlyCircuitData<C, D>,
     common_data: &CommonCircuitData<F, D>,
-) -> Result<()> {
+) -> Result<(), VerificationError> {
     validate_proof_with_pis_shape(&proof_with_pis, common_data)?;
 
     let public_inputs_hash = proof_with_pis.get_public_inputs_hash();
@@ -24,7 +23,7 @@ pub(crate) fn verify<F: RichField + Extendable<D>, C: GenericConfig<D, F = F>, c
         public_inputs_hash,
         &verifier_data.circuit_digest,
         common_data,
-    )?;
+    );
 
     verify_with_challenges(
         proof_with_pis.proof,
@@ -45,7 +44,7 @@ pub(crate) fn verify_with_challenges<
     challenges: ProofChallenges<F, D>,
     verifier_data: &VerifierOnlyCircuitData<C, D>,
     common_data: &CommonCircuitData<F, D>,
-) -> Result<()> {
+) -> Result<(), VerificationError> {
     let local_constants = &proof.openings.constants;
     let local_wires = &proof.openings.wires;
     let vars = EvaluationVars {
@@ -87,7 +86,9 @@ pub(crate) fn verify_with_challenges<
         .chunks(common_data.quotient_degree_factor)
         .enumerate()
     {
-        ensure!(vanishing_polys_zeta[i] == z_h_zeta * reduce_with_powers(chunk, zeta_pow_deg));
+        if vanishing_polys_zeta[i] != z_h_zeta * reduce_with_powers(chunk, zeta_pow_deg) {
+            return Err(VerificationError::VanishingCheckFailed { challenge: i });
+        }
     }
 
     let merkle_caps = &[
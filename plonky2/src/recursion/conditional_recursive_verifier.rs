@@ -397,7 +397,7 @@ mod tests {
         builder.print_gate_counts(100);
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     fn init_logger() {
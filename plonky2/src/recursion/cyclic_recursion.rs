@@ -12,6 +12,7 @@ use crate::plonk::circuit_data::{
 };
 use crate::plonk::config::{AlgebraicHasher, GenericConfig};
 use crate::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
+use crate::plonk::verification_error::VerificationError;
 
 impl<C: GenericConfig<D>, const D: usize> VerifierOnlyCircuitData<C, D> {
     fn from_slice(slice: &[C::F], common_data: &CommonCircuitData<C::F, D>) -> Result<Self>
@@ -165,13 +166,22 @@ pub fn check_cyclic_proof_verifier_data<
     proof: &ProofWithPublicInputs<F, C, D>,
     verifier_data: &VerifierOnlyCircuitData<C, D>,
     common_data: &CommonCircuitData<F, D>,
-) -> Result<()>
+) -> Result<(), VerificationError>
 where
     C::Hasher: AlgebraicHasher<F>,
 {
-    let pis = VerifierOnlyCircuitData::<C, D>::from_slice(&proof.public_inputs, common_data)?;
-    ensure!(verifier_data.constants_sigmas_cap == pis.constants_sigmas_cap);
-    ensure!(verifier_data.circuit_digest == pis.circuit_digest);
+    let pis = VerifierOnlyCircuitData::<C, D>::from_slice(&proof.public_inputs, common_data)
+        .map_err(|_| VerificationError::PublicInputsMismatch("the verifier data layout"))?;
+    if verifier_data.constants_sigmas_cap != pis.constants_sigmas_cap {
+        return Err(VerificationError::PublicInputsMismatch(
+            "the constants and sigmas cap",
+        ));
+    }
+    if verifier_data.circuit_digest != pis.circuit_digest {
+        return Err(VerificationError::PublicInputsMismatch(
+            "the circuit digest",
+        ));
+    }
 
     Ok(())
 }
@@ -179,6 +189,7 @@ where
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use hashbrown::HashMap;
 
     use crate::field::extension::Extendable;
     use crate::field::types::{Field, PrimeField64};
@@ -190,6 +201,7 @@ mod tests {
     use crate::plonk::circuit_builder::CircuitBuilder;
     use crate::plonk::circuit_data::{CircuitConfig, CommonCircuitData};
     use crate::plonk::config::{AlgebraicHasher, GenericConfig, PoseidonGoldilocksConfig};
+    use crate::plonk::public_input_schema::PublicInputValue;
     use crate::recursion::cyclic_recursion::check_cyclic_proof_verifier_data;
     use crate::recursion::dummy_circuit::cyclic_base_proof;
 
@@ -341,7 +353,74 @@ mod tests {
         );
         assert_eq!(hash, expected_hash);
 
-        cyclic_circuit_data.verify(proof)
+        Ok(cyclic_circuit_data.verify(proof)?)
+    }
+
+    /// Uses cyclic recursion to count the number of layers, exposing the count as a named public
+    /// input.
+    #[test]
+    fn test_cyclic_recursion_named_public_input() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let one = builder.one();
+
+        let counter = builder.add_virtual_target();
+        builder.register_named_public_input("counter", counter);
+
+        let mut common_data = common_data_for_recursion::<F, C, D>();
+        let verifier_data_target = builder.add_verifier_data_public_inputs();
+        common_data.num_public_inputs = builder.num_public_inputs();
+
+        let condition = builder.add_virtual_bool_target_safe();
+        let inner_cyclic_proof_with_pis = builder.add_virtual_proof_with_pis::<C>(&common_data);
+        let inner_cyclic_counter = inner_cyclic_proof_with_pis.public_inputs[0];
+        let new_counter = builder.mul_add(condition.target, inner_cyclic_counter, one);
+        builder.connect(counter, new_counter);
+
+        builder.conditionally_verify_cyclic_proof_or_dummy::<C>(
+            condition,
+            &inner_cyclic_proof_with_pis,
+            &common_data,
+        )?;
+
+        let cyclic_circuit_data = builder.build::<C>();
+        assert_eq!(cyclic_circuit_data.common, common_data);
+
+        let mut pw = PartialWitness::new();
+        pw.set_bool_target(condition, false);
+        pw.set_proof_with_pis_target::<C, D>(
+            &inner_cyclic_proof_with_pis,
+            &cyclic_base_proof(
+                &common_data,
+                &cyclic_circuit_data.verifier_only,
+                HashMap::new(),
+            ),
+        );
+        pw.set_verifier_data_target(&verifier_data_target, &cyclic_circuit_data.verifier_only);
+        let proof = cyclic_circuit_data.prove(pw)?;
+        cyclic_circuit_data.verify(proof.clone())?;
+
+        let mut pw = PartialWitness::new();
+        pw.set_bool_target(condition, true);
+        pw.set_proof_with_pis_target(&inner_cyclic_proof_with_pis, &proof);
+        pw.set_verifier_data_target(&verifier_data_target, &cyclic_circuit_data.verifier_only);
+        let proof = cyclic_circuit_data.prove(pw)?;
+        check_cyclic_proof_verifier_data(
+            &proof,
+            &cyclic_circuit_data.verifier_only,
+            &cyclic_circuit_data.common,
+        )?;
+
+        assert_eq!(
+            proof.public_input("counter", &cyclic_circuit_data.common)?,
+            PublicInputValue::Target(F::TWO)
+        );
+
+        Ok(cyclic_circuit_data.verify(proof)?)
     }
 
     fn iterate_poseidon<F: RichField>(initial_state: [F; 4], n: usize) -> [F; 4] {
@@ -10,6 +10,7 @@ use crate::field::types::Field;
 pub(crate) mod context_tree;
 pub(crate) mod partial_products;
 
+pub mod prover_context;
 pub mod reducing;
 pub mod serialization;
 pub mod strided_view;
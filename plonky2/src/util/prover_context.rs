@@ -0,0 +1,134 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::fmt::{Display, Formatter};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, Result};
+
+/// A phase of a prover, as reported to the progress callback of a `ProverContext`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProverPhase {
+    /// Running the witness generators of a circuit, or generating the traces of a STARK.
+    WitnessGeneration,
+    /// Committing to the wire polynomials, or to the trace polynomials of a STARK.
+    WireCommitment,
+    /// Computing and committing to the permutation `Z` polynomials and partial products.
+    PermutationZs,
+    /// Computing and committing to the quotient polynomials.
+    Quotient,
+    /// Computing the openings and the FRI proof.
+    Fri,
+}
+
+/// A flag shared between the caller and a prover, which can be set from any thread to ask the
+/// prover to stop.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The error returned by a prover which was cancelled through its `CancellationToken`. It can be
+/// recovered from the returned `anyhow::Error` with `downcast_ref`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Proving was cancelled.")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Cancelled {}
+
+type ProgressCallback = Box<dyn Fn(ProverPhase) + Send + Sync>;
+
+/// Lets the caller of a prover monitor its progress and cancel it.
+///
+/// The prover calls `enter_phase` at the start of each phase, which fails with `Cancelled` if the
+/// token was cancelled. Cancellation is thus cooperative: a phase which has started runs to
+/// completion.
+#[derive(Default)]
+pub struct ProverContext {
+    progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl ProverContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `progress` at the start of each phase of the prover.
+    pub fn with_progress<P>(mut self, progress: P) -> Self
+    where
+        P: Fn(ProverPhase) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Stops the prover at the start of the next phase once `token` is cancelled.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .iter()
+            .any(CancellationToken::is_cancelled)
+    }
+
+    /// Called by provers at the start of `phase`. Fails with `Cancelled` if the prover should stop.
+    pub fn enter_phase(&self, phase: ProverPhase) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(anyhow!(Cancelled));
+        }
+        if let Some(progress) = &self.progress {
+            progress(phase);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn test_prover_context() {
+        let token = CancellationToken::new();
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let phases_clone = phases.clone();
+        let ctx = ProverContext::new()
+            .with_progress(move |phase| phases_clone.lock().unwrap().push(phase))
+            .with_cancellation(token.clone());
+
+        ctx.enter_phase(ProverPhase::WitnessGeneration).unwrap();
+        ctx.enter_phase(ProverPhase::WireCommitment).unwrap();
+        token.cancel();
+        let err = ctx.enter_phase(ProverPhase::PermutationZs).unwrap_err();
+        assert_eq!(err.downcast_ref::<Cancelled>(), Some(&Cancelled));
+        assert_eq!(
+            *phases.lock().unwrap(),
+            vec![ProverPhase::WitnessGeneration, ProverPhase::WireCommitment]
+        );
+    }
+}
@@ -68,7 +68,8 @@ pub trait Read {
         Ok(u32::from_le_bytes(buf))
     }
 
-    /// Reads a element from the field `F` with size less than `2^64` from `self.`
+    /// Reads a element from the field `F` with size less than `2^64` from `self.` Non-canonical
+    /// encodings are rejected.
     #[inline]
     fn read_field<F>(&mut self) -> IoResult<F>
     where
@@ -76,7 +77,11 @@ pub trait Read {
     {
         let mut buf = [0; size_of::<u64>()];
         self.read_exact(&mut buf)?;
-        Ok(F::from_canonical_u64(u64::from_le_bytes(buf)))
+        let n = u64::from_le_bytes(buf);
+        if n >= F::ORDER {
+            return Err(IoError);
+        }
+        Ok(F::from_canonical_u64(n))
     }
 
     /// Reads a vector of elements from the field `F` from `self`.
@@ -117,7 +122,7 @@ pub trait Read {
         (0..length).map(|_| self.read_field_ext::<F, D>()).collect()
     }
 
-    /// Reads a hash value from `self`.
+    /// Reads a hash value from `self`. Non-canonical encodings are rejected.
     #[inline]
     fn read_hash<F, H>(&mut self) -> IoResult<H::Hash>
     where
@@ -126,7 +131,11 @@ pub trait Read {
     {
         let mut buf = vec![0; H::HASH_SIZE];
         self.read_exact(&mut buf)?;
-        Ok(H::Hash::from_bytes(&buf))
+        let hash = H::Hash::from_bytes(&buf);
+        if hash.to_bytes() != buf {
+            return Err(IoError);
+        }
+        Ok(hash)
     }
 
     /// Reads a value of type [`MerkleCap`] from `self` with the given `cap_height`.
@@ -328,7 +337,8 @@ pub trait Read {
         })
     }
 
-    /// Reads a value of type [`ProofWithPublicInputs`] from `self` with `common_data`.
+    /// Reads a value of type [`ProofWithPublicInputs`] from `self` with `common_data`, rejecting
+    /// any trailing bytes.
     #[inline]
     fn read_proof_with_public_inputs<F, C, const D: usize>(
         &mut self,
@@ -340,7 +350,10 @@ pub trait Read {
         C: GenericConfig<D, F = F>,
     {
         let proof = self.read_proof(common_data)?;
-        let public_inputs = self.read_field_vec(self.remaining() / size_of::<u64>())?;
+        let public_inputs = self.read_field_vec(common_data.num_public_inputs)?;
+        if !self.is_empty() {
+            return Err(IoError);
+        }
         Ok(ProofWithPublicInputs {
             proof,
             public_inputs,
@@ -358,8 +371,12 @@ pub trait Read {
         C: GenericConfig<D, F = F>,
     {
         let config = &common_data.config;
+        let lde_size = common_data.fri_params.lde_size();
         let original_indices = (0..config.fri_config.num_query_rounds)
-            .map(|_| self.read_u32().map(|i| i as usize))
+            .map(|_| match self.read_u32()? as usize {
+                i if i < lde_size => Ok(i),
+                _ => Err(IoError),
+            })
             .collect::<Result<Vec<_>, _>>()?;
         let mut indices = original_indices.clone();
         indices.sort_unstable();
@@ -447,7 +464,8 @@ pub trait Read {
         })
     }
 
-    /// Reads a value of type [`CompressedProofWithPublicInputs`] from `self` with `common_data`.
+    /// Reads a value of type [`CompressedProofWithPublicInputs`] from `self` with `common_data`,
+    /// rejecting any trailing bytes.
     #[inline]
     fn read_compressed_proof_with_public_inputs<F, C, const D: usize>(
         &mut self,
@@ -459,7 +477,10 @@ pub trait Read {
         C: GenericConfig<D, F = F>,
     {
         let proof = self.read_compressed_proof(common_data)?;
-        let public_inputs = self.read_field_vec(self.remaining() / size_of::<u64>())?;
+        let public_inputs = self.read_field_vec(common_data.num_public_inputs)?;
+        if !self.is_empty() {
+            return Err(IoError);
+        }
         Ok(CompressedProofWithPublicInputs {
             proof,
             public_inputs,
@@ -16,6 +16,9 @@ pub struct TimingTree {
     exit_time: Option<Instant>,
     /// Any child scopes.
     children: Vec<TimingTree>,
+    /// The thread this scope ran on, as an index into the trees merged with `merge`. Zero is the
+    /// thread of the root.
+    thread: usize,
 }
 
 #[cfg(not(feature = "timing"))]
@@ -44,6 +47,7 @@ impl TimingTree {
             enter_time: Instant::now(),
             exit_time: None,
             children: vec![],
+            thread: 0,
         }
     }
 
@@ -96,6 +100,7 @@ impl TimingTree {
             enter_time: Instant::now(),
             exit_time: None,
             children: vec![],
+            thread: self.thread,
         })
     }
 
@@ -141,6 +146,99 @@ impl TimingTree {
                 .filter(|c| c.duration() >= min_delta)
                 .map(|c| c.filter(min_delta))
                 .collect(),
+            thread: self.thread,
+        }
+    }
+
+    /// Adds `other`, typically recorded on another thread, as a child of the deepest open scope
+    /// of this tree. Its scopes keep their own times, and get a new thread index so that trace
+    /// viewers show them on a separate track.
+    #[cfg(feature = "timing")]
+    pub fn merge(&mut self, mut other: Self) {
+        other.set_thread(self.max_thread() + 1);
+        self.merge_helper(other);
+    }
+
+    #[cfg(feature = "timing")]
+    fn merge_helper(&mut self, other: Self) {
+        if let Some(last_child) = self.children.last_mut() {
+            if last_child.is_open() {
+                last_child.merge_helper(other);
+                return;
+            }
+        }
+        self.children.push(other);
+    }
+
+    #[cfg(feature = "timing")]
+    fn max_thread(&self) -> usize {
+        self.children
+            .iter()
+            .map(|c| c.max_thread())
+            .fold(self.thread, usize::max)
+    }
+
+    #[cfg(feature = "timing")]
+    fn set_thread(&mut self, thread: usize) {
+        self.thread = thread;
+        for child in &mut self.children {
+            child.set_thread(thread);
+        }
+    }
+
+    /// Serializes the tree as JSON. Each scope is an object with its `name`, its `start` time
+    /// relative to the root and its `duration`, both in seconds, the `thread` it ran on and its
+    /// `children`. Scopes which are still open are timed up to now.
+    #[cfg(feature = "timing")]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.to_json_helper(self.enter_time, &mut json);
+        json
+    }
+
+    #[cfg(feature = "timing")]
+    fn to_json_helper(&self, root_enter_time: Instant, json: &mut String) {
+        json.push_str(&format!(
+            "{{\"name\":{},\"start\":{},\"duration\":{},\"thread\":{},\"children\":[",
+            json_string(&self.name),
+            self.enter_time
+                .saturating_duration_since(root_enter_time)
+                .as_secs_f64(),
+            self.duration().as_secs_f64(),
+            self.thread,
+        ));
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            child.to_json_helper(root_enter_time, json);
+        }
+        json.push_str("]}");
+    }
+
+    /// Serializes the tree in the Chrome `trace_event` format, which can be loaded into
+    /// `chrome://tracing` or Perfetto. Each scope is a complete event, with timestamps in
+    /// microseconds relative to the root.
+    #[cfg(feature = "timing")]
+    pub fn to_chrome_trace(&self) -> String {
+        let mut events = Vec::new();
+        self.chrome_trace_events(self.enter_time, &mut events);
+        format!("{{\"traceEvents\":[{}]}}", events.join(","))
+    }
+
+    #[cfg(feature = "timing")]
+    fn chrome_trace_events(&self, root_enter_time: Instant, events: &mut Vec<String>) {
+        events.push(format!(
+            "{{\"name\":{},\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":0,\"tid\":{}}}",
+            json_string(&self.name),
+            self.enter_time
+                .saturating_duration_since(root_enter_time)
+                .as_micros(),
+            self.duration().as_micros(),
+            self.thread,
+        ));
+        for child in &self.children {
+            child.chrome_trace_events(root_enter_time, events);
         }
     }
 
@@ -173,6 +271,24 @@ impl TimingTree {
     }
 }
 
+/// Quotes and escapes `s` as a JSON string.
+#[cfg(feature = "timing")]
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// Creates a named scope; useful for debugging.
 #[macro_export]
 macro_rules! timed {
@@ -190,3 +306,50 @@ macro_rules! timed {
         res
     }};
 }
+
+#[cfg(all(test, feature = "timing"))]
+mod tests {
+    use log::Level;
+
+    use super::*;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a \"b\" \\ c\n\t"), r#""a \"b\" \\ c\n\u0009""#);
+    }
+
+    #[test]
+    fn test_export() {
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        timed!(timing, "commit", {
+            timing.push("hash", Level::Debug);
+            timing.pop();
+        });
+
+        let mut worker = TimingTree::new("worker", Level::Debug);
+        timed!(worker, "generate \"trace\"", ());
+        worker.pop();
+        timing.merge(worker);
+        timing.pop();
+
+        let json = timing.to_json();
+        assert!(json.starts_with(r#"{"name":"prove","start":0,"duration":"#));
+        for expected in [
+            r#"{"name":"commit","#,
+            r#"{"name":"hash","#,
+            r#"{"name":"worker","#,
+            r#"{"name":"generate \"trace\"","#,
+            r#""thread":1,"children":[]}"#,
+        ] {
+            assert!(json.contains(expected), "{expected} not in {json}");
+        }
+        assert_eq!(json.matches('{').count(), 5);
+        assert_eq!(json.matches('}').count(), 5);
+
+        let trace = timing.to_chrome_trace();
+        assert!(trace.starts_with(r#"{"traceEvents":[{"name":"prove","ph":"X","ts":0,"#));
+        assert_eq!(trace.matches(r#""ph":"X""#).count(), 5);
+        assert_eq!(trace.matches(r#""tid":0}"#).count(), 3);
+        assert_eq!(trace.matches(r#""tid":1}"#).count(), 2);
+    }
+}
@@ -124,6 +124,7 @@ impl<F: RichField + Extendable<D>, const D: usize> Stark<F, D> for FibonacciStar
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use maybe_rayon::ThreadPool;
     use plonky2::field::extension::Extendable;
     use plonky2::field::types::Field;
     use plonky2::hash::hash_types::RichField;
@@ -133,12 +134,13 @@ mod tests {
     use plonky2::plonk::config::{
         AlgebraicHasher, GenericConfig, Hasher, PoseidonGoldilocksConfig,
     };
+    use plonky2::util::prover_context::{CancellationToken, Cancelled, ProverContext, ProverPhase};
     use plonky2::util::timing::TimingTree;
 
     use crate::config::StarkConfig;
     use crate::fibonacci_stark::FibonacciStark;
     use crate::proof::StarkProofWithPublicInputs;
-    use crate::prover::prove;
+    use crate::prover::{prove, prove_with_context, prove_with_pool};
     use crate::recursive_verifier::{
         add_virtual_stark_proof_with_pis, set_stark_proof_with_pis_target,
         verify_stark_proof_circuit,
@@ -171,7 +173,67 @@ mod tests {
             &mut TimingTree::default(),
         )?;
 
-        verify_stark_proof(stark, proof, &config)
+        Ok(verify_stark_proof(stark, proof, &config)?)
+    }
+
+    #[test]
+    fn test_fibonacci_stark_with_pool() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = FibonacciStark<F, D>;
+
+        let config = StarkConfig::standard_fast_config();
+        let num_rows = 1 << 5;
+        let public_inputs = [F::ZERO, F::ONE, fibonacci(num_rows - 1, F::ZERO, F::ONE)];
+        let stark = S::new(num_rows);
+        let trace = stark.generate_trace(public_inputs[0], public_inputs[1]);
+        let pool = ThreadPool::new(1)?;
+        let proof = prove_with_pool::<F, C, S, D>(
+            stark,
+            &config,
+            trace,
+            public_inputs,
+            &mut TimingTree::default(),
+            &pool,
+        )?;
+
+        Ok(verify_stark_proof(stark, proof, &config)?)
+    }
+
+    #[test]
+    fn test_fibonacci_stark_cancelled() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type S = FibonacciStark<F, D>;
+
+        let config = StarkConfig::standard_fast_config();
+        let num_rows = 1 << 5;
+        let public_inputs = [F::ZERO, F::ONE, fibonacci(num_rows - 1, F::ZERO, F::ONE)];
+        let stark = S::new(num_rows);
+        let trace = stark.generate_trace(public_inputs[0], public_inputs[1]);
+
+        // Cancel once the quotient is being computed, so that the prover stops before FRI.
+        let token = CancellationToken::new();
+        let token_clone = token.clone();
+        let ctx = ProverContext::new()
+            .with_progress(move |phase| {
+                if phase == ProverPhase::Quotient {
+                    token_clone.cancel();
+                }
+            })
+            .with_cancellation(token);
+        let err = prove_with_context::<F, C, S, D>(
+            stark,
+            &config,
+            trace,
+            public_inputs,
+            &mut TimingTree::default(),
+            &ctx,
+        )
+        .unwrap_err();
+        assert_eq!(err.downcast_ref::<Cancelled>(), Some(&Cancelled));
     }
 
     #[test]
@@ -256,7 +318,7 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     fn init_logger() {
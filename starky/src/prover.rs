@@ -15,6 +15,7 @@ use plonky2::hash::hash_types::RichField;
 use plonky2::iop::challenger::Challenger;
 use plonky2::plonk::config::{GenericConfig, Hasher};
 use plonky2::timed;
+use plonky2::util::prover_context::{ProverContext, ProverPhase};
 use plonky2::util::timing::TimingTree;
 use plonky2::util::{log2_ceil, log2_strict, transpose};
 
@@ -36,6 +37,34 @@ pub fn prove<F, C, S, const D: usize>(
     public_inputs: [F; S::PUBLIC_INPUTS],
     timing: &mut TimingTree,
 ) -> Result<StarkProofWithPublicInputs<F, C, D>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    S: Stark<F, D>,
+    [(); S::COLUMNS]:,
+    [(); S::PUBLIC_INPUTS]:,
+    [(); C::Hasher::HASH_SIZE]:,
+{
+    prove_with_context(
+        stark,
+        config,
+        trace_poly_values,
+        public_inputs,
+        timing,
+        &ProverContext::default(),
+    )
+}
+
+/// Like `prove`, but reports the progress of the prover to `ctx`, and stops with a `Cancelled`
+/// error between phases if `ctx` is cancelled.
+pub fn prove_with_context<F, C, S, const D: usize>(
+    stark: S,
+    config: &StarkConfig,
+    trace_poly_values: Vec<PolynomialValues<F>>,
+    public_inputs: [F; S::PUBLIC_INPUTS],
+    timing: &mut TimingTree,
+    ctx: &ProverContext,
+) -> Result<StarkProofWithPublicInputs<F, C, D>>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
@@ -54,6 +83,7 @@ where
         "FRI total reduction arity is too large.",
     );
 
+    ctx.enter_phase(ProverPhase::WireCommitment)?;
     let trace_commitment = timed!(
         timing,
         "compute trace commitment",
@@ -74,6 +104,9 @@ where
     challenger.observe_cap(&trace_cap);
 
     // Permutation arguments.
+    if stark.uses_permutation_args() {
+        ctx.enter_phase(ProverPhase::PermutationZs)?;
+    }
     let permutation_zs_commitment_challenges = stark.uses_permutation_args().then(|| {
         let permutation_challenge_sets = get_n_permutation_challenge_sets(
             &mut challenger,
@@ -111,6 +144,7 @@ where
         challenger.observe_cap(cap);
     }
 
+    ctx.enter_phase(ProverPhase::Quotient)?;
     let alphas = challenger.get_n_challenges(config.num_challenges);
     let quotient_polys = compute_quotient_polys::<F, <F as Packable>::Packing, C, S, D>(
         &stark,
@@ -155,6 +189,7 @@ where
         zeta.exp_power_of_2(degree_bits) != F::Extension::ONE,
         "Opening point is in the subgroup."
     );
+    ctx.enter_phase(ProverPhase::Fri)?;
     let openings = StarkOpeningSet::new(
         zeta,
         g,
@@ -194,6 +229,27 @@ where
     })
 }
 
+/// Like `prove`, but runs every parallel step of the prover in `pool` rather than in the global
+/// thread pool.
+pub fn prove_with_pool<F, C, S, const D: usize>(
+    stark: S,
+    config: &StarkConfig,
+    trace_poly_values: Vec<PolynomialValues<F>>,
+    public_inputs: [F; S::PUBLIC_INPUTS],
+    timing: &mut TimingTree,
+    pool: &ThreadPool,
+) -> Result<StarkProofWithPublicInputs<F, C, D>>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F>,
+    S: Stark<F, D> + Send,
+    [(); S::COLUMNS]:,
+    [(); S::PUBLIC_INPUTS]:,
+    [(); C::Hasher::HASH_SIZE]:,
+{
+    pool.install(|| prove(stark, config, trace_poly_values, public_inputs, timing))
+}
+
 /// Computes the quotient polynomials `(sum alpha^i C_i(x)) / Z_H(x)` for `alpha` in `alphas`,
 /// where the `C_i`s are the Stark constraints.
 fn compute_quotient_polys<'a, F, P, C, S, const D: usize>(
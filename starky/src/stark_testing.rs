@@ -153,7 +153,7 @@ where
 
     let data = builder.build::<C>();
     let proof = data.prove(pw)?;
-    data.verify(proof)
+    Ok(data.verify(proof)?)
 }
 
 fn random_low_degree_matrix<F: Field>(num_polys: usize, rate_bits: usize) -> Vec<Vec<F>> {
@@ -1,7 +1,6 @@
 use alloc::vec::Vec;
 use core::iter::once;
 
-use anyhow::{anyhow, ensure, Result};
 use itertools::Itertools;
 use plonky2::field::extension::{Extendable, FieldExtension};
 use plonky2::field::types::Field;
@@ -9,6 +8,7 @@ use plonky2::fri::verifier::verify_fri_proof;
 use plonky2::hash::hash_types::RichField;
 use plonky2::plonk::config::{GenericConfig, Hasher};
 use plonky2::plonk::plonk_common::reduce_with_powers;
+use plonky2::plonk::verification_error::{ensure_shape, VerificationError};
 
 use crate::config::StarkConfig;
 use crate::constraint_consumer::ConstraintConsumer;
@@ -27,13 +27,13 @@ pub fn verify_stark_proof<
     stark: S,
     proof_with_pis: StarkProofWithPublicInputs<F, C, D>,
     config: &StarkConfig,
-) -> Result<()>
+) -> Result<(), VerificationError>
 where
     [(); S::COLUMNS]:,
     [(); S::PUBLIC_INPUTS]:,
     [(); C::Hasher::HASH_SIZE]:,
 {
-    ensure!(proof_with_pis.public_inputs.len() == S::PUBLIC_INPUTS);
+    check_public_inputs_len::<F, S, D>(proof_with_pis.public_inputs.len())?;
     let degree_bits = proof_with_pis.proof.recover_degree_bits(config);
     let challenges = proof_with_pis.get_challenges(&stark, config, degree_bits);
     verify_stark_proof_with_challenges(stark, proof_with_pis, challenges, degree_bits, config)
@@ -50,7 +50,7 @@ pub(crate) fn verify_stark_proof_with_challenges<
     challenges: StarkProofChallenges<F, D>,
     degree_bits: usize,
     config: &StarkConfig,
-) -> Result<()>
+) -> Result<(), VerificationError>
 where
     [(); S::COLUMNS]:,
     [(); S::PUBLIC_INPUTS]:,
@@ -119,10 +119,9 @@ where
         .chunks(stark.quotient_degree_factor())
         .enumerate()
     {
-        ensure!(
-            vanishing_polys_zeta[i] == z_h_zeta * reduce_with_powers(chunk, zeta_pow_deg),
-            "Mismatch between evaluation and opening of quotient polynomial"
-        );
+        if vanishing_polys_zeta[i] != z_h_zeta * reduce_with_powers(chunk, zeta_pow_deg) {
+            return Err(VerificationError::VanishingCheckFailed { challenge: i });
+        }
     }
 
     let merkle_caps = once(proof.trace_cap)
@@ -150,7 +149,7 @@ fn validate_proof_shape<F, C, S, const D: usize>(
     stark: &S,
     proof_with_pis: &StarkProofWithPublicInputs<F, C, D>,
     config: &StarkConfig,
-) -> anyhow::Result<()>
+) -> Result<(), VerificationError>
 where
     F: RichField + Extendable<D>,
     C: GenericConfig<D, F = F>,
@@ -182,42 +181,80 @@ where
         quotient_polys,
     } = openings;
 
-    ensure!(public_inputs.len() == S::PUBLIC_INPUTS);
+    check_public_inputs_len::<F, S, D>(public_inputs.len())?;
 
     let fri_params = config.fri_params(degree_bits);
     let cap_height = fri_params.config.cap_height;
     let num_zs = stark.num_permutation_batches(config);
 
-    ensure!(trace_cap.height() == cap_height);
-    ensure!(quotient_polys_cap.height() == cap_height);
+    ensure_shape(trace_cap.len() == 1 << cap_height, "trace cap height")?;
+    ensure_shape(
+        quotient_polys_cap.len() == 1 << cap_height,
+        "quotient polynomials cap height",
+    )?;
 
-    ensure!(local_values.len() == S::COLUMNS);
-    ensure!(next_values.len() == S::COLUMNS);
-    ensure!(quotient_polys.len() == stark.num_quotient_polys(config));
+    ensure_shape(
+        local_values.len() == S::COLUMNS,
+        "number of local values openings",
+    )?;
+    ensure_shape(
+        next_values.len() == S::COLUMNS,
+        "number of next values openings",
+    )?;
+    ensure_shape(
+        quotient_polys.len() == stark.num_quotient_polys(config),
+        "number of quotient polynomials openings",
+    )?;
 
     if stark.uses_permutation_args() {
         let permutation_zs_cap = permutation_zs_cap
             .as_ref()
-            .ok_or_else(|| anyhow!("Missing Zs cap"))?;
+            .ok_or(VerificationError::ShapeMismatch("missing Zs cap"))?;
         let permutation_zs = permutation_zs
             .as_ref()
-            .ok_or_else(|| anyhow!("Missing permutation_zs"))?;
-        let permutation_zs_next = permutation_zs_next
-            .as_ref()
-            .ok_or_else(|| anyhow!("Missing permutation_zs_next"))?;
+            .ok_or(VerificationError::ShapeMismatch("missing permutation_zs"))?;
+        let permutation_zs_next =
+            permutation_zs_next
+                .as_ref()
+                .ok_or(VerificationError::ShapeMismatch(
+                    "missing permutation_zs_next",
+                ))?;
 
-        ensure!(permutation_zs_cap.height() == cap_height);
-        ensure!(permutation_zs.len() == num_zs);
-        ensure!(permutation_zs_next.len() == num_zs);
+        ensure_shape(permutation_zs_cap.len() == 1 << cap_height, "Zs cap height")?;
+        ensure_shape(
+            permutation_zs.len() == num_zs,
+            "number of permutation Zs openings",
+        )?;
+        ensure_shape(
+            permutation_zs_next.len() == num_zs,
+            "number of next permutation Zs openings",
+        )?;
     } else {
-        ensure!(permutation_zs_cap.is_none());
-        ensure!(permutation_zs.is_none());
-        ensure!(permutation_zs_next.is_none());
+        ensure_shape(permutation_zs_cap.is_none(), "unexpected Zs cap")?;
+        ensure_shape(permutation_zs.is_none(), "unexpected permutation_zs")?;
+        ensure_shape(
+            permutation_zs_next.is_none(),
+            "unexpected permutation_zs_next",
+        )?;
     }
 
     Ok(())
 }
 
+fn check_public_inputs_len<F, S, const D: usize>(len: usize) -> Result<(), VerificationError>
+where
+    F: RichField + Extendable<D>,
+    S: Stark<F, D>,
+{
+    if len != S::PUBLIC_INPUTS {
+        return Err(VerificationError::PublicInputsLengthMismatch {
+            expected: S::PUBLIC_INPUTS,
+            actual: len,
+        });
+    }
+    Ok(())
+}
+
 /// Evaluate the Lagrange polynomials `L_0` and `L_(n-1)` at a point `x`.
 /// `L_0(x) = (x^n - 1)/(n * (x - 1))`
 /// `L_(n-1)(x) = (x^n - 1)/(n * (g * x - 1))`, with `g` the first element of the subgroup.
@@ -241,20 +278,19 @@ fn check_permutation_options<
     stark: &S,
     proof_with_pis: &StarkProofWithPublicInputs<F, C, D>,
     challenges: &StarkProofChallenges<F, D>,
-) -> Result<()> {
+) -> Result<(), VerificationError> {
     let options_is_some = [
         proof_with_pis.proof.permutation_zs_cap.is_some(),
         proof_with_pis.proof.openings.permutation_zs.is_some(),
         proof_with_pis.proof.openings.permutation_zs_next.is_some(),
         challenges.permutation_challenge_sets.is_some(),
     ];
-    ensure!(
+    ensure_shape(
         options_is_some
             .into_iter()
             .all(|b| b == stark.uses_permutation_args()),
-        "Permutation data doesn't match with Stark configuration."
-    );
-    Ok(())
+        "permutation data doesn't match with Stark configuration",
+    )
 }
 
 #[cfg(test)]
@@ -4,19 +4,63 @@ use core::marker::PhantomData;
 
 use plonky2::field::extension::Extendable;
 use plonky2::hash::hash_types::RichField;
+use plonky2::iop::circuit_type::CircuitType;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
-use plonky2::iop::target::Target;
-use plonky2::iop::witness::{PartitionWitness, Witness};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::public_input_schema::PublicInputType;
 
+use crate::gadgets::range_check::range_check_u32_circuit;
 use crate::gates::add_many_u32::U32AddManyGate;
 use crate::gates::arithmetic_u32::U32ArithmeticGate;
+use crate::gates::bitwise_u32::U32BitwiseGate;
 use crate::gates::subtraction_u32::U32SubtractionGate;
 use crate::witness::GeneratedValuesU32;
 
 #[derive(Clone, Copy, Debug)]
 pub struct U32Target(pub Target);
 
+impl<F: RichField> CircuitType<F> for U32Target {
+    type Native = u32;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        let x = builder.add_virtual_u32_target();
+        range_check_u32_circuit(builder, vec![x]);
+        x
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &u32) {
+        witness.set_target(self.0, F::from_canonical_u32(*value));
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> u32 {
+        witness.get_target(self.0).to_canonical_u64() as u32
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect_u32(*x, *y);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        U32Target(builder.select(b, x.0, y.0))
+    }
+}
+
 pub trait CircuitBuilderU32<F: RichField + Extendable<D>, const D: usize> {
     fn add_virtual_u32_target(&mut self) -> U32Target;
 
@@ -33,6 +77,9 @@ pub trait CircuitBuilderU32<F: RichField + Extendable<D>, const D: usize> {
 
     fn assert_zero_u32(&mut self, x: U32Target);
 
+    /// Registers `x` as a public input named `name`, recorded in the public input schema.
+    fn register_named_public_u32(&mut self, name: &str, x: U32Target);
+
     /// Checks for special cases where the value of
     /// `x * y + z`
     /// can be determined without adding a `U32ArithmeticGate`.
@@ -60,6 +107,44 @@ pub trait CircuitBuilderU32<F: RichField + Extendable<D>, const D: usize> {
 
     // Returns x - y - borrow, as a pair (result, borrow), where borrow is 0 or 1 depending on whether borrowing from the next digit is required (iff y + borrow > x).
     fn sub_u32(&mut self, x: U32Target, y: U32Target, borrow: U32Target) -> (U32Target, U32Target);
+
+    /// Returns `(x / y, x % y)`, constraining `y` to be nonzero. This also range-checks `x`.
+    fn div_rem_u32(&mut self, x: U32Target, y: U32Target) -> (U32Target, U32Target);
+
+    /// Returns `(x & y, x ^ y)`. This also range-checks `x` and `y`.
+    fn and_xor_u32(&mut self, x: U32Target, y: U32Target) -> (U32Target, U32Target);
+
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn or_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target;
+
+    /// Returns `x << shift`, truncated to 32 bits. `shift` must be less than 32.
+    fn shl_u32(&mut self, x: U32Target, shift: usize) -> U32Target;
+
+    /// Returns `x >> shift`. `shift` must be less than 32.
+    fn shr_u32(&mut self, x: U32Target, shift: usize) -> U32Target;
+
+    /// Returns `x` rotated left by `shift` bits. `shift` must be less than 32.
+    fn rotl_u32(&mut self, x: U32Target, shift: usize) -> U32Target;
+
+    /// Returns `x` rotated right by `shift` bits. `shift` must be less than 32.
+    fn rotr_u32(&mut self, x: U32Target, shift: usize) -> U32Target;
+
+    /// Like `shl_u32`, with a shift determined in the circuit, constrained to be less than 32.
+    fn shl_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target;
+
+    /// Like `shr_u32`, with a shift determined in the circuit, constrained to be less than 32.
+    fn shr_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target;
+
+    /// Like `rotl_u32`, with a shift determined in the circuit, constrained to be less than 32.
+    fn rotl_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target;
+
+    /// Like `rotr_u32`, with a shift determined in the circuit, constrained to be less than 32.
+    fn rotr_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target;
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderU32<F, D>
@@ -97,6 +182,10 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderU32<F, D>
         self.assert_zero(x.0)
     }
 
+    fn register_named_public_u32(&mut self, name: &str, x: U32Target) {
+        self.register_named_public_inputs(name, PublicInputType::U32, &[x.0])
+    }
+
     /// Checks for special cases where the value of
     /// `x * y + z`
     /// can be determined without adding a `U32ArithmeticGate`.
@@ -228,6 +317,141 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderU32<F, D>
 
         (output_result, output_borrow)
     }
+
+    fn div_rem_u32(&mut self, x: U32Target, y: U32Target) -> (U32Target, U32Target) {
+        let div = U32Target::add_virtual(self);
+        let rem = U32Target::add_virtual(self);
+        self.add_simple_generator(DivRemU32Generator::<F, D> {
+            x,
+            y,
+            div,
+            rem,
+            _phantom: PhantomData,
+        });
+
+        // As `div` and `rem` are range-checked, `div * y + rem` can't wrap around.
+        let (low, high) = self.mul_add_u32(div, y, rem);
+        self.connect_u32(low, x);
+        self.assert_zero_u32(high);
+
+        // `rem - y` borrows iff `rem < y`, which also rules out `y == 0`.
+        let zero = self.zero_u32();
+        let (_, borrow) = self.sub_u32(rem, y, zero);
+        self.assert_one(borrow.0);
+
+        (div, rem)
+    }
+
+    fn and_xor_u32(&mut self, x: U32Target, y: U32Target) -> (U32Target, U32Target) {
+        let gate = U32BitwiseGate::<F, D>::new_from_config(&self.config);
+        let (row, copy) = self.find_slot(gate, &[], &[]);
+
+        self.connect(Target::wire(row, gate.wire_ith_input_x(copy)), x.0);
+        self.connect(Target::wire(row, gate.wire_ith_input_y(copy)), y.0);
+
+        let output_and = U32Target(Target::wire(row, gate.wire_ith_output_and(copy)));
+        let output_xor = U32Target(Target::wire(row, gate.wire_ith_output_xor(copy)));
+
+        (output_and, output_xor)
+    }
+
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        self.and_xor_u32(x, y).0
+    }
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        self.and_xor_u32(x, y).1
+    }
+
+    fn or_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        // `x | y == (x & y) + (x ^ y)`, as the two terms have no bits in common.
+        let (and, xor) = self.and_xor_u32(x, y);
+        U32Target(self.add(and.0, xor.0))
+    }
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target {
+        let u32_max = self.constant_u32(u32::MAX);
+        U32Target(self.sub(u32_max.0, x.0))
+    }
+
+    fn shl_u32(&mut self, x: U32Target, shift: usize) -> U32Target {
+        assert!(shift < 32, "Shift {shift} is too large");
+        let multiplier = self.constant_u32(1 << shift);
+        self.mul_u32(x, multiplier).0
+    }
+
+    fn shr_u32(&mut self, x: U32Target, shift: usize) -> U32Target {
+        assert!(shift < 32, "Shift {shift} is too large");
+        if shift == 0 {
+            return x;
+        }
+        let multiplier = self.constant_u32(1 << (32 - shift));
+        self.mul_u32(x, multiplier).1
+    }
+
+    fn rotl_u32(&mut self, x: U32Target, shift: usize) -> U32Target {
+        assert!(shift < 32, "Shift {shift} is too large");
+        if shift == 0 {
+            return x;
+        }
+        let multiplier = self.constant_u32(1 << shift);
+        let (low, high) = self.mul_u32(x, multiplier);
+        // The two halves have no bits in common.
+        U32Target(self.add(low.0, high.0))
+    }
+
+    fn rotr_u32(&mut self, x: U32Target, shift: usize) -> U32Target {
+        assert!(shift < 32, "Shift {shift} is too large");
+        self.rotl_u32(x, (32 - shift) % 32)
+    }
+
+    fn shl_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target {
+        let multiplier = pow2_circuit(self, shift, false);
+        self.mul_u32(x, multiplier).0
+    }
+
+    fn shr_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target {
+        let multiplier = pow2_circuit(self, shift, true);
+        self.mul_u32(x, multiplier).1
+    }
+
+    fn rotl_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target {
+        let multiplier = pow2_circuit(self, shift, false);
+        let (low, high) = self.mul_u32(x, multiplier);
+        U32Target(self.add(low.0, high.0))
+    }
+
+    fn rotr_u32_variable(&mut self, x: U32Target, shift: U32Target) -> U32Target {
+        let multiplier = pow2_circuit(self, shift, true);
+        let (low, high) = self.mul_u32(x, multiplier);
+        U32Target(self.add(low.0, high.0))
+    }
+}
+
+/// Returns `2^shift`, or `2^(32 - shift)` if `complement` is set, constraining `shift` to be less
+/// than 32. In the latter case, the result may be `2^32`, which `U32ArithmeticGate` still handles
+/// correctly as a multiplicand since the product of a `u32` with it fits in 64 bits.
+fn pow2_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    shift: U32Target,
+    complement: bool,
+) -> U32Target {
+    let bits = builder.split_le(shift.0, 5);
+    let mut result = builder.constant(if complement { F::TWO } else { F::ONE });
+    for (i, bit) in bits.into_iter().enumerate() {
+        // The factor is `2^(2^i)` if the bit is set, and one otherwise. For the complement, we use
+        // the bits of `31 - shift`, which are the negated bits of `shift`, and multiply by two.
+        let power = F::from_canonical_u64(1 << (1 << i));
+        let factor = if complement {
+            let power_target = builder.constant(power);
+            builder.mul_const_add(F::ONE - power, bit.target, power_target)
+        } else {
+            let one = builder.one();
+            builder.mul_const_add(power - F::ONE, bit.target, one)
+        };
+        result = builder.mul(result, factor);
+    }
+    U32Target(result)
 }
 
 #[derive(Debug)]
@@ -256,9 +480,36 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
     }
 }
 
+#[derive(Debug)]
+struct DivRemU32Generator<F: RichField + Extendable<D>, const D: usize> {
+    x: U32Target,
+    y: U32Target,
+    div: U32Target,
+    rem: U32Target,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F> for DivRemU32Generator<F, D> {
+    fn dependencies(&self) -> Vec<Target> {
+        vec![self.x.0, self.y.0]
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let x = self.x.get_witness(witness);
+        let y = self.y.get_witness(witness);
+        // Division by zero makes the circuit unsatisfiable, so any values will do.
+        let div = x.checked_div(y).unwrap_or(0);
+        let rem = x.checked_rem(y).unwrap_or(0);
+
+        out_buffer.set_u32_target(self.div, div);
+        out_buffer.set_u32_target(self.rem, rem);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use plonky2::field::types::Field;
     use plonky2::iop::witness::PartialWitness;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
@@ -266,6 +517,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::witness::WitnessU32;
 
     #[test]
     pub fn test_add_many_u32s() -> Result<()> {
@@ -298,6 +550,106 @@ mod tests {
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
-        data.verify(proof)
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    pub fn test_bitwise_u32() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let mut rng = OsRng;
+        let x_value: u32 = rng.gen();
+        let y_value: u32 = rng.gen();
+        let x = builder.add_virtual_u32_target();
+        let y = builder.add_virtual_u32_target();
+        let mut outputs = vec![
+            builder.and_u32(x, y),
+            builder.xor_u32(x, y),
+            builder.or_u32(x, y),
+            builder.not_u32(x),
+        ];
+        let mut expected = vec![
+            x_value & y_value,
+            x_value ^ y_value,
+            x_value | y_value,
+            !x_value,
+        ];
+
+        let shifts = [0, 1, 13, 31];
+        let mut pw = PartialWitness::new();
+        for shift in shifts {
+            let shift_target = builder.add_virtual_u32_target();
+            pw.set_u32_target(shift_target, shift as u32);
+            outputs.extend([
+                builder.shl_u32(x, shift),
+                builder.shr_u32(x, shift),
+                builder.rotl_u32(x, shift),
+                builder.rotr_u32(x, shift),
+                builder.shl_u32_variable(x, shift_target),
+                builder.shr_u32_variable(x, shift_target),
+                builder.rotl_u32_variable(x, shift_target),
+                builder.rotr_u32_variable(x, shift_target),
+            ]);
+            let native = [
+                x_value << shift,
+                x_value >> shift,
+                x_value.rotate_left(shift as u32),
+                x_value.rotate_right(shift as u32),
+            ];
+            expected.extend(native);
+            expected.extend(native);
+        }
+        for output in outputs {
+            builder.register_public_input(output.0);
+        }
+        pw.set_u32_target(x, x_value);
+        pw.set_u32_target(y, y_value);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        let expected: Vec<F> = expected.into_iter().map(F::from_canonical_u32).collect();
+        assert_eq!(proof.public_inputs, expected);
+        Ok(data.verify(proof)?)
+    }
+
+    #[test]
+    pub fn test_div_rem_u32() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let mut rng = OsRng;
+        let pairs = [
+            (rng.gen(), rng.gen_range(1..=u32::MAX)),
+            (rng.gen(), rng.gen_range(1..1 << 16)),
+            (u32::MAX, 1),
+            (7, u32::MAX),
+        ];
+        let mut pw = PartialWitness::new();
+        let mut expected = Vec::new();
+        for (x_value, y_value) in pairs {
+            let x = builder.add_virtual_u32_target();
+            let y = builder.add_virtual_u32_target();
+            let (div, rem) = builder.div_rem_u32(x, y);
+            builder.register_public_input(div.0);
+            builder.register_public_input(rem.0);
+            pw.set_u32_target(x, x_value);
+            pw.set_u32_target(y, y_value);
+            expected.extend([x_value / y_value, x_value % y_value]);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        let expected: Vec<F> = expected.into_iter().map(F::from_canonical_u32).collect();
+        assert_eq!(proof.public_inputs, expected);
+        Ok(data.verify(proof)?)
     }
 }
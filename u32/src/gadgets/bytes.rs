@@ -0,0 +1,408 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::U32Target;
+
+/// The number of bytes packed into each field element by `bytes_to_targets_le` and
+/// `bytes_to_targets_be`. Seven bytes always fit in a Goldilocks element, so packing is injective.
+pub const BYTES_PER_TARGET: usize = 7;
+
+/// A target holding a byte. It is only range-checked if created by one of the methods documented
+/// to do so.
+#[derive(Clone, Copy, Debug)]
+pub struct U8Target(pub Target);
+
+impl<F: RichField> CircuitType<F> for U8Target {
+    type Native = u8;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        let x = builder.add_virtual_u8_target();
+        builder.range_check_u8(x);
+        x
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &u8) {
+        witness.set_target(self.0, F::from_canonical_u8(*value));
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> u8 {
+        witness.get_target(self.0).to_canonical_u64() as u8
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        builder.connect(x.0, y.0);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        U8Target(builder.select(b, x.0, y.0))
+    }
+}
+
+/// A byte string of a length fixed when building the circuit.
+#[derive(Clone, Debug)]
+pub struct ByteArrayTarget(pub Vec<U8Target>);
+
+impl ByteArrayTarget {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn set_witness<F: RichField, W: WitnessWrite<F>>(&self, witness: &mut W, bytes: &[u8]) {
+        assert_eq!(self.len(), bytes.len());
+        for (t, b) in self.0.iter().zip(bytes) {
+            t.set_witness(witness, b);
+        }
+    }
+
+    pub fn get_witness<F: RichField, W: Witness<F>>(&self, witness: &W) -> Vec<u8> {
+        self.0.iter().map(|t| t.get_witness(witness)).collect()
+    }
+}
+
+pub trait CircuitBuilderBytes<F: RichField + Extendable<D>, const D: usize> {
+    /// Adds a byte target without range-checking it.
+    fn add_virtual_u8_target(&mut self) -> U8Target;
+
+    /// Adds `n` range-checked byte targets.
+    fn add_virtual_bytes(&mut self, n: usize) -> ByteArrayTarget;
+
+    fn range_check_u8(&mut self, x: U8Target);
+
+    fn constant_u8(&mut self, c: u8) -> U8Target;
+
+    fn constant_bytes(&mut self, bytes: &[u8]) -> ByteArrayTarget;
+
+    fn connect_bytes(&mut self, x: &ByteArrayTarget, y: &ByteArrayTarget);
+
+    /// Returns whether `x` and `y` are equal, assuming both are range-checked.
+    fn is_equal_bytes(&mut self, x: &ByteArrayTarget, y: &ByteArrayTarget) -> BoolTarget;
+
+    /// Returns `x` if `b` is true, and `y` otherwise.
+    fn select_bytes(
+        &mut self,
+        b: BoolTarget,
+        x: &ByteArrayTarget,
+        y: &ByteArrayTarget,
+    ) -> ByteArrayTarget;
+
+    /// Packs each group of four range-checked bytes into a little-endian `U32Target`.
+    fn bytes_to_u32s_le(&mut self, bytes: &ByteArrayTarget) -> Vec<U32Target>;
+
+    /// Packs each group of four range-checked bytes into a big-endian `U32Target`.
+    fn bytes_to_u32s_be(&mut self, bytes: &ByteArrayTarget) -> Vec<U32Target>;
+
+    /// Splits each `U32Target` into four range-checked little-endian bytes.
+    fn u32s_to_bytes_le(&mut self, xs: &[U32Target]) -> ByteArrayTarget;
+
+    /// Splits each `U32Target` into four range-checked big-endian bytes.
+    fn u32s_to_bytes_be(&mut self, xs: &[U32Target]) -> ByteArrayTarget;
+
+    /// Packs each group of `BYTES_PER_TARGET` range-checked bytes into a little-endian field
+    /// element. The last group may be shorter.
+    fn bytes_to_targets_le(&mut self, bytes: &ByteArrayTarget) -> Vec<Target>;
+
+    /// Packs each group of `BYTES_PER_TARGET` range-checked bytes into a big-endian field
+    /// element. The last group may be shorter.
+    fn bytes_to_targets_be(&mut self, bytes: &ByteArrayTarget) -> Vec<Target>;
+
+    /// Splits the canonical representation of `x` into eight range-checked little-endian bytes.
+    fn target_to_bytes_le(&mut self, x: Target) -> ByteArrayTarget;
+
+    /// Splits the canonical representation of `x` into eight range-checked big-endian bytes.
+    fn target_to_bytes_be(&mut self, x: Target) -> ByteArrayTarget;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBytes<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn add_virtual_u8_target(&mut self) -> U8Target {
+        U8Target(self.add_virtual_target())
+    }
+
+    fn add_virtual_bytes(&mut self, n: usize) -> ByteArrayTarget {
+        ByteArrayTarget((0..n).map(|_| U8Target::add_virtual(self)).collect())
+    }
+
+    fn range_check_u8(&mut self, x: U8Target) {
+        self.range_check(x.0, 8);
+    }
+
+    fn constant_u8(&mut self, c: u8) -> U8Target {
+        U8Target(self.constant(F::from_canonical_u8(c)))
+    }
+
+    fn constant_bytes(&mut self, bytes: &[u8]) -> ByteArrayTarget {
+        ByteArrayTarget(bytes.iter().map(|&b| self.constant_u8(b)).collect())
+    }
+
+    fn connect_bytes(&mut self, x: &ByteArrayTarget, y: &ByteArrayTarget) {
+        assert_eq!(x.len(), y.len());
+        for (a, b) in x.0.iter().zip(&y.0) {
+            self.connect(a.0, b.0);
+        }
+    }
+
+    fn is_equal_bytes(&mut self, x: &ByteArrayTarget, y: &ByteArrayTarget) -> BoolTarget {
+        assert_eq!(x.len(), y.len());
+        // Comparing packed elements is cheaper than comparing bytes, and equivalent as packing is
+        // injective.
+        let x_packed = self.bytes_to_targets_le(x);
+        let y_packed = self.bytes_to_targets_le(y);
+        let mut result = self._true();
+        for (a, b) in x_packed.into_iter().zip(y_packed) {
+            let equal = self.is_equal(a, b);
+            result = self.and(result, equal);
+        }
+        result
+    }
+
+    fn select_bytes(
+        &mut self,
+        b: BoolTarget,
+        x: &ByteArrayTarget,
+        y: &ByteArrayTarget,
+    ) -> ByteArrayTarget {
+        assert_eq!(x.len(), y.len());
+        ByteArrayTarget(
+            x.0.iter()
+                .zip(&y.0)
+                .map(|(x, y)| U8Target::select(self, b, x, y))
+                .collect(),
+        )
+    }
+
+    fn bytes_to_u32s_le(&mut self, bytes: &ByteArrayTarget) -> Vec<U32Target> {
+        assert_eq!(bytes.len() % 4, 0, "Length must be a multiple of 4");
+        bytes
+            .0
+            .chunks(4)
+            .map(|chunk| U32Target(pack_bytes_le(self, chunk.iter())))
+            .collect()
+    }
+
+    fn bytes_to_u32s_be(&mut self, bytes: &ByteArrayTarget) -> Vec<U32Target> {
+        assert_eq!(bytes.len() % 4, 0, "Length must be a multiple of 4");
+        bytes
+            .0
+            .chunks(4)
+            .map(|chunk| U32Target(pack_bytes_le(self, chunk.iter().rev())))
+            .collect()
+    }
+
+    fn u32s_to_bytes_le(&mut self, xs: &[U32Target]) -> ByteArrayTarget {
+        let mut bytes = Vec::with_capacity(4 * xs.len());
+        for x in xs {
+            let bits = self.split_le(x.0, 32);
+            bytes.extend(unpack_bits_le(self, &bits));
+        }
+        ByteArrayTarget(bytes)
+    }
+
+    fn u32s_to_bytes_be(&mut self, xs: &[U32Target]) -> ByteArrayTarget {
+        let mut bytes = Vec::with_capacity(4 * xs.len());
+        for x in xs {
+            let bits = self.split_le(x.0, 32);
+            bytes.extend(unpack_bits_le(self, &bits).into_iter().rev());
+        }
+        ByteArrayTarget(bytes)
+    }
+
+    fn bytes_to_targets_le(&mut self, bytes: &ByteArrayTarget) -> Vec<Target> {
+        bytes
+            .0
+            .chunks(BYTES_PER_TARGET)
+            .map(|chunk| pack_bytes_le(self, chunk.iter()))
+            .collect()
+    }
+
+    fn bytes_to_targets_be(&mut self, bytes: &ByteArrayTarget) -> Vec<Target> {
+        bytes
+            .0
+            .chunks(BYTES_PER_TARGET)
+            .map(|chunk| pack_bytes_le(self, chunk.iter().rev()))
+            .collect()
+    }
+
+    fn target_to_bytes_le(&mut self, x: Target) -> ByteArrayTarget {
+        let bits = self.split_le_canonical(x);
+        ByteArrayTarget(unpack_bits_le(self, &bits))
+    }
+
+    fn target_to_bytes_be(&mut self, x: Target) -> ByteArrayTarget {
+        let bits = self.split_le_canonical(x);
+        let mut bytes = unpack_bits_le(self, &bits);
+        bytes.reverse();
+        ByteArrayTarget(bytes)
+    }
+}
+
+/// Returns `sum_i bytes[i] * 256^i`.
+fn pack_bytes_le<'a, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    bytes: impl DoubleEndedIterator<Item = &'a U8Target>,
+) -> Target {
+    let mut acc = builder.zero();
+    for byte in bytes.rev() {
+        acc = builder.mul_const_add(F::from_canonical_u16(256), acc, byte.0);
+    }
+    acc
+}
+
+/// Groups little-endian bits into little-endian bytes.
+fn unpack_bits_le<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    bits: &[BoolTarget],
+) -> Vec<U8Target> {
+    bits.chunks(8)
+        .map(|chunk| U8Target(builder.le_sum(chunk.iter())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::{Field, PrimeField64, Sample};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    #[test]
+    fn test_u32_bytes_round_trip() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let bytes = builder.add_virtual_bytes(8);
+        let words_le = builder.bytes_to_u32s_le(&bytes);
+        let words_be = builder.bytes_to_u32s_be(&bytes);
+        let bytes_le = builder.u32s_to_bytes_le(&words_le);
+        let bytes_be = builder.u32s_to_bytes_be(&words_be);
+        builder.connect_bytes(&bytes, &bytes_le);
+        builder.connect_bytes(&bytes, &bytes_be);
+        for word in words_le.iter().chain(&words_be) {
+            builder.register_public_input(word.0);
+        }
+        let data = builder.build::<C>();
+
+        let mut rng = OsRng;
+        let values: [u8; 8] = rng.gen();
+        let mut pw = PartialWitness::new();
+        bytes.set_witness(&mut pw, &values);
+        let proof = data.prove(pw)?;
+
+        let expected = values
+            .chunks(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .chain(
+                values
+                    .chunks(4)
+                    .map(|c| u32::from_be_bytes(c.try_into().unwrap())),
+            )
+            .map(F::from_canonical_u32)
+            .collect::<Vec<_>>();
+        assert_eq!(proof.public_inputs, expected);
+        data.verify(proof)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_target_bytes() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = builder.add_virtual_target();
+        let bytes_le = builder.target_to_bytes_le(x);
+        let bytes_be = builder.target_to_bytes_be(x);
+        let packed_le = builder.bytes_to_targets_le(&bytes_le);
+        let packed_be = builder.bytes_to_targets_be(&bytes_be);
+        for byte in bytes_le.0.iter().chain(&bytes_be.0) {
+            builder.register_public_input(byte.0);
+        }
+        builder.register_public_inputs(&packed_le);
+        builder.register_public_inputs(&packed_be);
+        let data = builder.build::<C>();
+
+        let x_value = F::rand();
+        let mut pw = PartialWitness::new();
+        pw.set_target(x, x_value);
+        let proof = data.prove(pw)?;
+
+        let x_u64 = x_value.to_canonical_u64();
+        let mut expected = x_u64
+            .to_le_bytes()
+            .into_iter()
+            .chain(x_u64.to_be_bytes())
+            .map(F::from_canonical_u8)
+            .collect::<Vec<_>>();
+        // The eight bytes are packed as seven bytes, then one.
+        expected.extend([
+            F::from_canonical_u64(x_u64 & ((1 << 56) - 1)),
+            F::from_canonical_u64(x_u64 >> 56),
+            F::from_canonical_u64(x_u64 >> 8),
+            F::from_canonical_u64(x_u64 & 0xff),
+        ]);
+        assert_eq!(proof.public_inputs, expected);
+        data.verify(proof)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_bytes_equality_and_selection() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let message = b"plonky2 byte strings";
+        let x = builder.add_virtual_bytes(message.len());
+        let constant = builder.constant_bytes(message);
+        let equal = builder.is_equal_bytes(&x, &constant);
+        let zeros = builder.constant_bytes(&[0; 20]);
+        let selected = builder.select_bytes(equal, &x, &zeros);
+        builder.register_public_input(equal.target);
+        builder.register_public_inputs(&selected.0.iter().map(|b| b.0).collect::<Vec<_>>());
+        let data = builder.build::<C>();
+
+        for (input, is_equal) in [(*message, true), (*b"plonky2 byte strinGs", false)] {
+            let mut pw = PartialWitness::new();
+            x.set_witness(&mut pw, &input);
+            let proof = data.prove(pw)?;
+            let expected_selected = if is_equal { input } else { [0; 20] };
+            let expected = core::iter::once(F::from_bool(is_equal))
+                .chain(expected_selected.into_iter().map(F::from_canonical_u8))
+                .collect::<Vec<_>>();
+            assert_eq!(proof.public_inputs, expected);
+            data.verify(proof)?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
 pub mod arithmetic_u32;
+pub mod bytes;
 pub mod multiple_comparison;
 pub mod range_check;
+pub mod sha256;
+pub mod sha512;
+pub mod uint;
@@ -0,0 +1,183 @@
+use alloc::vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::ceil_div_usize;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::bytes::{ByteArrayTarget, CircuitBuilderBytes};
+
+/// The length in bytes of a SHA-256 digest.
+pub const SHA256_DIGEST_LEN: usize = 32;
+
+/// The length in bytes of a SHA-256 message block.
+const SHA256_BLOCK_LEN: usize = 64;
+
+/// The initial hash value, from FIPS 180-4, section 5.3.3.
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The round constants, from FIPS 180-4, section 4.2.2.
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub trait CircuitBuilderSha256<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes the SHA-256 digest of `msg`, whose length is fixed when building the circuit. The
+    /// bytes of `msg` are assumed to be range-checked, and those of the digest are range-checked.
+    fn sha256(&mut self, msg: &ByteArrayTarget) -> ByteArrayTarget;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderSha256<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn sha256(&mut self, msg: &ByteArrayTarget) -> ByteArrayTarget {
+        // Pads the message with `0x80`, zeros and its length in bits as a 64-bit integer.
+        let num_padded_bytes = ceil_div_usize(msg.len() + 9, SHA256_BLOCK_LEN) * SHA256_BLOCK_LEN;
+        let mut padding = vec![0u8; num_padded_bytes - msg.len()];
+        padding[0] = 0x80;
+        let padding_len = padding.len();
+        padding[padding_len - 8..].copy_from_slice(&(8 * msg.len() as u64).to_be_bytes());
+        let mut padded = msg.0.clone();
+        padded.extend(self.constant_bytes(&padding).0);
+
+        let words = self.bytes_to_u32s_be(&ByteArrayTarget(padded));
+
+        let mut state = INITIAL_STATE.map(|h| self.constant_u32(h));
+        for block in words.chunks(SHA256_BLOCK_LEN / 4) {
+            state = compress(self, state, block);
+        }
+
+        self.u32s_to_bytes_be(&state)
+    }
+}
+
+/// Applies the SHA-256 compression function to `state` and a block of 16 words.
+fn compress<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    state: [U32Target; 8],
+    block: &[U32Target],
+) -> [U32Target; 8] {
+    let mut schedule = block.to_vec();
+    for t in 16..64 {
+        let s0 = small_sigma(builder, schedule[t - 15], 7, 18, 3);
+        let s1 = small_sigma(builder, schedule[t - 2], 17, 19, 10);
+        let w = add_many(builder, &[s1, schedule[t - 7], s0, schedule[t - 16]]);
+        schedule.push(w);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for t in 0..64 {
+        let big_s1 = big_sigma(builder, e, 6, 11, 25);
+        // `Ch(e, f, g) = g ^ (e & (f ^ g))`.
+        let f_xor_g = builder.xor_u32(f, g);
+        let e_and_f_xor_g = builder.and_u32(e, f_xor_g);
+        let ch = builder.xor_u32(g, e_and_f_xor_g);
+        let k = builder.constant_u32(ROUND_CONSTANTS[t]);
+        let t1 = add_many(builder, &[h, big_s1, ch, k, schedule[t]]);
+
+        let big_s0 = big_sigma(builder, a, 2, 13, 22);
+        // `Maj(a, b, c) = (a & b) ^ (c & (a ^ b))`.
+        let (a_and_b, a_xor_b) = builder.and_xor_u32(a, b);
+        let c_and_a_xor_b = builder.and_u32(c, a_xor_b);
+        let maj = builder.xor_u32(a_and_b, c_and_a_xor_b);
+
+        h = g;
+        g = f;
+        f = e;
+        e = add_many(builder, &[d, t1]);
+        d = c;
+        c = b;
+        b = a;
+        a = add_many(builder, &[t1, big_s0, maj]);
+    }
+
+    let working = [a, b, c, d, e, f, g, h];
+    core::array::from_fn(|i| add_many(builder, &[state[i], working[i]]))
+}
+
+/// `ROTR^r1(x) ^ ROTR^r2(x) ^ ROTR^r3(x)`.
+fn big_sigma<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U32Target,
+    r1: usize,
+    r2: usize,
+    r3: usize,
+) -> U32Target {
+    let x1 = builder.rotr_u32(x, r1);
+    let x2 = builder.rotr_u32(x, r2);
+    let x3 = builder.rotr_u32(x, r3);
+    let x12 = builder.xor_u32(x1, x2);
+    builder.xor_u32(x12, x3)
+}
+
+/// `ROTR^r1(x) ^ ROTR^r2(x) ^ SHR^s(x)`.
+fn small_sigma<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U32Target,
+    r1: usize,
+    r2: usize,
+    s: usize,
+) -> U32Target {
+    let x1 = builder.rotr_u32(x, r1);
+    let x2 = builder.rotr_u32(x, r2);
+    let x3 = builder.shr_u32(x, s);
+    let x12 = builder.xor_u32(x1, x2);
+    builder.xor_u32(x12, x3)
+}
+
+/// Adds `xs` modulo `2^32`.
+fn add_many<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    xs: &[U32Target],
+) -> U32Target {
+    builder.add_many_u32(xs).0
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+
+    #[test]
+    fn test_sha256() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        // Covers the empty message, and padding which just fits in a block or needs another one.
+        for len in [0, 55, 56] {
+            let mut msg = vec![0u8; len];
+            OsRng.fill_bytes(&mut msg);
+            let msg_target = builder.add_virtual_bytes(len);
+            msg_target.set_witness(&mut pw, &msg);
+            let digest_target = builder.sha256(&msg_target);
+            let expected = builder.constant_bytes(&Sha256::digest(&msg));
+            builder.connect_bytes(&digest_target, &expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+}
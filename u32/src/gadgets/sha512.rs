@@ -0,0 +1,374 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::util::ceil_div_usize;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::bytes::{ByteArrayTarget, CircuitBuilderBytes};
+use crate::gadgets::uint::U64Target;
+
+/// The length in bytes of a SHA-512 digest.
+pub const SHA512_DIGEST_LEN: usize = 64;
+
+/// The length in bytes of a SHA-512 message block.
+const SHA512_BLOCK_LEN: usize = 128;
+
+/// The initial hash value, from FIPS 180-4, section 5.3.5.
+const INITIAL_STATE: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The round constants, from FIPS 180-4, section 4.2.3.
+const ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+pub trait CircuitBuilderSha512<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes the SHA-512 digest of `msg`, whose length is fixed when building the circuit. The
+    /// bytes of `msg` are assumed to be range-checked, and those of the digest are range-checked.
+    fn sha512(&mut self, msg: &ByteArrayTarget) -> ByteArrayTarget;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderSha512<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn sha512(&mut self, msg: &ByteArrayTarget) -> ByteArrayTarget {
+        // Pads the message with `0x80`, zeros and its length in bits as a 128-bit integer.
+        let num_padded_bytes = ceil_div_usize(msg.len() + 17, SHA512_BLOCK_LEN) * SHA512_BLOCK_LEN;
+        let mut padding = vec![0u8; num_padded_bytes - msg.len()];
+        padding[0] = 0x80;
+        let padding_len = padding.len();
+        padding[padding_len - 16..].copy_from_slice(&(8 * msg.len() as u128).to_be_bytes());
+        let mut padded = msg.0.clone();
+        padded.extend(self.constant_bytes(&padding).0);
+
+        let words: Vec<U64Target> = self
+            .bytes_to_u32s_be(&ByteArrayTarget(padded))
+            .chunks(2)
+            .map(|halves| U64Target {
+                limbs: [halves[1], halves[0]],
+            })
+            .collect();
+
+        let mut state = INITIAL_STATE.map(|h| constant_u64(self, h));
+        for block in words.chunks(SHA512_BLOCK_LEN / 8) {
+            state = compress(self, state, block);
+        }
+
+        let limbs: Vec<U32Target> = state
+            .iter()
+            .flat_map(|word| [word.limbs[1], word.limbs[0]])
+            .collect();
+        self.u32s_to_bytes_be(&limbs)
+    }
+}
+
+/// Applies the SHA-512 compression function to `state` and a block of 16 words.
+fn compress<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    state: [U64Target; 8],
+    block: &[U64Target],
+) -> [U64Target; 8] {
+    let mut schedule = block.to_vec();
+    for t in 16..80 {
+        let s0 = small_sigma(builder, schedule[t - 15], 1, 8, 7);
+        let s1 = small_sigma(builder, schedule[t - 2], 19, 61, 6);
+        let w = add_many_u64(builder, &[s1, schedule[t - 7], s0, schedule[t - 16]]);
+        schedule.push(w);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for t in 0..80 {
+        let big_s1 = big_sigma(builder, e, 14, 18, 41);
+        // `Ch(e, f, g) = g ^ (e & (f ^ g))`.
+        let f_xor_g = xor_u64(builder, f, g);
+        let e_and_f_xor_g = and_u64(builder, e, f_xor_g);
+        let ch = xor_u64(builder, g, e_and_f_xor_g);
+        let k = constant_u64(builder, ROUND_CONSTANTS[t]);
+        let t1 = add_many_u64(builder, &[h, big_s1, ch, k, schedule[t]]);
+
+        let big_s0 = big_sigma(builder, a, 28, 34, 39);
+        // `Maj(a, b, c) = (a & b) ^ (c & (a ^ b))`.
+        let (a_and_b, a_xor_b) = and_xor_u64(builder, a, b);
+        let c_and_a_xor_b = and_u64(builder, c, a_xor_b);
+        let maj = xor_u64(builder, a_and_b, c_and_a_xor_b);
+
+        h = g;
+        g = f;
+        f = e;
+        e = add_many_u64(builder, &[d, t1]);
+        d = c;
+        c = b;
+        b = a;
+        a = add_many_u64(builder, &[t1, big_s0, maj]);
+    }
+
+    let working = [a, b, c, d, e, f, g, h];
+    core::array::from_fn(|i| add_many_u64(builder, &[state[i], working[i]]))
+}
+
+/// `ROTR^r1(x) ^ ROTR^r2(x) ^ ROTR^r3(x)`.
+fn big_sigma<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    r1: usize,
+    r2: usize,
+    r3: usize,
+) -> U64Target {
+    let x1 = rotr_u64(builder, x, r1);
+    let x2 = rotr_u64(builder, x, r2);
+    let x3 = rotr_u64(builder, x, r3);
+    let x12 = xor_u64(builder, x1, x2);
+    xor_u64(builder, x12, x3)
+}
+
+/// `ROTR^r1(x) ^ ROTR^r2(x) ^ SHR^s(x)`.
+fn small_sigma<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    r1: usize,
+    r2: usize,
+    s: usize,
+) -> U64Target {
+    let x1 = rotr_u64(builder, x, r1);
+    let x2 = rotr_u64(builder, x, r2);
+    let x3 = shr_u64(builder, x, s);
+    let x12 = xor_u64(builder, x1, x2);
+    xor_u64(builder, x12, x3)
+}
+
+fn constant_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: u64,
+) -> U64Target {
+    U64Target {
+        limbs: [
+            builder.constant_u32(x as u32),
+            builder.constant_u32((x >> 32) as u32),
+        ],
+    }
+}
+
+fn and_xor_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    y: U64Target,
+) -> (U64Target, U64Target) {
+    let (and_lo, xor_lo) = builder.and_xor_u32(x.limbs[0], y.limbs[0]);
+    let (and_hi, xor_hi) = builder.and_xor_u32(x.limbs[1], y.limbs[1]);
+    (
+        U64Target {
+            limbs: [and_lo, and_hi],
+        },
+        U64Target {
+            limbs: [xor_lo, xor_hi],
+        },
+    )
+}
+
+fn and_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    y: U64Target,
+) -> U64Target {
+    and_xor_u64(builder, x, y).0
+}
+
+fn xor_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    y: U64Target,
+) -> U64Target {
+    and_xor_u64(builder, x, y).1
+}
+
+/// Splits `x` into `(x << (32 - shift), x >> shift)`, both truncated to 32 bits.
+fn split_u32<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U32Target,
+    shift: usize,
+) -> (U32Target, U32Target) {
+    debug_assert!(0 < shift && shift < 32);
+    let multiplier = builder.constant_u32(1 << (32 - shift));
+    builder.mul_u32(x, multiplier)
+}
+
+fn rotr_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    shift: usize,
+) -> U64Target {
+    assert!(shift < 64, "Shift {shift} is too large");
+    let [mut lo, mut hi] = x.limbs;
+    if shift >= 32 {
+        (lo, hi) = (hi, lo);
+    }
+    let shift = shift % 32;
+    if shift == 0 {
+        return U64Target { limbs: [lo, hi] };
+    }
+    let (lo_shl, lo_shr) = split_u32(builder, lo, shift);
+    let (hi_shl, hi_shr) = split_u32(builder, hi, shift);
+    // The two terms of each sum have no bits in common.
+    U64Target {
+        limbs: [
+            U32Target(builder.add(lo_shr.0, hi_shl.0)),
+            U32Target(builder.add(hi_shr.0, lo_shl.0)),
+        ],
+    }
+}
+
+fn shr_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    shift: usize,
+) -> U64Target {
+    assert!(0 < shift && shift < 32, "Unsupported shift {shift}");
+    let [lo, hi] = x.limbs;
+    let (_, lo_shr) = split_u32(builder, lo, shift);
+    let (hi_shl, hi_shr) = split_u32(builder, hi, shift);
+    U64Target {
+        limbs: [U32Target(builder.add(lo_shr.0, hi_shl.0)), hi_shr],
+    }
+}
+
+/// Adds `xs` modulo `2^64`.
+fn add_many_u64<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    xs: &[U64Target],
+) -> U64Target {
+    let lo_limbs: Vec<_> = xs.iter().map(|x| x.limbs[0]).collect();
+    let (lo, carry) = builder.add_many_u32(&lo_limbs);
+    let mut hi_limbs: Vec<_> = xs.iter().map(|x| x.limbs[1]).collect();
+    hi_limbs.push(carry);
+    let (hi, _) = builder.add_many_u32(&hi_limbs);
+    U64Target { limbs: [lo, hi] }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+    use sha2::{Digest, Sha512};
+
+    use super::*;
+
+    #[test]
+    fn test_sha512() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw = PartialWitness::new();
+
+        // Covers the empty message, and padding which just fits in a block or needs another one.
+        for len in [0, 111, 112] {
+            let mut msg = vec![0u8; len];
+            OsRng.fill_bytes(&mut msg);
+            let msg_target = builder.add_virtual_bytes(len);
+            msg_target.set_witness(&mut pw, &msg);
+            let digest_target = builder.sha512(&msg_target);
+            let expected = builder.constant_bytes(&Sha512::digest(&msg));
+            builder.connect_bytes(&digest_target, &expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        Ok(data.verify(proof)?)
+    }
+}
@@ -0,0 +1,586 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::{BigUint, Integer, Zero};
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::circuit_type::CircuitType;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+
+/// An unsigned integer of `32 * N` bits, made of `N` 32-bit limbs, least significant limb first.
+/// Arithmetic follows the semantics of Rust's fixed-width unsigned integers.
+#[derive(Clone, Copy, Debug)]
+pub struct UintTarget<const N: usize> {
+    pub limbs: [U32Target; N],
+}
+
+pub type U64Target = UintTarget<2>;
+pub type U128Target = UintTarget<4>;
+pub type U256Target = UintTarget<8>;
+
+impl<F: RichField, const N: usize> CircuitType<F> for UintTarget<N> {
+    type Native = BigUint;
+
+    fn add_virtual<const D: usize>(builder: &mut CircuitBuilder<F, D>) -> Self
+    where
+        F: Extendable<D>,
+    {
+        Self {
+            limbs: <[U32Target; N]>::add_virtual(builder),
+        }
+    }
+
+    fn set_witness<W: WitnessWrite<F>>(&self, witness: &mut W, value: &BigUint) {
+        let mut limbs = value.to_u32_digits();
+        assert!(limbs.len() <= N, "{value} doesn't fit in {} bits", 32 * N);
+        limbs.resize(N, 0);
+        for (target, limb) in self.limbs.iter().zip(&limbs) {
+            target.set_witness(witness, limb);
+        }
+    }
+
+    fn get_witness<W: Witness<F>>(&self, witness: &W) -> BigUint {
+        let limbs = self.limbs.map(|limb| limb.get_witness(witness));
+        BigUint::from_slice(&limbs)
+    }
+
+    fn connect<const D: usize>(builder: &mut CircuitBuilder<F, D>, x: &Self, y: &Self)
+    where
+        F: Extendable<D>,
+    {
+        CircuitType::connect(builder, &x.limbs, &y.limbs);
+    }
+
+    fn select<const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &Self,
+        y: &Self,
+    ) -> Self
+    where
+        F: Extendable<D>,
+    {
+        Self {
+            limbs: CircuitType::select(builder, b, &x.limbs, &y.limbs),
+        }
+    }
+}
+
+/// Methods on `UintTarget`s, which all assume their inputs are range-checked, e.g. by having been
+/// created with `add_virtual_uint_target`. The `checked_*` methods assert that the operation
+/// doesn't overflow, where Rust's would return `None`.
+pub trait CircuitBuilderUint<F: RichField + Extendable<D>, const D: usize> {
+    /// Adds a range-checked `UintTarget`.
+    fn add_virtual_uint_target<const N: usize>(&mut self) -> UintTarget<N>;
+
+    fn constant_uint<const N: usize>(&mut self, value: &BigUint) -> UintTarget<N>;
+
+    fn connect_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>);
+
+    fn is_equal_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>)
+        -> BoolTarget;
+
+    /// Returns whether `a < b`.
+    fn is_less_than_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> BoolTarget;
+
+    /// Returns whether `a <= b`.
+    fn is_less_than_or_equal_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> BoolTarget;
+
+    /// Returns `a + b` wrapped around, and whether the addition overflowed.
+    fn overflowing_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget);
+
+    fn wrapping_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    fn checked_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    /// Returns `a - b` wrapped around, and whether the subtraction overflowed, i.e. `a < b`.
+    fn overflowing_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget);
+
+    fn wrapping_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    fn checked_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    /// Returns `a * b` wrapped around, and whether the multiplication overflowed.
+    fn overflowing_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget);
+
+    fn wrapping_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    fn checked_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N>;
+
+    /// Returns `(a / b, a % b)`. `b` is constrained to be nonzero.
+    fn div_rem_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, UintTarget<N>);
+
+    fn div_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>) -> UintTarget<N>;
+
+    fn rem_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>) -> UintTarget<N>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderUint<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn add_virtual_uint_target<const N: usize>(&mut self) -> UintTarget<N> {
+        UintTarget::add_virtual(self)
+    }
+
+    fn constant_uint<const N: usize>(&mut self, value: &BigUint) -> UintTarget<N> {
+        let mut limbs = value.to_u32_digits();
+        assert!(limbs.len() <= N, "{value} doesn't fit in {} bits", 32 * N);
+        limbs.resize(N, 0);
+        UintTarget {
+            limbs: core::array::from_fn(|i| self.constant_u32(limbs[i])),
+        }
+    }
+
+    fn connect_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>) {
+        CircuitType::connect(self, a, b);
+    }
+
+    fn is_equal_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> BoolTarget {
+        let mut result = self._true();
+        for (a_limb, b_limb) in a.limbs.iter().zip(&b.limbs) {
+            let limb_equal = self.is_equal(a_limb.0, b_limb.0);
+            result = self.and(result, limb_equal);
+        }
+        result
+    }
+
+    fn is_less_than_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> BoolTarget {
+        self.overflowing_sub_uint(a, b).1
+    }
+
+    fn is_less_than_or_equal_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> BoolTarget {
+        let b_lt_a = self.is_less_than_uint(b, a);
+        self.not(b_lt_a)
+    }
+
+    fn overflowing_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget) {
+        let mut carry = self.zero_u32();
+        let limbs = core::array::from_fn(|i| {
+            let (limb, new_carry) = self.add_u32s_with_carry(&[a.limbs[i], b.limbs[i]], carry);
+            carry = new_carry;
+            limb
+        });
+        // The sum of two limbs and a carry of at most one has a carry of at most one.
+        (UintTarget { limbs }, BoolTarget::new_unsafe(carry.0))
+    }
+
+    fn wrapping_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        self.overflowing_add_uint(a, b).0
+    }
+
+    fn checked_add_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        let (sum, overflow) = self.overflowing_add_uint(a, b);
+        self.assert_zero(overflow.target);
+        sum
+    }
+
+    fn overflowing_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget) {
+        let mut borrow = self.zero_u32();
+        let limbs = core::array::from_fn(|i| {
+            let (limb, new_borrow) = self.sub_u32(a.limbs[i], b.limbs[i], borrow);
+            borrow = new_borrow;
+            limb
+        });
+        // `U32SubtractionGate` constrains the borrow to be a bit.
+        (UintTarget { limbs }, BoolTarget::new_unsafe(borrow.0))
+    }
+
+    fn wrapping_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        self.overflowing_sub_uint(a, b).0
+    }
+
+    fn checked_sub_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        let (difference, overflow) = self.overflowing_sub_uint(a, b);
+        self.assert_zero(overflow.target);
+        difference
+    }
+
+    fn overflowing_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, BoolTarget) {
+        let product = mul_limbs(self, &a.limbs, &b.limbs, 2 * N);
+        let limbs = product[..N].try_into().unwrap();
+        // The sum of the high limbs can't wrap around, so it is zero iff they all are.
+        let high_sum = self.add_many(product[N..].iter().map(|limb| limb.0));
+        let zero = self.zero();
+        let high_is_zero = self.is_equal(high_sum, zero);
+        (UintTarget { limbs }, self.not(high_is_zero))
+    }
+
+    fn wrapping_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        let product = mul_limbs(self, &a.limbs, &b.limbs, N);
+        UintTarget {
+            limbs: product.try_into().unwrap(),
+        }
+    }
+
+    fn checked_mul_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> UintTarget<N> {
+        let product = mul_limbs(self, &a.limbs, &b.limbs, 2 * N);
+        for limb in &product[N..] {
+            self.assert_zero_u32(*limb);
+        }
+        UintTarget {
+            limbs: product[..N].try_into().unwrap(),
+        }
+    }
+
+    fn div_rem_uint<const N: usize>(
+        &mut self,
+        a: &UintTarget<N>,
+        b: &UintTarget<N>,
+    ) -> (UintTarget<N>, UintTarget<N>) {
+        let div = self.add_virtual_uint_target();
+        let rem = self.add_virtual_uint_target();
+        self.add_simple_generator(UintDivRemGenerator {
+            a: *a,
+            b: *b,
+            div,
+            rem,
+        });
+
+        let div_b = self.checked_mul_uint(&div, b);
+        let div_b_plus_rem = self.checked_add_uint(&div_b, &rem);
+        self.connect_uint(a, &div_b_plus_rem);
+        // This also rules out `b == 0`.
+        let rem_lt_b = self.is_less_than_uint(&rem, b);
+        self.assert_one(rem_lt_b.target);
+
+        (div, rem)
+    }
+
+    fn div_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>) -> UintTarget<N> {
+        self.div_rem_uint(a, b).0
+    }
+
+    fn rem_uint<const N: usize>(&mut self, a: &UintTarget<N>, b: &UintTarget<N>) -> UintTarget<N> {
+        self.div_rem_uint(a, b).1
+    }
+}
+
+/// Returns the `num_limbs` least significant limbs of `a * b`.
+fn mul_limbs<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    a: &[U32Target],
+    b: &[U32Target],
+    num_limbs: usize,
+) -> Vec<U32Target> {
+    let mut to_add = vec![vec![]; num_limbs];
+    for (i, &a_limb) in a.iter().enumerate() {
+        for (j, &b_limb) in b.iter().enumerate() {
+            if i + j >= num_limbs {
+                continue;
+            }
+            let (product, carry) = builder.mul_u32(a_limb, b_limb);
+            to_add[i + j].push(product);
+            if i + j + 1 < num_limbs {
+                to_add[i + j + 1].push(carry);
+            }
+        }
+    }
+
+    let mut limbs = Vec::with_capacity(num_limbs);
+    let mut carry = builder.zero_u32();
+    for summands in &to_add {
+        let (limb, new_carry) = builder.add_u32s_with_carry(summands, carry);
+        limbs.push(limb);
+        carry = new_carry;
+    }
+    limbs
+}
+
+#[derive(Debug)]
+struct UintDivRemGenerator<const N: usize> {
+    a: UintTarget<N>,
+    b: UintTarget<N>,
+    div: UintTarget<N>,
+    rem: UintTarget<N>,
+}
+
+impl<F: RichField, const N: usize> SimpleGenerator<F> for UintDivRemGenerator<N> {
+    fn dependencies(&self) -> Vec<Target> {
+        self.a
+            .limbs
+            .iter()
+            .chain(&self.b.limbs)
+            .map(|&l| l.0)
+            .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let a = self.a.get_witness(witness);
+        let b = self.b.get_witness(witness);
+        // Division by zero makes the circuit unsatisfiable, so any values will do.
+        let (div, rem) = if b.is_zero() {
+            (BigUint::zero(), BigUint::zero())
+        } else {
+            a.div_rem(&b)
+        };
+
+        self.div.set_witness(out_buffer, &div);
+        self.rem.set_witness(out_buffer, &rem);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::{Field, PrimeField64};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    /// Computes, in a circuit, the wrapping sum, difference and product of `a` and `b` along with
+    /// their overflow flags, then their quotient and remainder, and whether `a < b` and `a == b`.
+    fn uint_ops<const N: usize>(a: &BigUint, b: &BigUint) -> Result<Vec<BigUint>> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let a_target = builder.add_virtual_uint_target::<N>();
+        let b_target = builder.constant_uint::<N>(b);
+        let (sum, add_overflow) = builder.overflowing_add_uint(&a_target, &b_target);
+        let (difference, sub_overflow) = builder.overflowing_sub_uint(&a_target, &b_target);
+        let (product, mul_overflow) = builder.overflowing_mul_uint(&a_target, &b_target);
+        let wrapping_product = builder.wrapping_mul_uint(&a_target, &b_target);
+        builder.connect_uint(&product, &wrapping_product);
+        let (div, rem) = builder.div_rem_uint(&a_target, &b_target);
+        let lt = builder.is_less_than_uint(&a_target, &b_target);
+        let eq = builder.is_equal_uint(&a_target, &b_target);
+
+        let outputs = [sum, difference, product, div, rem];
+        for output in &outputs {
+            builder.register_public_inputs(&output.limbs.map(|limb| limb.0));
+        }
+        for flag in [add_overflow, sub_overflow, mul_overflow, lt, eq] {
+            builder.register_public_input(flag.target);
+        }
+
+        let mut pw = PartialWitness::new();
+        a_target.set_witness(&mut pw, a);
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        let public_inputs = proof.public_inputs.clone();
+        data.verify(proof)?;
+
+        let (uints, flags) = public_inputs.split_at(outputs.len() * N);
+        Ok(uints
+            .chunks(N)
+            .map(|limbs| {
+                let limbs: Vec<u32> = limbs.iter().map(|x| x.to_canonical_u64() as u32).collect();
+                BigUint::from_slice(&limbs)
+            })
+            .chain(flags.iter().map(|x| BigUint::from(x.to_canonical_u64())))
+            .collect())
+    }
+
+    #[test]
+    fn test_u64_ops() -> Result<()> {
+        let mut rng = OsRng;
+        for (a, b) in [(rng.gen(), rng.gen()), (u64::MAX, 1), (3, u64::MAX)] {
+            let b: u64 = b;
+            let outputs = uint_ops::<2>(&BigUint::from(a), &BigUint::from(b))?;
+            let expected = [
+                a.wrapping_add(b),
+                a.wrapping_sub(b),
+                a.wrapping_mul(b),
+                a / b,
+                a % b,
+                a.overflowing_add(b).1 as u64,
+                a.overflowing_sub(b).1 as u64,
+                a.overflowing_mul(b).1 as u64,
+                (a < b) as u64,
+                (a == b) as u64,
+            ];
+            assert_eq!(outputs, expected.map(BigUint::from));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_u128_ops() -> Result<()> {
+        let mut rng = OsRng;
+        for (a, b) in [
+            (rng.gen(), rng.gen::<u64>() as u128),
+            (u128::MAX, u128::MAX),
+        ] {
+            let b: u128 = b;
+            let outputs = uint_ops::<4>(&BigUint::from(a), &BigUint::from(b))?;
+            let expected = [
+                a.wrapping_add(b),
+                a.wrapping_sub(b),
+                a.wrapping_mul(b),
+                a / b,
+                a % b,
+                a.overflowing_add(b).1 as u128,
+                a.overflowing_sub(b).1 as u128,
+                a.overflowing_mul(b).1 as u128,
+                (a < b) as u128,
+                (a == b) as u128,
+            ];
+            assert_eq!(outputs, expected.map(BigUint::from));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_u256_ops() -> Result<()> {
+        let mut rng = OsRng;
+        let modulus = BigUint::from(1u8) << 256;
+        let a = BigUint::from_slice(&rng.gen::<[u32; 8]>());
+        let b = BigUint::from_slice(&rng.gen::<[u32; 5]>());
+        let outputs = uint_ops::<8>(&a, &b)?;
+
+        let sum = &a + &b;
+        let product = &a * &b;
+        let expected = [
+            &sum % &modulus,
+            (&a + &modulus - &b) % &modulus,
+            &product % &modulus,
+            &a / &b,
+            &a % &b,
+            BigUint::from((sum >= modulus) as u8),
+            BigUint::from((a < b) as u8),
+            BigUint::from((product >= modulus) as u8),
+            BigUint::from((a < b) as u8),
+            BigUint::from((a == b) as u8),
+        ];
+        assert_eq!(outputs, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_ops() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let a = builder.add_virtual_uint_target::<2>();
+        let b = builder.add_virtual_uint_target::<2>();
+        let sum = builder.checked_add_uint(&a, &b);
+        let difference = builder.checked_sub_uint(&sum, &b);
+        builder.connect_uint(&a, &difference);
+        let product = builder.checked_mul_uint(&a, &b);
+        let le = builder.is_less_than_or_equal_uint(&a, &product);
+        builder.assert_one(le.target);
+        builder.register_public_inputs(&product.limbs.map(|limb| limb.0));
+
+        let mut pw = PartialWitness::new();
+        a.set_witness(&mut pw, &BigUint::from(u32::MAX));
+        b.set_witness(&mut pw, &BigUint::from(u32::MAX));
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        let expected = (u32::MAX as u64) * (u32::MAX as u64);
+        assert_eq!(
+            proof.public_inputs,
+            [
+                F::from_canonical_u32(expected as u32),
+                F::from_canonical_u32((expected >> 32) as u32)
+            ]
+        );
+        Ok(data.verify(proof)?)
+    }
+}
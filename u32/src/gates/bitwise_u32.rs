@@ -0,0 +1,374 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::packed::PackedField;
+use plonky2::field::types::Field;
+use plonky2::gates::gate::Gate;
+use plonky2::gates::packed_util::PackedEvaluableBase;
+use plonky2::gates::util::StridedConstraintConsumer;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::wire::Wire;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::vars::{
+    EvaluationTargets, EvaluationVars, EvaluationVarsBase, EvaluationVarsBaseBatch,
+    EvaluationVarsBasePacked,
+};
+
+/// A gate computing the bitwise AND and XOR of two 32-bit values, by decomposing both into bits.
+/// The decomposition also range-checks the inputs.
+#[derive(Copy, Clone, Debug)]
+pub struct U32BitwiseGate<F: RichField + Extendable<D>, const D: usize> {
+    pub num_ops: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> U32BitwiseGate<F, D> {
+    pub fn new_from_config(config: &CircuitConfig) -> Self {
+        Self {
+            num_ops: Self::num_ops(config),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn num_ops(config: &CircuitConfig) -> usize {
+        let wires_per_op = Self::routed_wires_per_op() + 2 * Self::num_bits();
+        (config.num_wires / wires_per_op).min(config.num_routed_wires / Self::routed_wires_per_op())
+    }
+
+    pub fn wire_ith_input_x(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i
+    }
+    pub fn wire_ith_input_y(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i + 1
+    }
+    pub fn wire_ith_output_and(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i + 2
+    }
+    pub fn wire_ith_output_xor(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i + 3
+    }
+
+    pub fn num_bits() -> usize {
+        32
+    }
+    pub fn routed_wires_per_op() -> usize {
+        4
+    }
+    pub fn wire_ith_x_jth_bit(&self, i: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(j < Self::num_bits());
+        Self::routed_wires_per_op() * self.num_ops + 2 * Self::num_bits() * i + j
+    }
+    pub fn wire_ith_y_jth_bit(&self, i: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(j < Self::num_bits());
+        Self::routed_wires_per_op() * self.num_ops + 2 * Self::num_bits() * i + Self::num_bits() + j
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32BitwiseGate<F, D> {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        let two = F::Extension::TWO;
+        for i in 0..self.num_ops {
+            let mut x = F::Extension::ZERO;
+            let mut y = F::Extension::ZERO;
+            let mut and = F::Extension::ZERO;
+            let mut xor = F::Extension::ZERO;
+            for j in (0..Self::num_bits()).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_x_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_y_jth_bit(i, j)];
+                constraints.push(x_bit * (x_bit - F::Extension::ONE));
+                constraints.push(y_bit * (y_bit - F::Extension::ONE));
+
+                let and_bit = x_bit * y_bit;
+                let xor_bit = x_bit + y_bit - two * and_bit;
+                x = x * two + x_bit;
+                y = y * two + y_bit;
+                and = and * two + and_bit;
+                xor = xor * two + xor_bit;
+            }
+            constraints.push(x - vars.local_wires[self.wire_ith_input_x(i)]);
+            constraints.push(y - vars.local_wires[self.wire_ith_input_y(i)]);
+            constraints.push(and - vars.local_wires[self.wire_ith_output_and(i)]);
+            constraints.push(xor - vars.local_wires[self.wire_ith_output_xor(i)]);
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        _vars: EvaluationVarsBase<F>,
+        _yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        panic!("use eval_unfiltered_base_packed instead");
+    }
+
+    fn eval_unfiltered_base_batch(&self, vars_base: EvaluationVarsBaseBatch<F>) -> Vec<F> {
+        self.eval_unfiltered_base_batch_packed(vars_base)
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        let two = F::TWO;
+        for i in 0..self.num_ops {
+            let mut x = builder.zero_extension();
+            let mut y = builder.zero_extension();
+            let mut and = builder.zero_extension();
+            let mut xor = builder.zero_extension();
+            for j in (0..Self::num_bits()).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_x_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_y_jth_bit(i, j)];
+                constraints.push(builder.mul_sub_extension(x_bit, x_bit, x_bit));
+                constraints.push(builder.mul_sub_extension(y_bit, y_bit, y_bit));
+
+                let and_bit = builder.mul_extension(x_bit, y_bit);
+                let x_plus_y = builder.add_extension(x_bit, y_bit);
+                let xor_bit = builder.arithmetic_extension(-two, F::ONE, x_bit, y_bit, x_plus_y);
+                x = builder.mul_const_add_extension(two, x, x_bit);
+                y = builder.mul_const_add_extension(two, y, y_bit);
+                and = builder.mul_const_add_extension(two, and, and_bit);
+                xor = builder.mul_const_add_extension(two, xor, xor_bit);
+            }
+            let input_x = vars.local_wires[self.wire_ith_input_x(i)];
+            let input_y = vars.local_wires[self.wire_ith_input_y(i)];
+            let output_and = vars.local_wires[self.wire_ith_output_and(i)];
+            let output_xor = vars.local_wires[self.wire_ith_output_xor(i)];
+            constraints.push(builder.sub_extension(x, input_x));
+            constraints.push(builder.sub_extension(y, input_y));
+            constraints.push(builder.sub_extension(and, output_and));
+            constraints.push(builder.sub_extension(xor, output_xor));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<Box<dyn WitnessGenerator<F>>> {
+        (0..self.num_ops)
+            .map(|i| {
+                let g: Box<dyn WitnessGenerator<F>> = Box::new(
+                    U32BitwiseGenerator {
+                        gate: *self,
+                        row,
+                        i,
+                        _phantom: PhantomData,
+                    }
+                    .adapter(),
+                );
+                g
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * (Self::routed_wires_per_op() + 2 * Self::num_bits())
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_ops * (4 + 2 * Self::num_bits())
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> PackedEvaluableBase<F, D>
+    for U32BitwiseGate<F, D>
+{
+    fn eval_unfiltered_base_packed<P: PackedField<Scalar = F>>(
+        &self,
+        vars: EvaluationVarsBasePacked<P>,
+        mut yield_constr: StridedConstraintConsumer<P>,
+    ) {
+        let two = F::TWO;
+        for i in 0..self.num_ops {
+            let mut x = P::ZEROS;
+            let mut y = P::ZEROS;
+            let mut and = P::ZEROS;
+            let mut xor = P::ZEROS;
+            for j in (0..Self::num_bits()).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_x_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_y_jth_bit(i, j)];
+                yield_constr.one(x_bit * (x_bit - P::ONES));
+                yield_constr.one(y_bit * (y_bit - P::ONES));
+
+                let and_bit = x_bit * y_bit;
+                let xor_bit = x_bit + y_bit - and_bit * two;
+                x = x * two + x_bit;
+                y = y * two + y_bit;
+                and = and * two + and_bit;
+                xor = xor * two + xor_bit;
+            }
+            yield_constr.one(x - vars.local_wires[self.wire_ith_input_x(i)]);
+            yield_constr.one(y - vars.local_wires[self.wire_ith_input_y(i)]);
+            yield_constr.one(and - vars.local_wires[self.wire_ith_output_and(i)]);
+            yield_constr.one(xor - vars.local_wires[self.wire_ith_output_xor(i)]);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct U32BitwiseGenerator<F: RichField + Extendable<D>, const D: usize> {
+    gate: U32BitwiseGate<F, D>,
+    row: usize,
+    i: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F>
+    for U32BitwiseGenerator<F, D>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        let local_target = |column| Target::wire(self.row, column);
+
+        vec![
+            local_target(self.gate.wire_ith_input_x(self.i)),
+            local_target(self.gate.wire_ith_input_y(self.i)),
+        ]
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) {
+        let local_wire = |column| Wire {
+            row: self.row,
+            column,
+        };
+
+        let get_local_wire = |column| witness.get_wire(local_wire(column));
+
+        let x = get_local_wire(self.gate.wire_ith_input_x(self.i)).to_canonical_u64();
+        let y = get_local_wire(self.gate.wire_ith_input_y(self.i)).to_canonical_u64();
+
+        out_buffer.set_wire(
+            local_wire(self.gate.wire_ith_output_and(self.i)),
+            F::from_canonical_u64(x & y),
+        );
+        out_buffer.set_wire(
+            local_wire(self.gate.wire_ith_output_xor(self.i)),
+            F::from_canonical_u64(x ^ y),
+        );
+
+        for j in 0..U32BitwiseGate::<F, D>::num_bits() {
+            out_buffer.set_wire(
+                local_wire(self.gate.wire_ith_x_jth_bit(self.i, j)),
+                F::from_bool((x >> j) & 1 == 1),
+            );
+            out_buffer.set_wire(
+                local_wire(self.gate.wire_ith_y_jth_bit(self.i, j)),
+                F::from_bool((y >> j) & 1 == 1),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn low_degree() {
+        test_low_degree::<GoldilocksField, _, 4>(U32BitwiseGate::<GoldilocksField, 4> {
+            num_ops: 3,
+            _phantom: PhantomData,
+        })
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        test_eval_fns::<F, C, _, D>(U32BitwiseGate::<GoldilocksField, D> {
+            num_ops: 3,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn get_wires<F: RichField + Extendable<D>, FF: From<F>, const D: usize>(
+        xs: &[u32],
+        ys: &[u32],
+        and_outputs: &[u32],
+    ) -> Vec<FF> {
+        let mut v0 = Vec::new();
+        let mut v1 = Vec::new();
+        for ((&x, &y), &and) in xs.iter().zip(ys).zip(and_outputs) {
+            v0.push(F::from_canonical_u32(x));
+            v0.push(F::from_canonical_u32(y));
+            v0.push(F::from_canonical_u32(and));
+            v0.push(F::from_canonical_u32(x ^ y));
+            for z in [x, y] {
+                for j in 0..32 {
+                    v1.push(F::from_bool((z >> j) & 1 == 1));
+                }
+            }
+        }
+        v0.iter().chain(v1.iter()).map(|&x| x.into()).collect()
+    }
+
+    #[test]
+    fn test_gate_constraint() {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type FF = <C as GenericConfig<D>>::FE;
+        const NUM_OPS: usize = 3;
+
+        let mut rng = OsRng;
+        let xs: Vec<u32> = (0..NUM_OPS).map(|_| rng.gen()).collect();
+        let ys: Vec<u32> = (0..NUM_OPS).map(|_| rng.gen()).collect();
+        let gate = U32BitwiseGate::<F, D> {
+            num_ops: NUM_OPS,
+            _phantom: PhantomData,
+        };
+
+        let eval = |and_outputs: &[u32]| {
+            let wires = get_wires::<F, FF, D>(&xs, &ys, and_outputs);
+            let vars = EvaluationVars {
+                local_constants: &[],
+                local_wires: &wires,
+                public_inputs_hash: &HashOut::rand(),
+            };
+            gate.eval_unfiltered(vars).iter().all(|x| x.is_zero())
+        };
+
+        let ands: Vec<_> = xs.iter().zip(&ys).map(|(&x, &y)| x & y).collect();
+        assert!(eval(&ands), "Gate constraints are not satisfied.");
+        let ors: Vec<_> = xs.iter().zip(&ys).map(|(&x, &y)| x | y).collect();
+        assert!(!eval(&ors), "Incorrect output should not pass constraints.");
+    }
+}
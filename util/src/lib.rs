@@ -236,6 +236,16 @@ pub fn reverse_index_bits_in_place<T>(arr: &mut [T]) {
     }
 }
 
+/// Transposes in place the square matrix of size `2^lb_size` by `2^lb_size` stored in row-major
+/// order in `arr`.
+pub fn transpose_in_place_square_matrix<T>(arr: &mut [T], lb_size: usize) {
+    assert_eq!(arr.len(), 1 << (2 * lb_size));
+    // SAFETY: `arr` holds exactly `2^lb_size` rows of `2^lb_size` elements.
+    unsafe {
+        transpose_in_place_square(arr, lb_size, lb_size, 0);
+    }
+}
+
 // Lookup table of 6-bit reverses.
 // NB: 2^6=64 bytes is a cacheline. A smaller table wastes cache space.
 #[rustfmt::skip]
@@ -335,6 +345,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transpose_in_place_square_matrix() {
+        for lb_size in [0, 1, 3, 6] {
+            let size = 1 << lb_size;
+            let mut matrix: Vec<usize> = (0..size * size).collect();
+            super::transpose_in_place_square_matrix(&mut matrix, lb_size);
+            for i in 0..size {
+                for j in 0..size {
+                    assert_eq!(matrix[i * size + j], j * size + i);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_log2_strict() {
         assert_eq!(log2_strict(1), 0);
@@ -403,7 +403,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     fn test_permutation_duplicates(size: usize) -> Result<()> {
@@ -433,7 +433,7 @@ mod tests {
         let data = builder.build::<C>();
         let proof = data.prove(pw)?;
 
-        data.verify(proof)
+        Ok(data.verify(proof)?)
     }
 
     fn test_permutation_bad(size: usize) -> Result<()> {